@@ -0,0 +1,222 @@
+//! Feeds arbitrary bytes to every `from_bytes` parser. Parsers must never panic and the payload
+//! they return must always be the tail of the input following the header.
+#![no_main]
+
+use core::mem::size_of;
+use std::fmt::Write;
+
+use libfuzzer_sys::fuzz_target;
+use tygress::header::application::{Dhcpv6, Dhcpv6Option, Dns, DnsName, Ntp, NtpTimestamp};
+use tygress::header::internet::{
+    Arp, DestinationOptions, HopByHop, Icmpv4, Icmpv4Error, Icmpv6, IpProtocol, Ipv4, Ipv6,
+    Ipv6ExtHeaders, NeighborAdvertisement, NeighborSolicitation, RouterAdvertisement,
+    RouterSolicitation, Routing,
+};
+use tygress::header::link::{EthernetII, Ieee802154, MplsStack};
+use tygress::header::transport::{Tcp, TcpOption, Udp};
+use tygress::header::tunnel::{Geneve, Vxlan};
+use tygress::header::HeaderInvalid;
+
+fn check_payload(data: &[u8], header_len: usize, payload: &[u8]) {
+    assert_eq!(header_len + payload.len(), data.len());
+    assert_eq!(&data[header_len..], payload);
+}
+
+fuzz_target!(|data: &[u8]| {
+    let mut out = String::new();
+
+    if let Ok((header, payload)) = EthernetII::from_bytes(data) {
+        check_payload(data, size_of::<EthernetII>(), payload);
+        let _ = write!(out, "{header}");
+    }
+
+    if let Ok((header, payload)) = Ieee802154::from_bytes(data) {
+        check_payload(data, header.header_len(), payload);
+        let _ = (header.src_pan_id(), header.dst_addr(), header.src_addr());
+        let _ = write!(out, "{header}");
+    }
+
+    if let Ok((stack, payload)) = MplsStack::from_bytes(data) {
+        check_payload(data, stack.len() * 4, payload);
+        for label in stack.labels() {
+            let _ = write!(out, "{label}");
+        }
+    }
+
+    if let Ok((header, payload)) = Arp::from_bytes(data) {
+        check_payload(data, size_of::<Arp>(), payload);
+        let _ = write!(out, "{header}");
+    }
+
+    if let Ok((header, payload)) = Ipv4::from_bytes(data) {
+        check_payload(data, header.header_len(), payload);
+        let _ = write!(out, "{header}");
+        for option in header.options() {
+            let _ = write!(out, "{option:?}");
+        }
+    }
+
+    // strict parsing only rejects more, and only short buffers are reported as truncated
+    match Ipv4::from_bytes_strict(data) {
+        Ok((header, payload)) => assert_eq!(Ipv4::from_bytes(data), Ok((header, payload))),
+        Err(HeaderInvalid::Truncated) => assert!(Ipv4::from_bytes(data).is_err()),
+        Err(_) => {}
+    }
+
+    if let Ok((header, payload)) = Icmpv4::from_bytes(data) {
+        check_payload(data, size_of::<Icmpv4>(), payload);
+        let _ = write!(out, "{header}");
+    }
+
+    if let Ok((error, payload)) = Icmpv4Error::from_bytes(data) {
+        let len = error.header().header_len() + error.data().len();
+        check_payload(data, len, payload);
+        let _ = write!(out, "{error}");
+    }
+
+    if let Ok((header, payload)) = Icmpv6::from_bytes(data) {
+        check_payload(data, size_of::<Icmpv6>(), payload);
+        let _ = write!(out, "{header}");
+        if let Ok(ra) = RouterAdvertisement::from_icmpv6(header, payload) {
+            let _ = write!(out, "{ra}");
+            for option in ra.options() {
+                let _ = write!(out, "{option:?}");
+            }
+        }
+        if let Ok(rs) = RouterSolicitation::from_icmpv6(header, payload) {
+            let _ = write!(out, "{rs}");
+        }
+        if let Ok(ns) = NeighborSolicitation::from_icmpv6(header, payload) {
+            let _ = write!(out, "{ns}");
+        }
+        if let Ok(na) = NeighborAdvertisement::from_icmpv6(header, payload) {
+            let _ = write!(out, "{na}");
+        }
+    }
+
+    if let Ok((header, payload)) = Ipv6::from_bytes(data) {
+        check_payload(data, size_of::<Ipv6>(), payload);
+        let _ = write!(out, "{header}");
+    }
+
+    if let Ok((header, payload)) = HopByHop::from_bytes(data) {
+        check_payload(data, header.header_len(), payload);
+        let _ = write!(out, "{header}");
+        for option in header.options() {
+            let _ = write!(out, "{option:?}");
+        }
+    }
+
+    if let Ok((header, payload)) = DestinationOptions::from_bytes(data) {
+        check_payload(data, header.header_len(), payload);
+        let _ = write!(out, "{header}");
+        for option in header.options() {
+            let _ = write!(out, "{option:?}");
+        }
+    }
+
+    if let Some((&next_header, payload)) = data.split_first() {
+        let mut headers = Ipv6ExtHeaders::new(IpProtocol::new(next_header), payload);
+        for header in headers.by_ref().flatten() {
+            let _ = write!(out, "{header}");
+        }
+        assert!(payload.ends_with(headers.payload()));
+    }
+
+    if let Ok((header, payload)) = Routing::from_bytes(data) {
+        check_payload(data, header.header_len(), payload);
+        let _ = write!(out, "{header}");
+        for addr in header.addresses() {
+            let _ = write!(out, "{addr}");
+        }
+    }
+
+    if let Ok((header, payload)) = Udp::from_bytes(data) {
+        check_payload(data, size_of::<Udp>(), payload);
+        let _ = write!(out, "{header}");
+    }
+
+    if let Ok((header, payload)) = Tcp::from_bytes(data) {
+        check_payload(data, header.header_len(), payload);
+        let _ = write!(out, "{header}");
+        for option in header.options() {
+            match option {
+                TcpOption::Sack(blocks) => blocks.for_each(drop),
+                option => {
+                    let _ = write!(out, "{option:?}");
+                }
+            }
+        }
+
+        // rewriting the MSS in place never changes the shape of the segment
+        let mut bytes = data.to_vec();
+        let (mut header, _) = Tcp::from_bytes_mut(&mut bytes).unwrap();
+        header.set_mss(1200);
+        let (header, payload) = Tcp::from_bytes(&bytes).unwrap();
+        check_payload(data, header.header_len(), payload);
+    }
+
+    if let Ok((header, payload)) = Vxlan::from_bytes(data) {
+        check_payload(data, size_of::<Vxlan>(), payload);
+        let _ = write!(out, "{header}");
+    }
+
+    if let Ok((header, payload)) = Geneve::from_bytes(data) {
+        check_payload(data, header.header_len(), payload);
+        let _ = write!(out, "{header}");
+        for option in header.options() {
+            let _ = write!(out, "{option:?}");
+        }
+    }
+
+    if let Ok((header, payload)) = Dns::from_bytes(data) {
+        check_payload(data, 12, payload);
+        let _ = write!(out, "{header}");
+        let mut questions = header.questions();
+        for question in questions.by_ref() {
+            let _ = write!(out, "{question}");
+        }
+        assert!(questions.offset() <= data.len());
+
+        // names may start anywhere a resource record points, so decode one at every offset
+        for offset in 0..data.len() {
+            if let Ok((name, end)) = DnsName::from_message(data, offset) {
+                assert!(offset < end && end <= data.len());
+                let len: usize = name.labels().map(|label| label.len() + 1).sum();
+                assert!(len < DnsName::MAX_LEN);
+                let _ = write!(out, "{name}");
+            }
+        }
+    }
+
+    if let Ok((header, payload)) = Ntp::from_bytes(data) {
+        check_payload(data, size_of::<Ntp>(), payload);
+        let _ = write!(out, "{header}");
+        for timestamp in [
+            header.reference_timestamp(),
+            header.origin_timestamp(),
+            header.receive_timestamp(),
+            header.transmit_timestamp(),
+        ] {
+            if let Some(duration) = timestamp.to_unix_duration() {
+                let converted = NtpTimestamp::from_unix_duration(duration).unwrap();
+                assert_eq!(converted.seconds(), timestamp.seconds());
+            }
+        }
+    }
+
+    if let Ok((header, payload)) = Dhcpv6::from_bytes(data) {
+        check_payload(data, data.len(), payload);
+        let _ = write!(out, "{header}");
+        for option in header.options() {
+            match option {
+                Dhcpv6Option::IaNa(ia_na) => ia_na.options().for_each(drop),
+                Dhcpv6Option::IaAddr(ia_addr) => ia_addr.options().for_each(drop),
+                Dhcpv6Option::DnsServers(servers) => servers.for_each(drop),
+                option => {
+                    let _ = write!(out, "{option:?}");
+                }
+            }
+        }
+    }
+});
@@ -0,0 +1,603 @@
+//! [`Dhcpv6`] message
+//!
+//! [`Dhcpv6`] message exchanged between an IPv6 client and a server to configure addresses and
+//! other parameters, carrying [`Dhcpv6Option`]s. Messages are written with [`Dhcpv6Builder`].
+use core::fmt;
+
+use crate::header::error::HeaderTruncated;
+use crate::header::internet::Ipv6Addr;
+use crate::header::primitive::{non_exhaustive_enum, U32, U8};
+use crate::header::utils::{as_header, split_at};
+use crate::header::Header;
+
+/// A DHCPv6 client/server message. [Read more][RFC 8415]
+///
+/// DHCPv6 is carried over UDP. Clients send from port [`Dhcpv6::CLIENT_PORT`] to the
+/// All_DHCP_Relay_Agents_and_Servers multicast group `ff02::1:2`, port [`Dhcpv6::SERVER_PORT`]. A
+/// client solicits servers, requests addresses from the server that advertised the best offer,
+/// and receives them in a reply. Addresses are held in Identity Associations (IAs), each a set of
+/// addresses with common renewal times. Relay agent messages have a different format and are not
+/// supported.
+///
+/// Options span the rest of the message, so the returned payload is always empty.
+///
+/// [RFC 8415]: https://tools.ietf.org/html/rfc8415#section-8
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct Dhcpv6<'a> {
+    header: &'a Dhcpv6Header,
+    options: &'a [u8],
+}
+
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[repr(C)]
+struct Dhcpv6Header {
+    msg_type: U8,
+    transaction_id: [u8; 3],
+}
+
+impl<'a> Dhcpv6<'a> {
+    /// The UDP port clients listen on.
+    pub const CLIENT_PORT: u16 = 546;
+    /// The UDP port servers and relay agents listen on.
+    pub const SERVER_PORT: u16 = 547;
+    /// The All_DHCP_Relay_Agents_and_Servers link-scoped multicast address, `ff02::1:2`.
+    pub const ALL_SERVERS: Ipv6Addr =
+        Ipv6Addr::new([0xff, 0x02, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x01, 0, 0x02]);
+
+    /// Returns an immutable view of `bytes` as a DHCPv6 message or an error if the size or
+    /// contents do not represent a valid DHCPv6 client/server message.
+    #[inline]
+    pub const fn from_bytes(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), HeaderTruncated> {
+        let (header, options) = match as_header!(Dhcpv6Header, bytes) {
+            Ok(v) => v,
+            Err(e) => return Err(e),
+        };
+
+        if matches!(
+            Dhcpv6MessageType::new(header.msg_type.get()),
+            Dhcpv6MessageType::RelayForward | Dhcpv6MessageType::RelayReply
+        ) {
+            return Err(HeaderTruncated);
+        }
+
+        Ok((Dhcpv6 { header, options }, &[]))
+    }
+
+    /// Returns the type of message.
+    #[inline]
+    pub const fn message_type(&self) -> Dhcpv6MessageType {
+        Dhcpv6MessageType::new(self.header.msg_type.get())
+    }
+
+    /// Returns the `u24` transaction identifier chosen by the client and copied into replies.
+    #[inline]
+    pub const fn transaction_id(&self) -> u32 {
+        let id = self.header.transaction_id;
+        u32::from_be_bytes([0, id[0], id[1], id[2]])
+    }
+
+    /// Returns iterator of [`Dhcpv6Option`].
+    #[inline]
+    pub const fn options(&self) -> Dhcpv6Options<'a> {
+        Dhcpv6Options {
+            options: self.options,
+        }
+    }
+}
+
+impl<'a> Header<'a> for Dhcpv6<'a> {
+    #[inline]
+    fn from_bytes(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), HeaderTruncated> {
+        Dhcpv6::from_bytes(bytes)
+    }
+}
+
+impl<'a> fmt::Display for Dhcpv6<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "DHCPv6 {} xid={:#08x}",
+            self.message_type(),
+            self.transaction_id()
+        )
+    }
+}
+
+non_exhaustive_enum! {
+/// A DHCPv6 message type. [Read more][IANA]
+///
+/// A complete list of message types is maintained by the [IANA].
+///
+/// [IANA]: https://www.iana.org/assignments/dhcpv6-parameters/dhcpv6-parameters.xhtml#dhcpv6-parameters-1
+pub enum Dhcpv6MessageType(u8) {
+    Solicit = 1,
+    Advertise = 2,
+    Request = 3,
+    Confirm = 4,
+    Renew = 5,
+    Rebind = 6,
+    Reply = 7,
+    Release = 8,
+    Decline = 9,
+    Reconfigure = 10,
+    InformationRequest = 11,
+    RelayForward = 12,
+    RelayReply = 13,
+}
+}
+
+/// Iterator of [`Dhcpv6Option`].
+///
+/// Iteration stops early if an option claims more bytes than remain in the message.
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct Dhcpv6Options<'a> {
+    options: &'a [u8],
+}
+
+impl<'a> Iterator for Dhcpv6Options<'a> {
+    type Item = Dhcpv6Option<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Some((&[c0, c1, l0, l1], rest)) = self.options.split_first_chunk::<4>() else {
+            self.options = &[];
+            return None;
+        };
+        let Some((data, rest)) = split_at(rest, u16::from_be_bytes([l0, l1]) as usize) else {
+            self.options = &[];
+            return None;
+        };
+        self.options = rest;
+
+        let code = u16::from_be_bytes([c0, c1]);
+        Some(match (code, data) {
+            (Dhcpv6Option::CLIENT_ID, duid) => Dhcpv6Option::ClientId(duid),
+            (Dhcpv6Option::SERVER_ID, duid) => Dhcpv6Option::ServerId(duid),
+            (Dhcpv6Option::IA_NA, data) => match IaNa::from_data(data) {
+                Ok(ia_na) => Dhcpv6Option::IaNa(ia_na),
+                Err(_) => Dhcpv6Option::Unknown { code, data },
+            },
+            (Dhcpv6Option::IA_ADDR, data) => match IaAddr::from_data(data) {
+                Ok(ia_addr) => Dhcpv6Option::IaAddr(ia_addr),
+                Err(_) => Dhcpv6Option::Unknown { code, data },
+            },
+            (Dhcpv6Option::ORO, data) if data.len() % 2 == 0 => Dhcpv6Option::OptionRequest(data),
+            (Dhcpv6Option::ELAPSED_TIME, &[a, b]) => {
+                Dhcpv6Option::ElapsedTime(u16::from_be_bytes([a, b]))
+            }
+            (Dhcpv6Option::DNS_SERVERS, data) if data.len() % 16 == 0 => {
+                Dhcpv6Option::DnsServers(Dhcpv6Addrs { addrs: data })
+            }
+            (code, data) => Dhcpv6Option::Unknown { code, data },
+        })
+    }
+}
+
+/// An option carried by a [`Dhcpv6`] message. [Read more][RFC 8415]
+///
+/// [RFC 8415]: https://tools.ietf.org/html/rfc8415#section-21
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[non_exhaustive]
+pub enum Dhcpv6Option<'a> {
+    /// The DHCP Unique Identifier (DUID) of the client.
+    ClientId(&'a [u8]),
+    /// The DHCP Unique Identifier (DUID) of the server.
+    ServerId(&'a [u8]),
+    /// An Identity Association for Non-temporary Addresses.
+    IaNa(IaNa<'a>),
+    /// An address of an Identity Association.
+    IaAddr(IaAddr<'a>),
+    /// Codes of the options requested by the client, each a network endian `u16`.
+    OptionRequest(&'a [u8]),
+    /// Time since the client began the exchange in hundredths of a second.
+    ElapsedTime(u16),
+    /// Recursive DNS servers. [Read more][RFC 3646]
+    ///
+    /// [RFC 3646]: https://tools.ietf.org/html/rfc3646#section-3
+    DnsServers(Dhcpv6Addrs<'a>),
+    /// An unrecognized option, or a recognized option with an unexpected length.
+    Unknown { code: u16, data: &'a [u8] },
+}
+
+impl<'a> Dhcpv6Option<'a> {
+    const CLIENT_ID: u16 = 1;
+    const SERVER_ID: u16 = 2;
+    const IA_NA: u16 = 3;
+    const IA_ADDR: u16 = 5;
+    const ORO: u16 = 6;
+    const ELAPSED_TIME: u16 = 8;
+    const DNS_SERVERS: u16 = 23;
+}
+
+/// An Identity Association for Non-temporary Addresses (IA_NA). [Read more][RFC 8415]
+///
+/// Groups the addresses assigned to one interface of a client, carried as [`Dhcpv6Option::IaAddr`]
+/// options within it.
+///
+/// [RFC 8415]: https://tools.ietf.org/html/rfc8415#section-21.4
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct IaNa<'a> {
+    fixed: &'a IaNaFixed,
+    options: &'a [u8],
+}
+
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[repr(C)]
+struct IaNaFixed {
+    iaid: U32,
+    t1: U32,
+    t2: U32,
+}
+
+impl<'a> IaNa<'a> {
+    const fn from_data(data: &'a [u8]) -> Result<Self, HeaderTruncated> {
+        match as_header!(IaNaFixed, data) {
+            Ok((fixed, options)) => Ok(IaNa { fixed, options }),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Returns the identifier of the IA chosen by the client.
+    #[inline]
+    pub const fn iaid(&self) -> u32 {
+        self.fixed.iaid.get()
+    }
+
+    /// Returns the time in seconds at which the client should renew its addresses with the server
+    /// that assigned them.
+    #[inline]
+    pub const fn t1(&self) -> u32 {
+        self.fixed.t1.get()
+    }
+
+    /// Returns the time in seconds at which the client should renew its addresses with any
+    /// server.
+    #[inline]
+    pub const fn t2(&self) -> u32 {
+        self.fixed.t2.get()
+    }
+
+    /// Returns iterator of the [`Dhcpv6Option`]s within the IA.
+    #[inline]
+    pub const fn options(&self) -> Dhcpv6Options<'a> {
+        Dhcpv6Options {
+            options: self.options,
+        }
+    }
+}
+
+/// An address of an Identity Association (IA Address). [Read more][RFC 8415]
+///
+/// [RFC 8415]: https://tools.ietf.org/html/rfc8415#section-21.6
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct IaAddr<'a> {
+    fixed: &'a IaAddrFixed,
+    options: &'a [u8],
+}
+
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[repr(C)]
+struct IaAddrFixed {
+    addr: Ipv6Addr,
+    preferred_lifetime: U32,
+    valid_lifetime: U32,
+}
+
+impl<'a> IaAddr<'a> {
+    const fn from_data(data: &'a [u8]) -> Result<Self, HeaderTruncated> {
+        match as_header!(IaAddrFixed, data) {
+            Ok((fixed, options)) => Ok(IaAddr { fixed, options }),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Returns the address.
+    #[inline]
+    pub const fn addr(&self) -> Ipv6Addr {
+        self.fixed.addr
+    }
+
+    /// Returns the time in seconds the address remains preferred.
+    #[inline]
+    pub const fn preferred_lifetime(&self) -> u32 {
+        self.fixed.preferred_lifetime.get()
+    }
+
+    /// Returns the time in seconds the address is valid.
+    #[inline]
+    pub const fn valid_lifetime(&self) -> u32 {
+        self.fixed.valid_lifetime.get()
+    }
+
+    /// Returns iterator of the [`Dhcpv6Option`]s within the IA Address.
+    #[inline]
+    pub const fn options(&self) -> Dhcpv6Options<'a> {
+        Dhcpv6Options {
+            options: self.options,
+        }
+    }
+}
+
+/// Iterator of the [`Ipv6Addr`]s of an option, such as [`Dhcpv6Option::DnsServers`].
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct Dhcpv6Addrs<'a> {
+    addrs: &'a [u8],
+}
+
+impl<'a> Iterator for Dhcpv6Addrs<'a> {
+    type Item = Ipv6Addr;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (addr, rest) = self.addrs.split_first_chunk::<16>()?;
+        self.addrs = rest;
+        Some(Ipv6Addr::new(*addr))
+    }
+}
+
+/// Writes a [`Dhcpv6`] message into a buffer, one option at a time.
+///
+/// Every method returns an error if the buffer is too small, leaving the message unfinished.
+#[derive(Debug)]
+pub struct Dhcpv6Builder<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl<'a> Dhcpv6Builder<'a> {
+    /// Starts a message of `msg_type` with the low 24 bits of `transaction_id` in `buf`.
+    pub fn new(
+        buf: &'a mut [u8],
+        msg_type: Dhcpv6MessageType,
+        transaction_id: u32,
+    ) -> Result<Self, HeaderTruncated> {
+        let header = buf.get_mut(..4).ok_or(HeaderTruncated)?;
+        let id = transaction_id.to_be_bytes();
+        header.copy_from_slice(&[msg_type.get(), id[1], id[2], id[3]]);
+        Ok(Dhcpv6Builder { buf, len: 4 })
+    }
+
+    /// Appends an option with `code` and the concatenation of `data` as its data.
+    pub fn option(&mut self, code: u16, data: &[&[u8]]) -> Result<&mut Self, HeaderTruncated> {
+        let data_len: usize = data.iter().map(|data| data.len()).sum();
+        let len = u16::try_from(data_len).map_err(|_| HeaderTruncated)?;
+        let option = self
+            .buf
+            .get_mut(self.len..self.len + 4 + data_len)
+            .ok_or(HeaderTruncated)?;
+
+        option[..2].copy_from_slice(&code.to_be_bytes());
+        option[2..4].copy_from_slice(&len.to_be_bytes());
+        let mut pos = 4;
+        for data in data {
+            option[pos..pos + data.len()].copy_from_slice(data);
+            pos += data.len();
+        }
+        self.len += 4 + data_len;
+        Ok(self)
+    }
+
+    /// Appends a Client Identifier option carrying `duid`.
+    #[inline]
+    pub fn client_id(&mut self, duid: &[u8]) -> Result<&mut Self, HeaderTruncated> {
+        self.option(Dhcpv6Option::CLIENT_ID, &[duid])
+    }
+
+    /// Appends a Server Identifier option carrying `duid`.
+    #[inline]
+    pub fn server_id(&mut self, duid: &[u8]) -> Result<&mut Self, HeaderTruncated> {
+        self.option(Dhcpv6Option::SERVER_ID, &[duid])
+    }
+
+    /// Appends an Elapsed Time option in hundredths of a second.
+    #[inline]
+    pub fn elapsed_time(&mut self, elapsed: u16) -> Result<&mut Self, HeaderTruncated> {
+        self.option(Dhcpv6Option::ELAPSED_TIME, &[&elapsed.to_be_bytes()])
+    }
+
+    /// Appends an Option Request option asking for the options with `codes`.
+    pub fn option_request(&mut self, codes: &[u16]) -> Result<&mut Self, HeaderTruncated> {
+        let len = codes
+            .len()
+            .checked_mul(2)
+            .and_then(|len| u16::try_from(len).ok())
+            .ok_or(HeaderTruncated)?;
+        let start = self.len;
+        self.option(Dhcpv6Option::ORO, &[])?;
+        for code in codes {
+            let bytes = self
+                .buf
+                .get_mut(self.len..self.len + 2)
+                .ok_or(HeaderTruncated)?;
+            bytes.copy_from_slice(&code.to_be_bytes());
+            self.len += 2;
+        }
+        self.buf[start + 2..start + 4].copy_from_slice(&len.to_be_bytes());
+        Ok(self)
+    }
+
+    /// Appends an IA_NA option with an IA Address option for each of `addrs`, given as the
+    /// address and its preferred and valid lifetimes. A client soliciting any address passes no
+    /// addresses.
+    pub fn ia_na(
+        &mut self,
+        iaid: u32,
+        t1: u32,
+        t2: u32,
+        addrs: &[(Ipv6Addr, u32, u32)],
+    ) -> Result<&mut Self, HeaderTruncated> {
+        let start = self.len;
+        self.option(
+            Dhcpv6Option::IA_NA,
+            &[&iaid.to_be_bytes(), &t1.to_be_bytes(), &t2.to_be_bytes()],
+        )?;
+        for (addr, preferred, valid) in addrs {
+            self.option(
+                Dhcpv6Option::IA_ADDR,
+                &[
+                    addr.as_bytes(),
+                    &preferred.to_be_bytes(),
+                    &valid.to_be_bytes(),
+                ],
+            )?;
+        }
+        let len = u16::try_from(self.len - start - 4).map_err(|_| HeaderTruncated)?;
+        self.buf[start + 2..start + 4].copy_from_slice(&len.to_be_bytes());
+        Ok(self)
+    }
+
+    /// Returns the length of the message written so far.
+    #[inline]
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DUID: [u8; 10] = [0x00, 0x03, 0x00, 0x01, 0x02, 0x00, 0x00, 0x00, 0x00, 0x01];
+
+    #[test]
+    fn short_header() {
+        let bytes = [0x01, 0x00, 0x00];
+        assert_eq!(Dhcpv6::from_bytes(&bytes).unwrap_err(), HeaderTruncated);
+
+        // relay messages are not supported
+        let bytes = [0x0c, 0x00, 0x00, 0x00];
+        assert_eq!(Dhcpv6::from_bytes(&bytes).unwrap_err(), HeaderTruncated);
+    }
+
+    #[test]
+    fn solicit_round_trip() {
+        let mut buf = [0; 64];
+        let mut builder =
+            Dhcpv6Builder::new(&mut buf, Dhcpv6MessageType::Solicit, 0x00ab_cdef).unwrap();
+        builder
+            .client_id(&DUID)
+            .unwrap()
+            .elapsed_time(0)
+            .unwrap()
+            .option_request(&[23])
+            .unwrap()
+            .ia_na(1, 0, 0, &[])
+            .unwrap();
+        let len = builder.len();
+        assert_eq!(len, 4 + 14 + 6 + 6 + 16);
+
+        let (message, payload) = Dhcpv6::from_bytes(&buf[..len]).unwrap();
+        assert!(payload.is_empty());
+        assert_eq!(message.message_type(), Dhcpv6MessageType::Solicit);
+        assert_eq!(message.transaction_id(), 0xabcdef);
+        assert_eq!(message.to_string(), "DHCPv6 Solicit xid=0xabcdef");
+
+        let mut options = message.options();
+        assert_eq!(options.next(), Some(Dhcpv6Option::ClientId(&DUID)));
+        assert_eq!(options.next(), Some(Dhcpv6Option::ElapsedTime(0)));
+        assert_eq!(options.next(), Some(Dhcpv6Option::OptionRequest(&[0, 23])));
+        let Some(Dhcpv6Option::IaNa(ia_na)) = options.next() else {
+            panic!("expected IA_NA");
+        };
+        assert_eq!(ia_na.iaid(), 1);
+        assert_eq!(ia_na.options().next(), None);
+        assert_eq!(options.next(), None);
+
+        assert_eq!(
+            Dhcpv6Builder::new(&mut buf[..20], Dhcpv6MessageType::Solicit, 1)
+                .unwrap()
+                .client_id(&DUID)
+                .unwrap()
+                .elapsed_time(0)
+                .unwrap_err(),
+            HeaderTruncated
+        );
+    }
+
+    #[test]
+    fn advertise_ia_na() {
+        let bytes = [
+            0x02, 0xab, 0xcd, 0xef, // Advertise
+            0x00, 0x02, 0x00, 0x04, 0x00, 0x03, 0x00, 0x01, // Server ID
+            0x00, 0x03, 0x00, 0x28, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x0e, 0x10, 0x00, 0x00,
+            0x15, 0x18, // IA_NA iaid=1, t1=3600, t2=5400
+            0x00, 0x05, 0x00, 0x18, 0x20, 0x01, 0x0d, 0xb8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x10, 0x01, 0x00, 0x00, 0x1c, 0x20, 0x00, 0x00, 0x2a,
+            0x30, // IA Address 2001:db8::1001, preferred=7200, valid=10800
+            0x00, 0x17, 0x00, 0x10, 0x20, 0x01, 0x0d, 0xb8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x35, // DNS servers
+        ];
+
+        let (message, _) = Dhcpv6::from_bytes(&bytes).unwrap();
+        assert_eq!(message.message_type(), Dhcpv6MessageType::Advertise);
+
+        let mut options = message.options();
+        assert_eq!(
+            options.next(),
+            Some(Dhcpv6Option::ServerId(&[0x00, 0x03, 0x00, 0x01]))
+        );
+        let Some(Dhcpv6Option::IaNa(ia_na)) = options.next() else {
+            panic!("expected IA_NA");
+        };
+        assert_eq!(ia_na.iaid(), 1);
+        assert_eq!(ia_na.t1(), 3600);
+        assert_eq!(ia_na.t2(), 5400);
+
+        let mut ia_options = ia_na.options();
+        let Some(Dhcpv6Option::IaAddr(ia_addr)) = ia_options.next() else {
+            panic!("expected IA Address");
+        };
+        assert_eq!(ia_addr.addr().to_string(), "2001:db8::1001");
+        assert_eq!(ia_addr.preferred_lifetime(), 7200);
+        assert_eq!(ia_addr.valid_lifetime(), 10800);
+        assert_eq!(ia_options.next(), None);
+
+        let Some(Dhcpv6Option::DnsServers(servers)) = options.next() else {
+            panic!("expected DNS servers");
+        };
+        assert_eq!(
+            servers.map(|addr| addr.to_string()).collect::<Vec<_>>(),
+            ["2001:db8::35"]
+        );
+        assert_eq!(options.next(), None);
+    }
+
+    #[test]
+    fn option_overruns_message() {
+        let bytes = [0x01, 0x00, 0x00, 0x01, 0x00, 0x01, 0x00, 0x04, 0x00];
+        let (message, _) = Dhcpv6::from_bytes(&bytes).unwrap();
+        assert_eq!(message.options().next(), None);
+    }
+
+    #[test]
+    fn short_ia_na() {
+        // IA_NA missing its T2
+        let bytes = [
+            0x01, 0x00, 0x00, 0x01, 0x00, 0x03, 0x00, 0x08, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00,
+            0x0e, 0x10,
+        ];
+        let (message, _) = Dhcpv6::from_bytes(&bytes).unwrap();
+        assert_eq!(
+            message.options().next(),
+            Some(Dhcpv6Option::Unknown {
+                code: 3,
+                data: &bytes[8..]
+            })
+        );
+    }
+
+    #[test]
+    fn option_too_long() {
+        let mut buf = vec![0; 70_000];
+        let mut builder = Dhcpv6Builder::new(&mut buf, Dhcpv6MessageType::Solicit, 1).unwrap();
+        assert!(builder.option_request(&[23; 32_767]).is_ok());
+        assert_eq!(
+            builder.option_request(&[23; 32_768]).unwrap_err(),
+            HeaderTruncated
+        );
+
+        let mut buf = vec![0; 70_000];
+        let mut builder = Dhcpv6Builder::new(&mut buf, Dhcpv6MessageType::Solicit, 1).unwrap();
+        let addrs = [(Dhcpv6::ALL_SERVERS, 0, 0); 2_341];
+        assert_eq!(builder.ia_na(1, 0, 0, &addrs).unwrap_err(), HeaderTruncated);
+    }
+}
@@ -0,0 +1,577 @@
+//! [`Dns`] header
+//!
+//! [`Dns`] message header followed by the question section and the resource records of a
+//! response. Names in the message are parsed as [`DnsName`]s.
+use core::fmt;
+
+use crate::header::error::HeaderTruncated;
+use crate::header::primitive::{non_exhaustive_enum, U16};
+use crate::header::utils::as_header;
+use crate::header::Header;
+
+/// A Domain Name System (DNS) message header. [Read more][RFC 1035]
+///
+/// DNS is usually carried over UDP, port [`Dns::PORT`]. The header identifies a query and its
+/// response, and counts the entries of the four sections that follow: questions, answers,
+/// authority records and additional records. Names in a message may be compressed by pointing back
+/// to a name earlier in the message, so the header keeps a view of the whole message.
+///
+/// [RFC 1035]: https://tools.ietf.org/html/rfc1035#section-4.1
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct Dns<'a> {
+    header: &'a DnsHeader,
+    message: &'a [u8],
+}
+
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[repr(C)]
+struct DnsHeader {
+    id: U16,
+    flags: U16,
+    qdcount: U16,
+    ancount: U16,
+    nscount: U16,
+    arcount: U16,
+}
+
+impl<'a> Dns<'a> {
+    /// The well known UDP port of DNS.
+    pub const PORT: u16 = 53;
+
+    const FLAG_QR: u16 = 0b1000_0000_0000_0000;
+    const FLAG_AA: u16 = 0b0000_0100_0000_0000;
+    const FLAG_TC: u16 = 0b0000_0010_0000_0000;
+    const FLAG_RD: u16 = 0b0000_0001_0000_0000;
+    const FLAG_RA: u16 = 0b0000_0000_1000_0000;
+
+    /// Returns an immutable view of `bytes` as a DNS header followed by the sections of the
+    /// message or an error if the size does not represent a valid DNS header.
+    #[inline]
+    pub const fn from_bytes(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), HeaderTruncated> {
+        let (header, sections) = match as_header!(DnsHeader, bytes) {
+            Ok(v) => v,
+            Err(e) => return Err(e),
+        };
+
+        Ok((
+            Dns {
+                header,
+                message: bytes,
+            },
+            sections,
+        ))
+    }
+
+    /// Returns the identifier copied from a query into its response.
+    #[inline]
+    pub const fn id(&self) -> u16 {
+        self.header.id.get()
+    }
+
+    /// Returns `true` if the message is a response rather than a query.
+    #[inline]
+    pub const fn is_response(&self) -> bool {
+        self.header.flags.get() & Self::FLAG_QR != 0
+    }
+
+    /// Returns the kind of query.
+    #[inline]
+    pub const fn opcode(&self) -> DnsOpcode {
+        DnsOpcode::new((self.header.flags.get() >> 11) as u8 & 0b1111)
+    }
+
+    /// Returns `true` if the responding server is an authority for the name in question.
+    #[inline]
+    pub const fn is_authoritative(&self) -> bool {
+        self.header.flags.get() & Self::FLAG_AA != 0
+    }
+
+    /// Returns `true` if the message was truncated to fit the transport.
+    #[inline]
+    pub const fn is_truncated(&self) -> bool {
+        self.header.flags.get() & Self::FLAG_TC != 0
+    }
+
+    /// Returns `true` if the query asks the server to resolve the name recursively.
+    #[inline]
+    pub const fn recursion_desired(&self) -> bool {
+        self.header.flags.get() & Self::FLAG_RD != 0
+    }
+
+    /// Returns `true` if the responding server supports recursive queries.
+    #[inline]
+    pub const fn recursion_available(&self) -> bool {
+        self.header.flags.get() & Self::FLAG_RA != 0
+    }
+
+    /// Returns the response code of the message.
+    #[inline]
+    pub const fn rcode(&self) -> DnsRcode {
+        DnsRcode::new(self.header.flags.get() as u8 & 0b1111)
+    }
+
+    /// Returns the number of entries in the question section.
+    #[inline]
+    pub const fn question_count(&self) -> u16 {
+        self.header.qdcount.get()
+    }
+
+    /// Returns the number of resource records in the answer section.
+    #[inline]
+    pub const fn answer_count(&self) -> u16 {
+        self.header.ancount.get()
+    }
+
+    /// Returns the number of resource records in the authority section.
+    #[inline]
+    pub const fn authority_count(&self) -> u16 {
+        self.header.nscount.get()
+    }
+
+    /// Returns the number of resource records in the additional section.
+    #[inline]
+    pub const fn additional_count(&self) -> u16 {
+        self.header.arcount.get()
+    }
+
+    /// Returns the whole message, including the header. Offsets of names are relative to it.
+    #[inline]
+    pub const fn message(&self) -> &'a [u8] {
+        self.message
+    }
+
+    /// Returns iterator of [`DnsQuestion`].
+    #[inline]
+    pub const fn questions(&self) -> DnsQuestions<'a> {
+        DnsQuestions {
+            message: self.message,
+            offset: core::mem::size_of::<DnsHeader>(),
+            remaining: self.question_count(),
+        }
+    }
+}
+
+impl<'a> Header<'a> for Dns<'a> {
+    #[inline]
+    fn from_bytes(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), HeaderTruncated> {
+        Dns::from_bytes(bytes)
+    }
+}
+
+impl<'a> fmt::Display for Dns<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "DNS id={} {} opcode={} rcode={} qd={} an={} ns={} ar={}",
+            self.id(),
+            if self.is_response() {
+                "response"
+            } else {
+                "query"
+            },
+            self.opcode(),
+            self.rcode(),
+            self.question_count(),
+            self.answer_count(),
+            self.authority_count(),
+            self.additional_count()
+        )
+    }
+}
+
+non_exhaustive_enum! {
+/// A DNS opcode. [Read more][IANA]
+///
+/// Identifies the kind of query in a message. A complete list of opcodes is maintained by the
+/// [IANA].
+///
+/// [IANA]: https://www.iana.org/assignments/dns-parameters/dns-parameters.xhtml#dns-parameters-5
+pub enum DnsOpcode(u8) {
+    Query = 0,
+    InverseQuery = 1,
+    Status = 2,
+    Notify = 4,
+    Update = 5,
+}
+}
+
+non_exhaustive_enum! {
+/// A DNS response code. [Read more][IANA]
+///
+/// Reports the outcome of a query. A complete list of response codes is maintained by the
+/// [IANA].
+///
+/// [IANA]: https://www.iana.org/assignments/dns-parameters/dns-parameters.xhtml#dns-parameters-6
+pub enum DnsRcode(u8) {
+    NoError = 0,
+    FormatError = 1,
+    ServerFailure = 2,
+    NameError = 3,
+    NotImplemented = 4,
+    Refused = 5,
+}
+}
+
+non_exhaustive_enum! {
+/// A DNS resource record type. [Read more][IANA]
+///
+/// Identifies the kind of data asked for by a question or held by a resource record. A complete
+/// list of types is maintained by the [IANA].
+///
+/// [IANA]: https://www.iana.org/assignments/dns-parameters/dns-parameters.xhtml#dns-parameters-4
+pub enum DnsType(u16) {
+    A = 1,
+    NS = 2,
+    CNAME = 5,
+    SOA = 6,
+    PTR = 12,
+    MX = 15,
+    TXT = 16,
+    AAAA = 28,
+    SRV = 33,
+    OPT = 41,
+    ANY = 255,
+}
+}
+
+non_exhaustive_enum! {
+/// A DNS class. [Read more][IANA]
+///
+/// Identifies the namespace of a question or resource record, almost always [`DnsClass::IN`].
+///
+/// [IANA]: https://www.iana.org/assignments/dns-parameters/dns-parameters.xhtml#dns-parameters-2
+pub enum DnsClass(u16) {
+    IN = 1,
+    CH = 3,
+    HS = 4,
+    ANY = 255,
+}
+}
+
+/// Iterator of [`DnsQuestion`].
+///
+/// Iteration stops early if a question is malformed or overruns the message.
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct DnsQuestions<'a> {
+    message: &'a [u8],
+    offset: usize,
+    remaining: u16,
+}
+
+impl<'a> DnsQuestions<'a> {
+    /// Returns the offset in the message just past the questions iterated so far. Once iteration
+    /// completes, this is the start of the answer section.
+    #[inline]
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl<'a> Iterator for DnsQuestions<'a> {
+    type Item = DnsQuestion<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let parsed = DnsName::from_message(self.message, self.offset)
+            .ok()
+            .and_then(|(name, end)| {
+                let &[t0, t1, c0, c1] = self.message.get(end..end + 4)? else {
+                    return None;
+                };
+                Some((name, t0, t1, c0, c1, end + 4))
+            });
+        let Some((name, t0, t1, c0, c1, end)) = parsed else {
+            self.remaining = 0;
+            return None;
+        };
+        self.offset = end;
+        self.remaining -= 1;
+
+        Some(DnsQuestion {
+            name,
+            ty: DnsType::new(u16::from_be_bytes([t0, t1])),
+            class: DnsClass::new(u16::from_be_bytes([c0, c1])),
+        })
+    }
+}
+
+/// An entry of the question section of a [`Dns`] message. [Read more][RFC 1035]
+///
+/// [RFC 1035]: https://tools.ietf.org/html/rfc1035#section-4.1.2
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub struct DnsQuestion<'a> {
+    name: DnsName<'a>,
+    ty: DnsType,
+    class: DnsClass,
+}
+
+impl<'a> DnsQuestion<'a> {
+    /// Returns the name in question.
+    #[inline]
+    pub const fn name(&self) -> DnsName<'a> {
+        self.name
+    }
+
+    /// Returns the type of record asked for.
+    #[inline]
+    pub const fn question_type(&self) -> DnsType {
+        self.ty
+    }
+
+    /// Returns the class of record asked for.
+    #[inline]
+    pub const fn question_class(&self) -> DnsClass {
+        self.class
+    }
+}
+
+impl<'a> fmt::Display for DnsQuestion<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {}", self.name, self.class, self.ty)
+    }
+}
+
+/// A domain name in a [`Dns`] message. [Read more][RFC 1035]
+///
+/// A name is a sequence of length prefixed labels ending in the empty root label. To save space,
+/// the tail of a name may be replaced by a pointer to a name earlier in the message, so a name is
+/// a view of the whole message starting at the offset of its first label.
+///
+/// [RFC 1035]: https://tools.ietf.org/html/rfc1035#section-4.1.4
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub struct DnsName<'a> {
+    message: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> DnsName<'a> {
+    /// The maximum length of a name on the wire in bytes.
+    pub const MAX_LEN: usize = 255;
+
+    const POINTER: u8 = 0b1100_0000;
+
+    /// Returns a view of the name at `offset` of `message` and the offset just past it, where the
+    /// next field of a question or resource record begins. Returns an error if the name overruns
+    /// the message, is too long, or has a compression pointer that does not point strictly
+    /// backwards.
+    pub fn from_message(
+        message: &'a [u8],
+        offset: usize,
+    ) -> Result<(Self, usize), HeaderTruncated> {
+        let mut pos = offset;
+        let mut segment_start = offset;
+        let mut end = None;
+        let mut len = 0;
+
+        loop {
+            let &byte = message.get(pos).ok_or(HeaderTruncated)?;
+            match byte & Self::POINTER {
+                0 if byte == 0 => {
+                    let name = DnsName { message, offset };
+                    return Ok((name, end.unwrap_or(pos + 1)));
+                }
+                0 => {
+                    len += 1 + byte as usize;
+                    pos += 1 + byte as usize;
+                    if len >= Self::MAX_LEN || pos > message.len() {
+                        return Err(HeaderTruncated);
+                    }
+                }
+                Self::POINTER => {
+                    let &low = message.get(pos + 1).ok_or(HeaderTruncated)?;
+                    let target = u16::from_be_bytes([byte & !Self::POINTER, low]) as usize;
+                    // jumping strictly backwards guarantees termination
+                    if target >= segment_start {
+                        return Err(HeaderTruncated);
+                    }
+                    end.get_or_insert(pos + 2);
+                    segment_start = target;
+                    pos = target;
+                }
+                _ => return Err(HeaderTruncated),
+            }
+        }
+    }
+
+    /// Returns `true` if the name is the root, which has no labels.
+    #[inline]
+    pub fn is_root(&self) -> bool {
+        self.labels().next().is_none()
+    }
+
+    /// Returns iterator of the labels of the name, excluding the empty root label.
+    #[inline]
+    pub const fn labels(&self) -> DnsLabels<'a> {
+        DnsLabels {
+            message: self.message,
+            offset: self.offset,
+        }
+    }
+
+    /// Returns `true` if the name is `name`, a dotted name such as `example.com`, ignoring ASCII
+    /// case. A trailing dot is optional.
+    pub fn eq_ignore_ascii_case(&self, name: &str) -> bool {
+        let name = name.strip_suffix('.').unwrap_or(name);
+        let mut labels = self.labels();
+        if name.is_empty() {
+            return labels.next().is_none();
+        }
+        name.split('.').all(|expected| {
+            labels
+                .next()
+                .is_some_and(|l| l.eq_ignore_ascii_case(expected.as_bytes()))
+        }) && labels.next().is_none()
+    }
+}
+
+impl<'a> fmt::Display for DnsName<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_root() {
+            return write!(f, ".");
+        }
+        for (i, label) in self.labels().enumerate() {
+            if i != 0 {
+                write!(f, ".")?;
+            }
+            for &byte in label {
+                match byte {
+                    b'.' | b'\\' => write!(f, "\\{}", byte as char)?,
+                    0x21..=0x7E => write!(f, "{}", byte as char)?,
+                    _ => write!(f, "\\{byte:03}")?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Iterator of the labels of a [`DnsName`], following compression pointers.
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct DnsLabels<'a> {
+    message: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Iterator for DnsLabels<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // the name was validated on construction, so pointers always jump backwards
+        loop {
+            let &byte = self.message.get(self.offset)?;
+            if byte & DnsName::POINTER == DnsName::POINTER {
+                let &low = self.message.get(self.offset + 1)?;
+                self.offset = u16::from_be_bytes([byte & !DnsName::POINTER, low]) as usize;
+                continue;
+            }
+            if byte == 0 {
+                return None;
+            }
+
+            let start = self.offset + 1;
+            self.offset = start + byte as usize;
+            return self.message.get(start..self.offset);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // query for the A record of example.com
+    const QUERY: [u8; 29] = [
+        0x12, 0x34, 0x01, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x07, b'e', b'x',
+        b'a', b'm', b'p', b'l', b'e', 0x03, b'c', b'o', b'm', 0x00, 0x00, 0x01, 0x00, 0x01,
+    ];
+
+    #[test]
+    fn short_header() {
+        let bytes = [0; 11];
+        assert_eq!(Dns::from_bytes(&bytes).unwrap_err(), HeaderTruncated);
+    }
+
+    #[test]
+    fn a_query() {
+        let (header, sections) = Dns::from_bytes(&QUERY).unwrap();
+        assert_eq!(header.id(), 0x1234);
+        assert!(!header.is_response());
+        assert_eq!(header.opcode(), DnsOpcode::Query);
+        assert!(!header.is_authoritative());
+        assert!(!header.is_truncated());
+        assert!(header.recursion_desired());
+        assert!(!header.recursion_available());
+        assert_eq!(header.rcode(), DnsRcode::NoError);
+        assert_eq!(header.question_count(), 1);
+        assert_eq!(header.answer_count(), 0);
+        assert_eq!(header.authority_count(), 0);
+        assert_eq!(header.additional_count(), 0);
+        assert_eq!(sections, &QUERY[12..]);
+
+        let mut questions = header.questions();
+        let question = questions.next().unwrap();
+        assert_eq!(question.question_type(), DnsType::A);
+        assert_eq!(question.question_class(), DnsClass::IN);
+        assert_eq!(
+            question.name().labels().collect::<Vec<_>>(),
+            [&b"example"[..], &b"com"[..]]
+        );
+        assert!(question.name().eq_ignore_ascii_case("Example.COM."));
+        assert!(!question.name().eq_ignore_ascii_case("example"));
+        assert_eq!(question.to_string(), "example.com IN A");
+        assert_eq!(questions.next(), None);
+        assert_eq!(questions.offset(), QUERY.len());
+    }
+
+    #[test]
+    fn compressed_name() {
+        // response to QUERY, answer name pointing at the question name
+        let mut bytes = QUERY.to_vec();
+        bytes[2..4].copy_from_slice(&[0x81, 0x80]);
+        bytes[7] = 1;
+        bytes.extend_from_slice(&[
+            0x03, b'w', b'w', b'w', 0xC0, 0x0C, 0x00, 0x01, 0x00, 0x01, 0x00, 0x00, 0x0E, 0x10,
+            0x00, 0x04, 93, 184, 216, 34,
+        ]);
+
+        let (header, _) = Dns::from_bytes(&bytes).unwrap();
+        assert!(header.is_response());
+        assert!(header.recursion_available());
+        assert_eq!(header.answer_count(), 1);
+
+        let mut questions = header.questions();
+        assert!(questions.next().is_some());
+        let (name, end) = DnsName::from_message(header.message(), questions.offset()).unwrap();
+        assert_eq!(name.to_string(), "www.example.com");
+        assert_eq!(end, QUERY.len() + 6);
+        assert_eq!(&bytes[end..end + 4], &[0x00, 0x01, 0x00, 0x01]);
+    }
+
+    #[test]
+    fn bad_names() {
+        // pointer to itself
+        let bytes = [0x01, b'a', 0xC0, 0x00];
+        assert_eq!(DnsName::from_message(&bytes, 2), Err(HeaderTruncated));
+        assert_eq!(DnsName::from_message(&bytes, 0), Err(HeaderTruncated));
+        // label overruns message
+        let bytes = [0x03, b'a', b'b'];
+        assert_eq!(DnsName::from_message(&bytes, 0), Err(HeaderTruncated));
+        // reserved label type
+        let bytes = [0x41, b'a', 0x00];
+        assert_eq!(DnsName::from_message(&bytes, 0), Err(HeaderTruncated));
+        // root
+        let (name, end) = DnsName::from_message(&[0x00], 0).unwrap();
+        assert!(name.is_root());
+        assert_eq!(name.to_string(), ".");
+        assert_eq!(end, 1);
+    }
+
+    #[test]
+    fn truncated_question() {
+        let (header, _) = Dns::from_bytes(&QUERY[..QUERY.len() - 1]).unwrap();
+        assert_eq!(header.questions().next(), None);
+    }
+}
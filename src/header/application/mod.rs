@@ -0,0 +1,15 @@
+//! The application layer of the Internet Protocol suite
+//!
+//! This layer implements protocols used by applications to exchange data over a transport. Most
+//! application data is opaque to the network stack, but a few small protocols are useful to a
+//! host itself, such as name resolution. For more info, see [RFC 1123].
+//!
+//! [RFC 1123]: https://tools.ietf.org/html/rfc1123
+
+mod dhcpv6;
+mod dns;
+mod ntp;
+
+pub use dhcpv6::*;
+pub use dns::*;
+pub use ntp::*;
@@ -0,0 +1,483 @@
+//! [`Ntp`] packet
+//!
+//! [`Ntp`] packet exchanged between a client and a server to synchronize clocks, with
+//! [`NtpTimestamp`]s marking when it was sent and received.
+use core::fmt;
+use core::time::Duration;
+
+use crate::header::error::HeaderTruncated;
+use crate::header::primitive::{non_exhaustive_enum, U32, U64, U8};
+use crate::header::utils::{as_bytes, as_header, as_header_mut};
+use crate::header::Header;
+
+/// A Network Time Protocol (NTP) packet. [Read more][RFC 5905]
+///
+/// NTP is carried over UDP, port [`Ntp::PORT`]. A client sends a request with its transmit
+/// timestamp, which the server copies into the origin timestamp of the reply along with the times
+/// it received the request and transmitted the reply. From the four timestamps the client can
+/// estimate both the offset of its clock and the round trip delay. Extension fields and the
+/// message authentication code that may follow the packet are left in the payload.
+///
+/// [RFC 5905]: https://tools.ietf.org/html/rfc5905#section-7.3
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[repr(C)]
+pub struct Ntp {
+    li_vn_mode: U8,
+    stratum: U8,
+    poll: U8,
+    precision: U8,
+    root_delay: U32,
+    root_dispersion: U32,
+    reference_id: [u8; 4],
+    reference: U64,
+    origin: U64,
+    receive: U64,
+    transmit: U64,
+}
+
+impl Ntp {
+    /// The well known UDP port of NTP.
+    pub const PORT: u16 = 123;
+
+    /// Constructs an NTP version 4 client request sent at `transmit`. All other fields are zero.
+    /// Usable in `const` contexts, such as packet templates.
+    #[inline]
+    pub const fn client_request(transmit: NtpTimestamp) -> Self {
+        Ntp {
+            li_vn_mode: U8::new(4 << 3 | NtpMode::Client.get()),
+            stratum: U8::new(0),
+            poll: U8::new(0),
+            precision: U8::new(0),
+            root_delay: U32::new(0),
+            root_dispersion: U32::new(0),
+            reference_id: [0; 4],
+            reference: U64::new(0),
+            origin: U64::new(0),
+            receive: U64::new(0),
+            transmit: U64::new(transmit.0),
+        }
+    }
+
+    /// Returns an immutable view of `bytes` as an NTP packet followed by any extension fields or
+    /// an error if the size does not represent a valid NTP packet.
+    #[inline]
+    pub const fn from_bytes(bytes: &[u8]) -> Result<(&Self, &[u8]), HeaderTruncated> {
+        as_header!(Ntp, bytes)
+    }
+
+    /// Returns a mutable view of `bytes` as an NTP packet followed by any extension fields or an
+    /// error if the size does not represent a valid NTP packet.
+    #[inline]
+    pub fn from_bytes_mut(bytes: &mut [u8]) -> Result<(&mut Self, &mut [u8]), HeaderTruncated> {
+        as_header_mut!(Ntp, bytes)
+    }
+
+    /// Returns the NTP packet as a sequence of network endian bytes.
+    #[inline]
+    pub const fn as_bytes(&self) -> &[u8; 48] {
+        as_bytes!(Ntp, self)
+    }
+
+    /// Returns the warning of an impending leap second.
+    #[inline]
+    pub const fn leap(&self) -> NtpLeap {
+        NtpLeap::new(self.li_vn_mode.get() >> 6)
+    }
+
+    /// Returns the NTP version number, currently `4`.
+    #[inline]
+    pub const fn version(&self) -> u8 {
+        (self.li_vn_mode.get() >> 3) & 0b111
+    }
+
+    /// Returns the role of the sender.
+    #[inline]
+    pub const fn mode(&self) -> NtpMode {
+        NtpMode::new(self.li_vn_mode.get() & 0b111)
+    }
+
+    /// Returns the distance of the server from a reference clock in hops, where `1` is a primary
+    /// server. `0` is unspecified, used by a Kiss-o'-Death reply.
+    #[inline]
+    pub const fn stratum(&self) -> u8 {
+        self.stratum.get()
+    }
+
+    /// Returns the maximum interval between successive messages as a log2 of seconds.
+    #[inline]
+    pub const fn poll(&self) -> i8 {
+        self.poll.get() as i8
+    }
+
+    /// Returns the precision of the sender's clock as a log2 of seconds.
+    #[inline]
+    pub const fn precision(&self) -> i8 {
+        self.precision.get() as i8
+    }
+
+    /// Returns the total round trip delay to the reference clock.
+    #[inline]
+    pub const fn root_delay(&self) -> Duration {
+        short_to_duration(self.root_delay.get())
+    }
+
+    /// Returns the total dispersion to the reference clock.
+    #[inline]
+    pub const fn root_dispersion(&self) -> Duration {
+        short_to_duration(self.root_dispersion.get())
+    }
+
+    /// Returns the identifier of the reference clock. For a primary server, this is an ASCII code
+    /// such as `GPS`. Otherwise, it is the IPv4 address of the upstream server or a hash of its
+    /// IPv6 address.
+    #[inline]
+    pub const fn reference_id(&self) -> [u8; 4] {
+        self.reference_id
+    }
+
+    /// Returns the time the sender's clock was last set.
+    #[inline]
+    pub const fn reference_timestamp(&self) -> NtpTimestamp {
+        NtpTimestamp(self.reference.get())
+    }
+
+    /// Returns the time the request this packet replies to was sent, copied from its transmit
+    /// timestamp.
+    #[inline]
+    pub const fn origin_timestamp(&self) -> NtpTimestamp {
+        NtpTimestamp(self.origin.get())
+    }
+
+    /// Returns the time the request this packet replies to was received.
+    #[inline]
+    pub const fn receive_timestamp(&self) -> NtpTimestamp {
+        NtpTimestamp(self.receive.get())
+    }
+
+    /// Returns the time this packet was sent.
+    #[inline]
+    pub const fn transmit_timestamp(&self) -> NtpTimestamp {
+        NtpTimestamp(self.transmit.get())
+    }
+
+    /// Sets the leap indicator, version number, and mode.
+    #[inline]
+    pub fn set_leap_version_mode(&mut self, leap: NtpLeap, version: u8, mode: NtpMode) {
+        self.li_vn_mode = U8::new(leap.get() << 6 | (version & 0b111) << 3 | (mode.get() & 0b111));
+    }
+
+    /// Sets the stratum.
+    #[inline]
+    pub fn set_stratum(&mut self, stratum: u8) {
+        self.stratum = U8::new(stratum);
+    }
+
+    /// Sets the poll interval as a log2 of seconds.
+    #[inline]
+    pub fn set_poll(&mut self, poll: i8) {
+        self.poll = U8::new(poll as u8);
+    }
+
+    /// Sets the precision as a log2 of seconds.
+    #[inline]
+    pub fn set_precision(&mut self, precision: i8) {
+        self.precision = U8::new(precision as u8);
+    }
+
+    /// Sets the reference identifier.
+    #[inline]
+    pub fn set_reference_id(&mut self, reference_id: [u8; 4]) {
+        self.reference_id = reference_id;
+    }
+
+    /// Sets the reference timestamp.
+    #[inline]
+    pub fn set_reference_timestamp(&mut self, timestamp: NtpTimestamp) {
+        self.reference = U64::new(timestamp.0);
+    }
+
+    /// Sets the origin timestamp.
+    #[inline]
+    pub fn set_origin_timestamp(&mut self, timestamp: NtpTimestamp) {
+        self.origin = U64::new(timestamp.0);
+    }
+
+    /// Sets the receive timestamp.
+    #[inline]
+    pub fn set_receive_timestamp(&mut self, timestamp: NtpTimestamp) {
+        self.receive = U64::new(timestamp.0);
+    }
+
+    /// Sets the transmit timestamp.
+    #[inline]
+    pub fn set_transmit_timestamp(&mut self, timestamp: NtpTimestamp) {
+        self.transmit = U64::new(timestamp.0);
+    }
+}
+
+impl<'a> Header<'a> for &'a Ntp {
+    #[inline]
+    fn from_bytes(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), HeaderTruncated> {
+        Ntp::from_bytes(bytes)
+    }
+}
+
+impl fmt::Display for Ntp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "NTPv{} {} stratum={} transmit={}",
+            self.version(),
+            self.mode(),
+            self.stratum(),
+            self.transmit_timestamp()
+        )
+    }
+}
+
+// Converts an NTP short format, 16.16 fixed point seconds, to a Duration.
+const fn short_to_duration(short: u32) -> Duration {
+    let nanos = ((short & 0xFFFF) as u64 * 1_000_000_000) >> 16;
+    Duration::new((short >> 16) as u64, nanos as u32)
+}
+
+non_exhaustive_enum! {
+/// An NTP leap indicator. [Read more][RFC 5905]
+///
+/// Warns of a leap second to be inserted or deleted at the end of the current day.
+///
+/// [RFC 5905]: https://tools.ietf.org/html/rfc5905#section-7.3
+pub enum NtpLeap(u8) {
+    NoWarning = 0,
+    InsertSecond = 1,
+    DeleteSecond = 2,
+    Unsynchronized = 3,
+}
+}
+
+non_exhaustive_enum! {
+/// An NTP association mode. [Read more][RFC 5905]
+///
+/// Identifies the role of the sender of an NTP packet.
+///
+/// [RFC 5905]: https://tools.ietf.org/html/rfc5905#section-7.3
+pub enum NtpMode(u8) {
+    SymmetricActive = 1,
+    SymmetricPassive = 2,
+    Client = 3,
+    Server = 4,
+    Broadcast = 5,
+    Control = 6,
+    Private = 7,
+}
+}
+
+/// An NTP timestamp. [Read more][RFC 5905]
+///
+/// A 64-bit fixed point number of seconds since the NTP epoch, 1900-01-01 00:00:00 UTC. The upper
+/// 32 bits count whole seconds and the lower 32 bits count fractions of a second. The seconds
+/// overflow on 2036-02-07, starting a new era. Conversions to and from [`Duration`] follow
+/// [RFC 4330]: timestamps with the most significant bit of the seconds set fall between 1968 and
+/// 2036, and the rest between 2036 and 2104, in the next era. A zero timestamp means the time is
+/// unknown and converts to the NTP epoch itself.
+///
+/// [RFC 5905]: https://tools.ietf.org/html/rfc5905#section-6
+/// [RFC 4330]: https://tools.ietf.org/html/rfc4330#section-3
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Default)]
+pub struct NtpTimestamp(u64);
+
+impl NtpTimestamp {
+    /// The Unix epoch, 1970-01-01 00:00:00 UTC.
+    pub const UNIX_EPOCH: NtpTimestamp = NtpTimestamp::new(2_208_988_800, 0);
+
+    /// Constructs a timestamp from whole `seconds` and a `fraction` of a second in units of
+    /// 2^-32 seconds.
+    #[inline]
+    pub const fn new(seconds: u32, fraction: u32) -> Self {
+        NtpTimestamp((seconds as u64) << 32 | fraction as u64)
+    }
+
+    /// Returns the whole seconds since the NTP epoch.
+    #[inline]
+    pub const fn seconds(&self) -> u32 {
+        (self.0 >> 32) as u32
+    }
+
+    /// Returns the fraction of a second in units of 2^-32 seconds.
+    #[inline]
+    pub const fn fraction(&self) -> u32 {
+        self.0 as u32
+    }
+
+    /// Returns the time elapsed since the NTP epoch.
+    #[inline]
+    pub const fn to_duration(&self) -> Duration {
+        if self.0 == 0 {
+            return Duration::ZERO;
+        }
+        let nanos = (self.fraction() as u64 * 1_000_000_000) >> 32;
+        let seconds = self.seconds() as u64;
+        // without the most significant bit set, the timestamp is in the era starting in 2036
+        let seconds = if seconds < ERA_SPLIT {
+            seconds + (1 << 32)
+        } else {
+            seconds
+        };
+        Duration::new(seconds, nanos as u32)
+    }
+
+    /// Constructs a timestamp `duration` after the NTP epoch, rounding down to the nearest
+    /// fraction. Returns [`None`] if `duration` is before 1968-01-20 or after 2104-02-26, the
+    /// range timestamps can represent.
+    #[inline]
+    pub const fn from_duration(duration: Duration) -> Option<Self> {
+        if duration.as_secs() < ERA_SPLIT || duration.as_secs() >= ERA_SPLIT + (1 << 32) {
+            return None;
+        }
+        let fraction = ((duration.subsec_nanos() as u64) << 32) / 1_000_000_000;
+        Some(NtpTimestamp::new(
+            duration.as_secs() as u32,
+            fraction as u32,
+        ))
+    }
+
+    /// Returns the time elapsed since the Unix epoch or [`None`] if the timestamp is earlier.
+    #[inline]
+    pub const fn to_unix_duration(&self) -> Option<Duration> {
+        self.to_duration()
+            .checked_sub(NtpTimestamp::UNIX_EPOCH.to_duration())
+    }
+
+    /// Constructs a timestamp `duration` after the Unix epoch, such as the time reported by a
+    /// system clock. Returns [`None`] if `duration` is after 2104-02-26.
+    #[inline]
+    pub const fn from_unix_duration(duration: Duration) -> Option<Self> {
+        match duration.checked_add(NtpTimestamp::UNIX_EPOCH.to_duration()) {
+            Some(duration) => NtpTimestamp::from_duration(duration),
+            None => None,
+        }
+    }
+}
+
+// Seconds since the NTP epoch of 1968-01-20 03:14:08 UTC, when the most significant bit of the
+// seconds is first set. Timestamps before it belong to the next era.
+const ERA_SPLIT: u64 = 1 << 31;
+
+impl fmt::Display for NtpTimestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let duration = self.to_duration();
+        write!(f, "{}.{:09}", duration.as_secs(), duration.subsec_nanos())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_header() {
+        let bytes = [0; 47];
+        assert_eq!(Ntp::from_bytes(&bytes).unwrap_err(), HeaderTruncated);
+    }
+
+    #[test]
+    fn client_request() {
+        const REQUEST: Ntp = Ntp::client_request(NtpTimestamp::new(3_913_056_000, 0x4000_0000));
+        const BYTES: &[u8; 48] = REQUEST.as_bytes();
+        assert_eq!(BYTES[0], 0x23);
+
+        let (packet, payload) = Ntp::from_bytes(BYTES).unwrap();
+        assert_eq!(packet, &REQUEST);
+        assert!(payload.is_empty());
+        assert_eq!(packet.leap(), NtpLeap::NoWarning);
+        assert_eq!(packet.version(), 4);
+        assert_eq!(packet.mode(), NtpMode::Client);
+        assert_eq!(packet.stratum(), 0);
+        assert_eq!(packet.origin_timestamp(), NtpTimestamp::default());
+        assert_eq!(
+            packet.transmit_timestamp(),
+            NtpTimestamp::new(3_913_056_000, 0x4000_0000)
+        );
+    }
+
+    #[test]
+    fn server_reply() {
+        let mut bytes = [0; 48];
+        let (packet, _) = Ntp::from_bytes_mut(&mut bytes).unwrap();
+        packet.set_leap_version_mode(NtpLeap::NoWarning, 4, NtpMode::Server);
+        packet.set_stratum(1);
+        packet.set_poll(6);
+        packet.set_precision(-20);
+        packet.set_reference_id(*b"GPS\0");
+        packet.set_origin_timestamp(NtpTimestamp::new(3_913_056_000, 0x4000_0000));
+        packet.set_receive_timestamp(NtpTimestamp::new(3_913_056_000, 0x6000_0000));
+        packet.set_transmit_timestamp(NtpTimestamp::new(3_913_056_000, 0x8000_0000));
+        bytes[4..8].copy_from_slice(&[0x00, 0x00, 0x80, 0x00]);
+
+        assert_eq!(bytes[0], 0x24);
+        let (packet, _) = Ntp::from_bytes(&bytes).unwrap();
+        assert_eq!(packet.mode(), NtpMode::Server);
+        assert_eq!(packet.stratum(), 1);
+        assert_eq!(packet.poll(), 6);
+        assert_eq!(packet.precision(), -20);
+        assert_eq!(packet.reference_id(), *b"GPS\0");
+        assert_eq!(packet.root_delay(), Duration::from_millis(500));
+
+        // 2024-01-01 00:00:00.5 UTC
+        let transmit = packet.transmit_timestamp();
+        assert_eq!(transmit.seconds(), 3_913_056_000);
+        assert_eq!(transmit.fraction(), 0x8000_0000);
+        assert_eq!(
+            transmit.to_unix_duration(),
+            Some(Duration::from_millis(1_704_067_200_500))
+        );
+        assert_eq!(transmit.to_string(), "3913056000.500000000");
+    }
+
+    #[test]
+    fn timestamp_conversion() {
+        let unix = Duration::new(1_704_067_200, 250_000_000);
+        let timestamp = NtpTimestamp::from_unix_duration(unix).unwrap();
+        assert_eq!(timestamp, NtpTimestamp::new(3_913_056_000, 0x4000_0000));
+        assert_eq!(timestamp.to_unix_duration(), Some(unix));
+
+        assert_eq!(
+            NtpTimestamp::UNIX_EPOCH.to_unix_duration(),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(NtpTimestamp::default().to_unix_duration(), None);
+        assert_eq!(
+            NtpTimestamp::from_duration(Duration::from_secs((1 << 31) - 1)),
+            None
+        );
+        assert_eq!(
+            NtpTimestamp::from_duration(Duration::from_secs((1 << 31) + (1 << 32))),
+            None
+        );
+    }
+
+    #[test]
+    fn timestamp_next_era() {
+        // 2036-02-07 06:28:16 UTC, when the seconds wrap around
+        let wrap = Duration::from_secs(2_085_978_496);
+        let timestamp = NtpTimestamp::from_unix_duration(wrap + Duration::from_secs(1)).unwrap();
+        assert_eq!(timestamp, NtpTimestamp::new(1, 0));
+        assert_eq!(
+            timestamp.to_unix_duration(),
+            Some(wrap + Duration::from_secs(1))
+        );
+
+        // 2100-01-01 00:00:00 UTC
+        let unix = Duration::from_secs(4_102_444_800);
+        let timestamp = NtpTimestamp::from_unix_duration(unix).unwrap();
+        assert_eq!(timestamp.seconds(), 2_016_466_304);
+        assert_eq!(timestamp.to_unix_duration(), Some(unix));
+
+        // the last second before the wrap is still in the first era
+        let timestamp = NtpTimestamp::new(u32::MAX, 0);
+        assert_eq!(
+            timestamp.to_unix_duration(),
+            Some(wrap - Duration::from_secs(1))
+        );
+    }
+}
@@ -1,5 +1,8 @@
-use super::{error::ChecksumAssertion, utils::split_word};
+#[cfg(test)]
+use super::error::ChecksumAssertion;
+use super::utils::split_word;
 
+#[cfg(test)]
 #[inline]
 pub const fn verify_checksum(bytes: &[u8]) -> Result<(), ChecksumAssertion> {
     if compute_checksum(bytes) == !0 {
@@ -28,6 +31,68 @@ pub const fn compute_checksum(mut bytes: &[u8]) -> u16 {
     (sum >> 16) as u16 + sum as u16
 }
 
+// Same as `compute_checksum`, but folds over several slices as if they were one contiguous buffer.
+// A trailing odd byte of one slice is paired with the leading byte of the next.
+#[inline]
+pub const fn compute_checksum_chained(slices: &[&[u8]]) -> u16 {
+    let mut sum: u32 = 0;
+    let mut odd: Option<u8> = None;
+    let mut i = 0;
+    while i < slices.len() {
+        let mut bytes = slices[i];
+        i += 1;
+
+        if let Some(first) = odd {
+            match bytes.split_first() {
+                Some((second, rest)) => {
+                    sum += u16::from_ne_bytes([first, *second]) as u32;
+                    bytes = rest;
+                    odd = None;
+                }
+                None => continue,
+            }
+        }
+
+        while let Some((word, rest)) = split_word(bytes) {
+            sum += word as u32;
+            bytes = rest;
+        }
+
+        if let Some(value) = bytes.first() {
+            odd = Some(*value);
+        }
+    }
+
+    if let Some(value) = odd {
+        sum += value as u32
+    }
+
+    // carries are added to the sum (twice in case another carry is produced)
+    sum = (sum >> 16) + (sum & 0xffff);
+    (sum >> 16) as u16 + sum as u16
+}
+
+// Incrementally updates a checksum `cks` when the bytes `old` are replaced by `new` of the same
+// length, without summing the rest of the data again (RFC 1624, eqn. 3). Words are read as network
+// endian, so `cks` is the value as read from a header field.
+//
+// `old` and `new` must have an even length and start at an even offset of the checksummed data,
+// so that they cover whole 16-bit words of it. Widen a change to the surrounding words otherwise.
+#[inline]
+pub const fn update_checksum(cks: u16, mut old: &[u8], mut new: &[u8]) -> u16 {
+    debug_assert!(old.len() == new.len() && old.len().is_multiple_of(2));
+    let mut sum: u32 = !cks as u32;
+    while let (Some((o, old_rest)), Some((n, new_rest))) = (split_word(old), split_word(new)) {
+        sum += !u16::from_be(o) as u32 + u16::from_be(n) as u32;
+        old = old_rest;
+        new = new_rest;
+    }
+
+    // carries are added to the sum (twice in case another carry is produced)
+    sum = (sum >> 16) + (sum & 0xffff);
+    !((sum >> 16) as u16 + sum as u16)
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -91,4 +156,63 @@ mod tests {
         let bytes = [0xFF, 0xFF, 0xFF, 0xFF, 0x01];
         assert_eq!(verify_checksum(&bytes), Err(ChecksumAssertion));
     }
+
+    #[test]
+    fn chained_checksum() {
+        let bytes = [
+            0x45, 0x00, 0x00, 0x3c, 0x1c, 0x46, 0x40, 0x00, 0x40, 0x06, 0xb1, 0xe6, 0xac, 0x10,
+            0x0a, 0x63, 0xac, 0x10, 0x0a, 0x0c, 0x7f,
+        ];
+        let contiguous = compute_checksum(&bytes);
+
+        let (header, payload) = bytes.split_at(8);
+        assert_eq!(compute_checksum_chained(&[header, payload]), contiguous);
+        assert_eq!(compute_checksum_chained(&[&bytes]), contiguous);
+        assert_eq!(compute_checksum_chained(&[&[], &bytes, &[]]), contiguous);
+        assert_eq!(compute_checksum_chained(&[]), 0);
+    }
+
+    #[test]
+    fn chained_checksum_straddling_odd_byte() {
+        let bytes = [
+            0x45, 0x00, 0x00, 0x3c, 0x1c, 0x46, 0x40, 0x00, 0x40, 0x06, 0xb1, 0xe6, 0xac, 0x10,
+            0x0a, 0x63, 0xac, 0x10, 0x0a, 0x0c,
+        ];
+        let contiguous = compute_checksum(&bytes);
+
+        for mid in 0..=bytes.len() {
+            let (a, b) = bytes.split_at(mid);
+            assert_eq!(compute_checksum_chained(&[a, b]), contiguous);
+        }
+
+        let (a, rest) = bytes.split_at(3);
+        let (b, c) = rest.split_at(1);
+        assert_eq!(compute_checksum_chained(&[a, &[], b, c]), contiguous);
+        assert_eq!(
+            compute_checksum_chained(&[&bytes[..5], &bytes[5..10], &bytes[10..]]),
+            contiguous
+        );
+    }
+
+    #[test]
+    fn incremental_checksum() {
+        let mut bytes = [
+            0x45, 0x00, 0x00, 0x3c, 0x1c, 0x46, 0x40, 0x00, 0x40, 0x06, 0xb1, 0xe6, 0xac, 0x10,
+            0x0a, 0x63, 0xac, 0x10, 0x0a, 0x0c,
+        ];
+        let cks = u16::from_be_bytes([bytes[10], bytes[11]]);
+
+        let new = [0xc0, 0xa8, 0x01, 0x02];
+        let cks = update_checksum(cks, &bytes[12..16], &new);
+        bytes[12..16].copy_from_slice(&new);
+        bytes[10..12].copy_from_slice(&cks.to_be_bytes());
+
+        assert_eq!(verify_checksum(&bytes), Ok(()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn incremental_checksum_odd_length() {
+        update_checksum(0, &[0x01, 0x02, 0x03], &[0x04, 0x05, 0x06]);
+    }
 }
@@ -9,6 +9,30 @@ impl fmt::Display for HeaderTruncated {
     }
 }
 
+/// Distinguishes a buffer too short for a header from a header whose contents are invalid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeaderInvalid {
+    /// Not enough bytes to represent the header.
+    Truncated,
+    /// The bytes are present but do not represent a valid header.
+    Malformed,
+}
+
+impl From<HeaderTruncated> for HeaderInvalid {
+    fn from(_: HeaderTruncated) -> Self {
+        HeaderInvalid::Truncated
+    }
+}
+
+impl fmt::Display for HeaderInvalid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HeaderInvalid::Truncated => HeaderTruncated.fmt(f),
+            HeaderInvalid::Malformed => write!(f, "bytes do not represent a valid header"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ChecksumAssertion;
 
@@ -26,3 +50,14 @@ impl fmt::Display for ValueToLarge {
         write!(f, "value to large to represent as bitfield")
     }
 }
+
+#[cfg(feature = "heapless")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CapacityExceeded;
+
+#[cfg(feature = "heapless")]
+impl fmt::Display for CapacityExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "not enough capacity to hold all items")
+    }
+}
@@ -5,7 +5,8 @@ use crate::header::{
     error::HeaderTruncated,
     link::{EtherAddr, EtherType as Protocol, EtherTypeRepr as ProtocolRepr},
     primitive::{non_exhaustive_enum, U16, U8},
-    utils::as_header,
+    utils::{as_header, as_header_mut},
+    Header,
 };
 
 non_exhaustive_enum! {
@@ -51,6 +52,13 @@ impl Arp {
         as_header!(Arp, bytes)
     }
 
+    /// Returns a mutable view of `bytes` as an Arp header followed by a payload or an error if the
+    /// size or contents do not represent a valid Arp header.
+    #[inline]
+    pub fn from_bytes_mut(bytes: &mut [u8]) -> Result<(&mut Self, &mut [u8]), HeaderTruncated> {
+        as_header_mut!(Arp, bytes)
+    }
+
     /// Link layer hardware type, always [`Hardware::Ethernet`].
     #[inline]
     pub const fn hardware(&self) -> Hardware {
@@ -104,6 +112,43 @@ impl Arp {
     pub const fn dest_proto_addr(&self) -> Ipv4Addr {
         self.dest_paddr
     }
+
+    /// Sets the ARP operation.
+    #[inline]
+    pub fn set_operation(&mut self, operation: Operation) {
+        self.oper = OperationRepr::from(operation);
+    }
+
+    /// Sets source link layer [`EtherAddr`].
+    #[inline]
+    pub fn set_src_hw_addr(&mut self, addr: EtherAddr) {
+        self.src_haddr = addr;
+    }
+
+    /// Sets source internet layer [`Ipv4Addr`].
+    #[inline]
+    pub fn set_src_proto_addr(&mut self, addr: Ipv4Addr) {
+        self.src_paddr = addr;
+    }
+
+    /// Sets destination link layer [`EtherAddr`].
+    #[inline]
+    pub fn set_dest_hw_addr(&mut self, addr: EtherAddr) {
+        self.dest_haddr = addr;
+    }
+
+    /// Sets destination internet layer [`Ipv4Addr`].
+    #[inline]
+    pub fn set_dest_proto_addr(&mut self, addr: Ipv4Addr) {
+        self.dest_paddr = addr;
+    }
+}
+
+impl<'a> Header<'a> for &'a Arp {
+    #[inline]
+    fn from_bytes(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), HeaderTruncated> {
+        Arp::from_bytes(bytes)
+    }
 }
 
 impl fmt::Display for Arp {
@@ -159,9 +204,9 @@ impl OperationRepr {
     }
 }
 
-impl From<Hardware> for OperationRepr {
+impl From<Operation> for OperationRepr {
     #[inline]
-    fn from(value: Hardware) -> Self {
+    fn from(value: Operation) -> Self {
         OperationRepr(U16::from(value.get()))
     }
 }
@@ -181,4 +226,55 @@ mod tests {
         let bytes = [0; 27];
         assert_eq!(Arp::from_bytes(&bytes).unwrap_err(), HeaderTruncated);
     }
+
+    #[test]
+    fn mutate_header() {
+        let mut bytes = [0; 28];
+        let (header, _) = Arp::from_bytes_mut(&mut bytes).unwrap();
+        header.set_operation(Operation::Reply);
+        header.set_src_hw_addr(EtherAddr::new([0x02, 0, 0, 0, 0, 0x01]));
+        header.set_src_proto_addr(Ipv4Addr::new([192, 168, 0, 1]));
+        header.set_dest_hw_addr(EtherAddr::new([0x02, 0, 0, 0, 0, 0x02]));
+        header.set_dest_proto_addr(Ipv4Addr::new([192, 168, 0, 2]));
+
+        let (header, _) = Arp::from_bytes(&bytes).unwrap();
+        assert_eq!(header.operation(), Operation::Reply);
+        assert_eq!(
+            header.src_hw_addr(),
+            EtherAddr::new([0x02, 0, 0, 0, 0, 0x01])
+        );
+        assert_eq!(header.src_proto_addr(), Ipv4Addr::new([192, 168, 0, 1]));
+        assert_eq!(
+            header.dest_hw_addr(),
+            EtherAddr::new([0x02, 0, 0, 0, 0, 0x02])
+        );
+        assert_eq!(header.dest_proto_addr(), Ipv4Addr::new([192, 168, 0, 2]));
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn round_trip(
+            operation: u16,
+            src_hw: [u8; 6],
+            src_proto: [u8; 4],
+            dest_hw: [u8; 6],
+            dest_proto: [u8; 4],
+        ) {
+            let mut bytes = [0; 28];
+            let (header, _) = Arp::from_bytes_mut(&mut bytes).unwrap();
+            header.set_operation(Operation::from(operation));
+            header.set_src_hw_addr(EtherAddr::new(src_hw));
+            header.set_src_proto_addr(Ipv4Addr::new(src_proto));
+            header.set_dest_hw_addr(EtherAddr::new(dest_hw));
+            header.set_dest_proto_addr(Ipv4Addr::new(dest_proto));
+
+            let (header, payload) = Arp::from_bytes(&bytes).unwrap();
+            proptest::prop_assert_eq!(header.operation(), Operation::from(operation));
+            proptest::prop_assert_eq!(header.src_hw_addr(), EtherAddr::new(src_hw));
+            proptest::prop_assert_eq!(header.src_proto_addr(), Ipv4Addr::new(src_proto));
+            proptest::prop_assert_eq!(header.dest_hw_addr(), EtherAddr::new(dest_hw));
+            proptest::prop_assert_eq!(header.dest_proto_addr(), Ipv4Addr::new(dest_proto));
+            proptest::prop_assert!(payload.is_empty());
+        }
+    }
 }
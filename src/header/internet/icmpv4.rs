@@ -0,0 +1,363 @@
+//! [`Icmpv4`] header
+//!
+//! [`Icmpv4`] header used to report errors and exchange diagnostic messages between IPv4 nodes.
+use core::fmt;
+
+use crate::header::error::HeaderTruncated;
+use crate::header::primitive::{non_exhaustive_enum, U16, U8};
+use crate::header::utils::{as_header, split_at};
+use crate::header::Header;
+
+use super::{Ipv4, Ipv4Addr};
+
+/// An ICMPv4 header. [Read more][RFC 792]
+///
+/// ICMP is carried by IPv4, but is an integral part of the internet layer. It is used by nodes to
+/// report errors encountered while processing a datagram back to the source, and to perform simple
+/// diagnostics such as echo (ping).
+///
+/// The meaning of the last four bytes of the header depends on the [`Icmpv4Type`] of the message.
+///
+/// [RFC 792]: https://tools.ietf.org/html/rfc792
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[repr(C)]
+pub struct Icmpv4 {
+    ty: Icmpv4TypeRepr,
+    code: U8,
+    cks: U16,
+    rest: [u8; 4],
+}
+
+impl Icmpv4 {
+    /// Returns an immutable view of `bytes` as an ICMPv4 header followed by a payload or an error
+    /// if the size or contents do not represent a valid ICMPv4 header.
+    #[inline]
+    pub const fn from_bytes(bytes: &[u8]) -> Result<(&Self, &[u8]), HeaderTruncated> {
+        as_header!(Icmpv4, bytes)
+    }
+
+    /// Returns the type of ICMPv4 message.
+    #[inline]
+    pub const fn message_type(&self) -> Icmpv4Type {
+        self.ty.get()
+    }
+
+    /// Returns the code of the ICMPv4 message. The meaning of the code depends on the
+    /// [`message_type`][Icmpv4::message_type].
+    #[inline]
+    pub const fn code(&self) -> u8 {
+        self.code.get()
+    }
+
+    /// Returns the checksum of the ICMPv4 header and payload.
+    #[inline]
+    pub const fn cks(&self) -> u16 {
+        self.cks.get()
+    }
+
+    /// Returns `true` if the message reports an error. The payload of an error message begins with
+    /// the original datagram that caused it (see [`Icmpv4Error`]).
+    #[inline]
+    pub const fn is_error(&self) -> bool {
+        matches!(
+            self.message_type(),
+            Icmpv4Type::DestUnreachable
+                | Icmpv4Type::SourceQuench
+                | Icmpv4Type::Redirect
+                | Icmpv4Type::TimeExceeded
+                | Icmpv4Type::ParameterProblem
+        )
+    }
+
+    /// Returns the gateway of an [`Icmpv4Type::Redirect`] message or [`None`] for any other type.
+    /// Datagrams for the destination of the original datagram should be sent to this gateway
+    /// instead.
+    #[inline]
+    pub const fn gateway(&self) -> Option<Ipv4Addr> {
+        match self.message_type() {
+            Icmpv4Type::Redirect => Some(Ipv4Addr::new(self.rest)),
+            _ => None,
+        }
+    }
+
+    /// Returns the MTU of the next hop reported by an [`Icmpv4Type::DestUnreachable`] message with
+    /// code [`UnreachableCode::FragmentationNeeded`] or [`None`] for any other message. Routers
+    /// predating [RFC 1191] report an MTU of `0`.
+    ///
+    /// [RFC 1191]: https://tools.ietf.org/html/rfc1191#section-4
+    #[inline]
+    pub const fn next_hop_mtu(&self) -> Option<u16> {
+        match (self.message_type(), UnreachableCode::new(self.code())) {
+            (Icmpv4Type::DestUnreachable, UnreachableCode::FragmentationNeeded) => {
+                Some(u16::from_be_bytes([self.rest[2], self.rest[3]]))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl<'a> Header<'a> for &'a Icmpv4 {
+    #[inline]
+    fn from_bytes(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), HeaderTruncated> {
+        Icmpv4::from_bytes(bytes)
+    }
+}
+
+impl fmt::Display for Icmpv4 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ICMPv4 ({})", self.message_type())?;
+        match self.message_type() {
+            Icmpv4Type::DestUnreachable => write!(f, " {}", UnreachableCode::new(self.code())),
+            Icmpv4Type::TimeExceeded => write!(f, " {}", TimeExceededCode::new(self.code())),
+            Icmpv4Type::Redirect => write!(
+                f,
+                " {} gateway={}",
+                RedirectCode::new(self.code()),
+                Ipv4Addr::new(self.rest)
+            ),
+            _ => write!(f, " code={}", self.code()),
+        }
+    }
+}
+
+/// The original datagram embedded in the payload of an ICMPv4 error message. [Read more][RFC 792]
+///
+/// Error messages carry the IPv4 header of the datagram that caused the error followed by at least
+/// the first 8 bytes of its payload. For UDP and TCP, these 8 bytes are enough to recover the
+/// ports of the original datagram, so that the error can be matched to a socket.
+///
+/// [RFC 792]: https://tools.ietf.org/html/rfc792
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct Icmpv4Error<'a> {
+    header: Ipv4<'a>,
+    data: &'a [u8],
+}
+
+impl<'a> Icmpv4Error<'a> {
+    /// Length of the original datagram payload carried by an ICMPv4 error message.
+    pub const DATA_LEN: usize = 8;
+
+    /// Returns an immutable view of `bytes`, the payload of an ICMPv4 error message, as the
+    /// original datagram followed by any remaining bytes or an error if there are not enough bytes
+    /// to represent the original IPv4 header and the first 8 bytes of its payload.
+    #[inline]
+    pub const fn from_bytes(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), HeaderTruncated> {
+        let (header, payload) = match Ipv4::from_bytes(bytes) {
+            Ok(v) => v,
+            Err(e) => return Err(e),
+        };
+
+        let (data, rest) = match split_at(payload, Self::DATA_LEN) {
+            Some(v) => v,
+            None => return Err(HeaderTruncated),
+        };
+
+        Ok((Icmpv4Error { header, data }, rest))
+    }
+
+    /// Returns the IPv4 header of the original datagram.
+    #[inline]
+    pub const fn header(&self) -> &Ipv4<'a> {
+        &self.header
+    }
+
+    /// Returns the first 8 bytes of the payload of the original datagram.
+    #[inline]
+    pub const fn data(&self) -> &'a [u8] {
+        self.data
+    }
+}
+
+impl<'a> Header<'a> for Icmpv4Error<'a> {
+    #[inline]
+    fn from_bytes(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), HeaderTruncated> {
+        Icmpv4Error::from_bytes(bytes)
+    }
+}
+
+impl<'a> fmt::Display for Icmpv4Error<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "original: {}", self.header())
+    }
+}
+
+non_exhaustive_enum! {
+/// An ICMPv4 message type. [Read more][IANA]
+///
+/// A complete list of message types is maintained by the [IANA].
+///
+/// [IANA]: https://www.iana.org/assignments/icmp-parameters/icmp-parameters.xhtml
+pub enum Icmpv4Type(u8) {
+    EchoReply = 0,
+    DestUnreachable = 3,
+    SourceQuench = 4,
+    Redirect = 5,
+    EchoRequest = 8,
+    RouterAdvertisement = 9,
+    RouterSolicitation = 10,
+    TimeExceeded = 11,
+    ParameterProblem = 12,
+    Timestamp = 13,
+    TimestampReply = 14,
+}
+}
+
+non_exhaustive_enum! {
+/// Code of an [`Icmpv4Type::DestUnreachable`] message.
+pub enum UnreachableCode(u8) {
+    NetUnreachable = 0,
+    HostUnreachable = 1,
+    ProtocolUnreachable = 2,
+    PortUnreachable = 3,
+    FragmentationNeeded = 4,
+    SourceRouteFailed = 5,
+    DestNetworkUnknown = 6,
+    DestHostUnknown = 7,
+    SourceHostIsolated = 8,
+    NetProhibited = 9,
+    HostProhibited = 10,
+    NetUnreachableForTos = 11,
+    HostUnreachableForTos = 12,
+    CommProhibited = 13,
+    HostPrecedenceViolation = 14,
+    PrecedenceCutoff = 15,
+}
+}
+
+non_exhaustive_enum! {
+/// Code of an [`Icmpv4Type::TimeExceeded`] message.
+pub enum TimeExceededCode(u8) {
+    /// Time to live exceeded in transit
+    TtlExpired = 0,
+    /// Fragment reassembly time exceeded
+    FragReassembly = 1,
+}
+}
+
+non_exhaustive_enum! {
+/// Code of an [`Icmpv4Type::Redirect`] message.
+pub enum RedirectCode(u8) {
+    /// Redirect datagrams for the network
+    Network = 0,
+    /// Redirect datagrams for the host
+    Host = 1,
+    /// Redirect datagrams for the type of service and network
+    TosNetwork = 2,
+    /// Redirect datagrams for the type of service and host
+    TosHost = 3,
+}
+}
+
+/// Representation of [`Icmpv4Type`] cast from a slice of bytes instead of constructed.
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[repr(transparent)]
+struct Icmpv4TypeRepr(U8);
+
+impl Icmpv4TypeRepr {
+    /// Get the underlying [`Icmpv4Type`].
+    #[inline]
+    pub(crate) const fn get(&self) -> Icmpv4Type {
+        Icmpv4Type::new(self.0.get())
+    }
+}
+
+impl From<Icmpv4Type> for Icmpv4TypeRepr {
+    #[inline]
+    fn from(value: Icmpv4Type) -> Self {
+        Icmpv4TypeRepr(U8::new(value.get()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::header::internet::IpProtocol;
+    use crate::header::transport::Udp;
+
+    #[test]
+    fn short_header() {
+        let bytes = [0; 7];
+        assert_eq!(Icmpv4::from_bytes(&bytes).unwrap_err(), HeaderTruncated);
+    }
+
+    #[test]
+    fn short_error() {
+        // original IPv4 header is present, but only 7 bytes of its payload follow
+        let bytes = [
+            0x45, 0x00, 0x00, 0x24, 0x00, 0x00, 0x40, 0x00, 0x40, 0x11, 0x00, 0x00, 0xc0, 0xa8,
+            0x00, 0x01, 0xc0, 0xa8, 0x00, 0xc7, 0x30, 0x39, 0x00, 0x35, 0x00, 0x10, 0x00,
+        ];
+        assert_eq!(
+            Icmpv4Error::from_bytes(&bytes).unwrap_err(),
+            HeaderTruncated
+        );
+    }
+
+    #[test]
+    fn port_unreachable() {
+        let bytes = [
+            // ICMPv4 destination unreachable, port unreachable
+            0x03, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // original IPv4 header
+            0x45, 0x00, 0x00, 0x24, 0x00, 0x00, 0x40, 0x00, 0x40, 0x11, 0x00, 0x00, 0xc0, 0xa8,
+            0x00, 0x01, 0xc0, 0xa8, 0x00, 0xc7,
+            // original UDP header, src_port 12345, dst_port 53
+            0x30, 0x39, 0x00, 0x35, 0x00, 0x10, 0x00, 0x00,
+        ];
+
+        let (icmp, payload) = Icmpv4::from_bytes(&bytes).unwrap();
+        assert_eq!(icmp.message_type(), Icmpv4Type::DestUnreachable);
+        assert_eq!(
+            UnreachableCode::new(icmp.code()),
+            UnreachableCode::PortUnreachable
+        );
+        assert!(icmp.is_error());
+
+        let (error, rest) = Icmpv4Error::from_bytes(payload).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(error.header().protocol(), IpProtocol::UDP);
+        assert_eq!(error.data().len(), Icmpv4Error::DATA_LEN);
+
+        let (udp, _) = Udp::from_bytes(error.data()).unwrap();
+        assert_eq!(udp.source_port(), 12345);
+        assert_eq!(udp.destination_port(), 53);
+    }
+
+    #[test]
+    fn redirect() {
+        let bytes = [
+            // ICMPv4 redirect for host, gateway 192.168.0.254
+            0x05, 0x01, 0x00, 0x00, 0xc0, 0xa8, 0x00, 0xfe,
+            // original IPv4 header, 192.168.0.1 → 10.0.0.1
+            0x45, 0x00, 0x00, 0x24, 0x00, 0x00, 0x40, 0x00, 0x40, 0x11, 0x00, 0x00, 0xc0, 0xa8,
+            0x00, 0x01, 0x0a, 0x00, 0x00, 0x01, // original UDP header
+            0x30, 0x39, 0x00, 0x35, 0x00, 0x10, 0x00, 0x00,
+        ];
+
+        let (icmp, payload) = Icmpv4::from_bytes(&bytes).unwrap();
+        assert_eq!(icmp.message_type(), Icmpv4Type::Redirect);
+        assert_eq!(RedirectCode::new(icmp.code()), RedirectCode::Host);
+        assert_eq!(icmp.gateway(), Some(Ipv4Addr::new([192, 168, 0, 254])));
+
+        let (error, _) = Icmpv4Error::from_bytes(payload).unwrap();
+        assert_eq!(error.header().dst(), Ipv4Addr::new([10, 0, 0, 1]));
+    }
+
+    #[test]
+    fn gateway_only_for_redirect() {
+        let bytes = [0x03, 0x01, 0x00, 0x00, 0xc0, 0xa8, 0x00, 0xfe];
+        let (icmp, _) = Icmpv4::from_bytes(&bytes).unwrap();
+        assert_eq!(icmp.gateway(), None);
+    }
+
+    #[test]
+    fn next_hop_mtu() {
+        let mut bytes = [0x03, 0x04, 0x00, 0x00, 0x00, 0x00, 0x05, 0x78];
+        let (icmp, _) = Icmpv4::from_bytes(&bytes).unwrap();
+        assert_eq!(icmp.next_hop_mtu(), Some(1400));
+
+        // port unreachable
+        bytes[1] = 0x03;
+        let (icmp, _) = Icmpv4::from_bytes(&bytes).unwrap();
+        assert_eq!(icmp.next_hop_mtu(), None);
+    }
+}
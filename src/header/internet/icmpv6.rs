@@ -0,0 +1,145 @@
+//! [`Icmpv6`] header
+//!
+//! [`Icmpv6`] header used to report errors, exchange diagnostic messages, and discover neighbors
+//! between IPv6 nodes.
+use core::fmt;
+
+use crate::header::checksum::compute_checksum_chained;
+use crate::header::error::HeaderTruncated;
+use crate::header::primitive::{non_exhaustive_enum, U16, U8};
+use crate::header::utils::as_header;
+use crate::header::Header;
+
+use super::{IpProtocol, Ipv6Addr};
+
+/// An ICMPv6 header. [Read more][RFC 4443]
+///
+/// ICMPv6 is carried by IPv6, next header [`IpProtocol::ICMPv6`][super::IpProtocol::ICMPv6]. Like
+/// ICMPv4, it reports errors back to the source of a packet and performs diagnostics such as echo
+/// (ping). It also carries Neighbor Discovery, which replaces ARP and router discovery for IPv6.
+///
+/// The meaning of the last four bytes of the header depends on the [`Icmpv6Type`] of the message.
+/// Typed views of specific messages, such as [`RouterAdvertisement`][super::RouterAdvertisement],
+/// are constructed from the header and its payload.
+///
+/// [RFC 4443]: https://tools.ietf.org/html/rfc4443#section-2.1
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[repr(C)]
+pub struct Icmpv6 {
+    ty: U8,
+    code: U8,
+    cks: U16,
+    rest: [u8; 4],
+}
+
+impl Icmpv6 {
+    /// Returns an immutable view of `bytes` as an ICMPv6 header followed by a payload or an error
+    /// if the size or contents do not represent a valid ICMPv6 header.
+    #[inline]
+    pub const fn from_bytes(bytes: &[u8]) -> Result<(&Self, &[u8]), HeaderTruncated> {
+        as_header!(Icmpv6, bytes)
+    }
+
+    /// Returns the type of ICMPv6 message.
+    #[inline]
+    pub const fn message_type(&self) -> Icmpv6Type {
+        Icmpv6Type::new(self.ty.get())
+    }
+
+    /// Returns the code of the ICMPv6 message. The meaning of the code depends on the
+    /// [`message_type`][Icmpv6::message_type].
+    #[inline]
+    pub const fn code(&self) -> u8 {
+        self.code.get()
+    }
+
+    /// Returns the checksum of the ICMPv6 header, payload, and IPv6 pseudo-header.
+    #[inline]
+    pub const fn cks(&self) -> u16 {
+        self.cks.get()
+    }
+
+    /// Returns `true` if the message reports an error. Error messages have a type below 128.
+    #[inline]
+    pub const fn is_error(&self) -> bool {
+        self.ty.get() < 128
+    }
+
+    /// Returns the type specific last four bytes of the header.
+    #[inline]
+    pub(crate) const fn rest(&self) -> [u8; 4] {
+        self.rest
+    }
+}
+
+// Sums an ICMPv6 `message` and the IPv6 pseudo-header. A message with a valid checksum sums to !0.
+pub(crate) const fn icmpv6_checksum(src: &Ipv6Addr, dst: &Ipv6Addr, message: &[u8]) -> u16 {
+    let len = (message.len() as u32).to_be_bytes();
+    let next_header = [0, 0, 0, IpProtocol::ICMPv6.get()];
+    compute_checksum_chained(&[src.as_bytes(), dst.as_bytes(), &len, &next_header, message])
+}
+
+impl<'a> Header<'a> for &'a Icmpv6 {
+    #[inline]
+    fn from_bytes(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), HeaderTruncated> {
+        Icmpv6::from_bytes(bytes)
+    }
+}
+
+impl fmt::Display for Icmpv6 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ICMPv6 ({}) code={}", self.message_type(), self.code())
+    }
+}
+
+non_exhaustive_enum! {
+/// An ICMPv6 message type. [Read more][IANA]
+///
+/// A complete list of message types is maintained by the [IANA].
+///
+/// [IANA]: https://www.iana.org/assignments/icmpv6-parameters/icmpv6-parameters.xhtml
+pub enum Icmpv6Type(u8) {
+    DestUnreachable = 1,
+    PacketTooBig = 2,
+    TimeExceeded = 3,
+    ParameterProblem = 4,
+    EchoRequest = 128,
+    EchoReply = 129,
+    MulticastListenerQuery = 130,
+    MulticastListenerReport = 131,
+    MulticastListenerDone = 132,
+    RouterSolicitation = 133,
+    RouterAdvertisement = 134,
+    NeighborSolicitation = 135,
+    NeighborAdvertisement = 136,
+    Redirect = 137,
+    MulticastListenerReportV2 = 143,
+}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_header() {
+        let bytes = [0; 7];
+        assert_eq!(Icmpv6::from_bytes(&bytes).unwrap_err(), HeaderTruncated);
+    }
+
+    #[test]
+    fn echo_request() {
+        let bytes = [0x80, 0x00, 0xbe, 0xef, 0x12, 0x34, 0x00, 0x01, 0xAA, 0xAA];
+        let (icmp, payload) = Icmpv6::from_bytes(&bytes).unwrap();
+        assert_eq!(icmp.message_type(), Icmpv6Type::EchoRequest);
+        assert_eq!(icmp.code(), 0);
+        assert_eq!(icmp.cks(), 0xbeef);
+        assert!(!icmp.is_error());
+        assert_eq!(payload, &[0xAA, 0xAA]);
+        assert_eq!(icmp.to_string(), "ICMPv6 (EchoRequest) code=0");
+
+        let bytes = [0x01, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+        let (icmp, _) = Icmpv6::from_bytes(&bytes).unwrap();
+        assert!(icmp.is_error());
+    }
+}
@@ -42,6 +42,11 @@ pub enum IpProtocol(u8) {
 pub(crate) struct ProtocolRepr(U8);
 
 impl ProtocolRepr {
+    #[inline]
+    pub(crate) const fn new(value: IpProtocol) -> Self {
+        ProtocolRepr(U8::new(value.get()))
+    }
+
     #[inline]
     pub(crate) const fn get(&self) -> IpProtocol {
         IpProtocol::new(self.0.get())
@@ -4,9 +4,12 @@
 use core::fmt;
 use core::mem::size_of;
 
-use crate::header::error::HeaderTruncated;
+use crate::header::checksum::{compute_checksum, compute_checksum_chained};
+use crate::header::error::{ChecksumAssertion, HeaderInvalid, HeaderTruncated, ValueToLarge};
 use crate::header::primitive::{non_exhaustive_enum, U16, U8};
-use crate::header::utils::{as_header, split_at};
+use crate::header::utils::{as_bytes, as_header, as_header_mut, split_at, split_at_mut};
+use crate::header::Header;
+use crate::netdev::Capabilities;
 
 use super::ip::{Dscp, Ecn, IpProtocol, IpVersion, ProtocolRepr};
 use super::StdDscp;
@@ -28,9 +31,13 @@ pub struct Ipv4<'a> {
     options: &'a [u8],
 }
 
+/// The required portion of an IPv4 header, without options.
+///
+/// An owned IPv4 header constructed by [`Ipv4::new_const`]. View it as an [`Ipv4`] header with
+/// [`as_ipv4`][Ipv4Required::as_ipv4].
 #[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
 #[repr(C)]
-struct Ipv4Required {
+pub struct Ipv4Required {
     ver_ihl: VerIhl,
     diff_serv: DiffServ,
     tlen: U16,
@@ -48,12 +55,17 @@ impl<'a> Ipv4<'a> {
     /// the size or contents do not represent a valid IPv4 header. Since IPv4 options are dynamic in
     /// length, they are not included in the header and are instead returned as a split payload.
     #[inline]
-    pub const fn from_bytes(bytes: &'a [u8]) -> Result<(Self, &[u8]), HeaderTruncated> {
+    pub const fn from_bytes(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), HeaderTruncated> {
         let (required, options_payload) = match as_header!(Ipv4Required, bytes) {
             Ok(v) => v,
             Err(e) => return Err(e),
         };
 
+        // IHL counts the required portion of the header too
+        if required.ver_ihl.header_len() < size_of::<Ipv4Required>() {
+            return Err(HeaderTruncated);
+        }
+
         let (options, payload) = match split_at(options_payload, required.ver_ihl.options_len()) {
             Some(v) => v,
             None => return Err(HeaderTruncated),
@@ -62,6 +74,94 @@ impl<'a> Ipv4<'a> {
         Ok((Ipv4 { required, options }, payload))
     }
 
+    /// Same as [`from_bytes`][Ipv4::from_bytes], but also returns [`HeaderInvalid::Malformed`] if
+    /// the IHL or the options are malformed, rather than [`HeaderInvalid::Truncated`] for a short
+    /// buffer:
+    ///
+    /// - Bytes following an End of Option List (EOL) option must be zero padding.
+    /// - Options other than EOL and No Operation (NOP) must have a length of at least 2 that does
+    ///   not run past the end of the header.
+    #[inline]
+    pub const fn from_bytes_strict(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), HeaderInvalid> {
+        let (required, options_payload) = match as_header!(Ipv4Required, bytes) {
+            Ok(v) => v,
+            Err(_) => return Err(HeaderInvalid::Truncated),
+        };
+
+        if required.ver_ihl.header_len() < size_of::<Ipv4Required>() {
+            return Err(HeaderInvalid::Malformed);
+        }
+
+        let (options, payload) = match split_at(options_payload, required.ver_ihl.options_len()) {
+            Some(v) => v,
+            None => return Err(HeaderInvalid::Truncated),
+        };
+
+        if !options_valid(options) {
+            return Err(HeaderInvalid::Malformed);
+        }
+
+        Ok((Ipv4 { required, options }, payload))
+    }
+
+    /// Constructs an IPv4 header without options. Usable in `const` contexts, such as packet
+    /// templates.
+    ///
+    /// The remaining fields take on defaults: DSCP CS0, ECN Non-ECT, ID 0, no fragmentation. The
+    /// header checksum is computed.
+    #[inline]
+    pub const fn new_const(
+        src: Ipv4Addr,
+        dst: Ipv4Addr,
+        protocol: IpProtocol,
+        ttl: u8,
+        total_len: u16,
+    ) -> Ipv4Required {
+        let mut required = Ipv4Required {
+            ver_ihl: VerIhl(U8::new(0x45)),
+            diff_serv: DiffServ(U8::new(0)),
+            tlen: U16::new(total_len),
+            id: U16::new(0),
+            flag_frag: FlagsFragOffset(U16::new(0)),
+            ttl: U8::new(ttl),
+            proto: ProtocolRepr::new(protocol),
+            cks: U16::new(0),
+            src,
+            dst,
+        };
+        // words are summed in native endian, so the checksum is stored in native endian too
+        required.cks = U16((!compute_checksum(required.as_bytes())).to_ne_bytes());
+        required
+    }
+
+    /// Computes the header checksum of the IPv4 header at the start of `bytes` and writes it to the
+    /// header, such as after [`Ipv4Builder::write`]. Only the header and its options are summed,
+    /// not the payload. Returns an error if `bytes` does not hold a valid IPv4 header. See
+    /// [`Ipv4Mut::recompute_checksum`].
+    #[inline]
+    pub fn write_checksum(bytes: &mut [u8]) -> Result<(), HeaderTruncated> {
+        let (mut header, _) = Ipv4::from_bytes_mut(bytes)?;
+        header.recompute_checksum();
+        Ok(())
+    }
+
+    /// Returns a mutable view of `bytes` as an IPv4 header followed by a payload or an error if
+    /// the size or contents do not represent a valid IPv4 header. See [`Ipv4Mut`].
+    #[inline]
+    pub fn from_bytes_mut(
+        bytes: &'a mut [u8],
+    ) -> Result<(Ipv4Mut<'a>, &'a mut [u8]), HeaderTruncated> {
+        let (required, options_payload) = as_header_mut!(Ipv4Required, bytes)?;
+        if required.ver_ihl.header_len() < size_of::<Ipv4Required>() {
+            return Err(HeaderTruncated);
+        }
+        let options_len = required.ver_ihl.options_len();
+        let (options, payload) =
+            split_at_mut(options_payload, options_len).ok_or(HeaderTruncated)?;
+
+        Ok((Ipv4Mut { required, options }, payload))
+    }
+
     /// Always returns [`IpVersion::Ipv4`].
     #[inline]
     pub const fn version(&self) -> IpVersion {
@@ -156,11 +256,56 @@ impl<'a> Ipv4<'a> {
 
     /// Returns iterator IPv4 of [`Ipv4Option`]
     #[inline]
-    pub const fn options(&self) -> Ipv4Options {
+    pub const fn options(&self) -> Ipv4Options<'a> {
         Ipv4Options {
             options: self.options,
         }
     }
+
+    /// Returns the options of the header as raw bytes, including any padding. Useful to emit
+    /// options unchanged, including those [`Ipv4Option`] does not understand.
+    #[inline]
+    pub const fn options_bytes(&self) -> &'a [u8] {
+        self.options
+    }
+
+    /// Returns `true` if both headers have the same fields and options, disregarding the header
+    /// checksum. Useful to compare a built header against a parsed one.
+    #[inline]
+    pub fn eq_ignore_checksum(&self, other: &Ipv4<'_>) -> bool {
+        let (a, b) = (self.required, other.required);
+        a.ver_ihl == b.ver_ihl
+            && a.diff_serv == b.diff_serv
+            && a.tlen == b.tlen
+            && a.id == b.id
+            && a.flag_frag == b.flag_frag
+            && a.ttl == b.ttl
+            && a.proto == b.proto
+            && a.src == b.src
+            && a.dst == b.dst
+            && self.options == other.options
+    }
+
+    /// Verifies the header checksum of a received packet, unless `caps` reports the receiving
+    /// device already verified it or loops packets back. Returns an error if the checksum is
+    /// invalid.
+    #[inline]
+    pub const fn verify(&self, caps: Capabilities) -> Result<(), ChecksumAssertion> {
+        if caps.skips_rx_checksum()
+            || compute_checksum_chained(&[self.required.as_bytes(), self.options]) == !0
+        {
+            Ok(())
+        } else {
+            Err(ChecksumAssertion)
+        }
+    }
+}
+
+impl<'a> Header<'a> for Ipv4<'a> {
+    #[inline]
+    fn from_bytes(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), HeaderTruncated> {
+        Ipv4::from_bytes(bytes)
+    }
 }
 
 impl<'a> fmt::Display for Ipv4<'a> {
@@ -188,36 +333,372 @@ impl<'a> fmt::Display for Ipv4<'a> {
             ecn => write!(f, " ecn={ecn}")?,
         };
 
-        match StdDscp::try_from(self.dscp()) {
-            Ok(StdDscp::CS0) => {}
-            Ok(dscp) => write!(f, " dscp={dscp}")?,
-            Err(_) => write!(f, " dscp={}", self.dscp())?,
+        match StdDscp::from(self.dscp()) {
+            StdDscp::CS0 => {}
+            StdDscp::Unknown(_) => write!(f, " dscp={}", self.dscp())?,
+            dscp => write!(f, " dscp={dscp}")?,
         };
 
         Ok(())
     }
 }
 
+impl Ipv4Required {
+    /// Returns the IPv4 header as a sequence of network endian bytes.
+    #[inline]
+    pub const fn as_bytes(&self) -> &[u8; 20] {
+        as_bytes!(Ipv4Required, self)
+    }
+
+    /// Returns an immutable view of the IPv4 header for reading fields.
+    #[inline]
+    pub const fn as_ipv4(&self) -> Ipv4<'_> {
+        Ipv4 {
+            required: self,
+            options: &[],
+        }
+    }
+}
+
+/// A mutable view of an IPv4 header.
+///
+/// Setters write fields in place and leave the header checksum stale until
+/// [`recompute_checksum`][Ipv4Mut::recompute_checksum] is called.
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Ipv4Mut<'a> {
+    required: &'a mut Ipv4Required,
+    options: &'a mut [u8],
+}
+
+impl<'a> Ipv4Mut<'a> {
+    /// Returns an immutable view of the IPv4 header for reading fields.
+    #[inline]
+    pub fn as_ipv4(&self) -> Ipv4<'_> {
+        Ipv4 {
+            required: self.required,
+            options: self.options,
+        }
+    }
+
+    /// Sets Differentiated Services codepoint (DSCP).
+    #[inline]
+    pub fn set_dscp(&mut self, dscp: Dscp) {
+        self.required.diff_serv.set_dscp(dscp);
+    }
+
+    /// Sets Explicit Congestion Notification (ECN).
+    #[inline]
+    pub fn set_ecn(&mut self, ecn: Ecn) {
+        self.required.diff_serv.set_ecn(ecn);
+    }
+
+    /// Sets the total length of the IPv4 packet.
+    #[inline]
+    pub fn set_total_len(&mut self, len: u16) {
+        self.required.tlen = U16::new(len);
+    }
+
+    /// Sets ID of the IPv4 packet.
+    #[inline]
+    pub fn set_id(&mut self, id: u16) {
+        self.required.id = U16::new(id);
+    }
+
+    /// Sets IPv4 flags.
+    #[inline]
+    pub fn set_flags(&mut self, flags: Ipv4Flags) {
+        self.required.flag_frag.set_flags(flags);
+    }
+
+    /// Sets fragment offset. Only the lower 13 bits are used.
+    #[inline]
+    pub fn set_offset(&mut self, offset: u16) {
+        self.required.flag_frag.set_frag_offset(offset);
+    }
+
+    /// Sets time to live.
+    #[inline]
+    pub fn set_ttl(&mut self, ttl: u8) {
+        self.required.ttl = U8::new(ttl);
+    }
+
+    /// Sets the protocol of the payload.
+    #[inline]
+    pub fn set_protocol(&mut self, protocol: IpProtocol) {
+        self.required.proto = ProtocolRepr::from(protocol);
+    }
+
+    /// Sets header checksum.
+    #[inline]
+    pub fn set_cks(&mut self, cks: u16) {
+        self.required.cks = U16::new(cks);
+    }
+
+    /// Sets source IPv4 address.
+    #[inline]
+    pub fn set_src(&mut self, src: Ipv4Addr) {
+        self.required.src = src;
+    }
+
+    /// Sets destination IPv4 address.
+    #[inline]
+    pub fn set_dst(&mut self, dst: Ipv4Addr) {
+        self.required.dst = dst;
+    }
+
+    /// Computes the header checksum over the header and options and writes it to the header.
+    #[inline]
+    pub fn recompute_checksum(&mut self) {
+        self.required.cks = U16::new(0);
+        let cks = !compute_checksum_chained(&[self.required.as_bytes(), self.options]);
+        // words are summed in native endian, so the checksum is stored in native endian too
+        self.required.cks = U16(cks.to_ne_bytes());
+    }
+}
+
+/// A builder of IPv4 headers without options, for emitting packets.
+///
+/// Unlike [`Ipv4::new_const`], the header checksum is left zero, to be filled in by a separate
+/// checksum pass once the rest of the packet is written, such as [`Ipv4::write_checksum`]. Fields
+/// not set take on defaults: TTL 64, DSCP CS0, ECN Non-ECT, ID 0, no fragmentation and an empty
+/// payload.
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub struct Ipv4Builder {
+    src: Ipv4Addr,
+    dst: Ipv4Addr,
+    protocol: IpProtocol,
+    ttl: u8,
+    dscp: Dscp,
+    ecn: Ecn,
+    id: u16,
+    flags: Ipv4Flags,
+    payload_len: usize,
+}
+
+impl Ipv4Builder {
+    /// Starts a header for a packet from `src` to `dst` carrying `protocol`.
+    #[inline]
+    pub const fn new(src: Ipv4Addr, dst: Ipv4Addr, protocol: IpProtocol) -> Self {
+        Ipv4Builder {
+            src,
+            dst,
+            protocol,
+            ttl: 64,
+            dscp: Dscp(0),
+            ecn: Ecn::NonECT,
+            id: 0,
+            flags: Ipv4Flags::LF,
+            payload_len: 0,
+        }
+    }
+
+    /// Sets time to live.
+    #[inline]
+    pub const fn ttl(mut self, ttl: u8) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Sets Differentiated Services codepoint (DSCP).
+    #[inline]
+    pub const fn dscp(mut self, dscp: Dscp) -> Self {
+        self.dscp = dscp;
+        self
+    }
+
+    /// Sets Explicit Congestion Notification (ECN).
+    #[inline]
+    pub const fn ecn(mut self, ecn: Ecn) -> Self {
+        self.ecn = ecn;
+        self
+    }
+
+    /// Sets ID of the IPv4 packet.
+    #[inline]
+    pub const fn id(mut self, id: u16) -> Self {
+        self.id = id;
+        self
+    }
+
+    /// Sets IPv4 flags.
+    #[inline]
+    pub const fn flags(mut self, flags: Ipv4Flags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Sets the length of the payload following the header.
+    #[inline]
+    pub const fn payload_len(mut self, len: usize) -> Self {
+        self.payload_len = len;
+        self
+    }
+
+    /// Returns the length of the header in bytes.
+    #[inline]
+    pub const fn header_len(&self) -> usize {
+        size_of::<Ipv4Required>()
+    }
+
+    /// Writes the header to the start of `buf` and returns the number of bytes written. Returns an
+    /// error if `buf` is too small to hold the header, or if the total length of the packet does
+    /// not fit in a `u16`.
+    pub fn write(&self, buf: &mut [u8]) -> Result<usize, HeaderTruncated> {
+        let header_len = self.header_len();
+        let total_len = header_len
+            .checked_add(self.payload_len)
+            .and_then(|len| u16::try_from(len).ok())
+            .ok_or(HeaderTruncated)?;
+        let bytes = buf.get_mut(..header_len).ok_or(HeaderTruncated)?;
+
+        let mut required = Ipv4Required {
+            // IHL counts 32-bit words, and there are no options
+            ver_ihl: VerIhl(U8::new(0x40 | (header_len / 4) as u8)),
+            diff_serv: DiffServ(U8::new(0)),
+            tlen: U16::new(total_len),
+            id: U16::new(self.id),
+            flag_frag: FlagsFragOffset(U16::new(0)),
+            ttl: U8::new(self.ttl),
+            proto: ProtocolRepr::new(self.protocol),
+            cks: U16::new(0),
+            src: self.src,
+            dst: self.dst,
+        };
+        required.diff_serv.set_dscp(self.dscp);
+        required.diff_serv.set_ecn(self.ecn);
+        required.flag_frag.set_flags(self.flags);
+
+        bytes.copy_from_slice(required.as_bytes());
+        Ok(header_len)
+    }
+}
+
+// Walks the TLV encoded options, checking padding after EOL and lengths of all other options.
+const fn options_valid(options: &[u8]) -> bool {
+    let mut i = 0;
+    while i < options.len() {
+        match options[i] {
+            // EOL
+            0 => {
+                i += 1;
+                while i < options.len() {
+                    if options[i] != 0 {
+                        return false;
+                    }
+                    i += 1;
+                }
+            }
+            // NOP
+            1 => i += 1,
+            _ => {
+                if i + 1 >= options.len() {
+                    return false;
+                }
+                let len = options[i + 1] as usize;
+                if len < 2 || i + len > options.len() {
+                    return false;
+                }
+                i += len;
+            }
+        }
+    }
+    true
+}
+
 /// Iterator of [`Ipv4Option`].
 ///
-/// Currently no Ipv4 options are supported, so iterating immediately returns [`None`].
+/// Iteration stops after [`Ipv4Option::Eol`], or early if an option claims more bytes than remain
+/// in the header.
 #[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
 pub struct Ipv4Options<'a> {
     options: &'a [u8],
 }
 
 impl<'a> Iterator for Ipv4Options<'a> {
-    type Item = Ipv4Option;
+    type Item = Ipv4Option<'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let _options = self.options;
-        None
+        let (&kind, rest) = self.options.split_first()?;
+        match kind {
+            Ipv4Option::EOL => {
+                self.options = &[];
+                return Some(Ipv4Option::Eol);
+            }
+            Ipv4Option::NOP => {
+                self.options = rest;
+                return Some(Ipv4Option::Nop);
+            }
+            _ => {}
+        }
+
+        // length covers kind and length bytes
+        let Some((data, rest)) = rest
+            .split_first()
+            .and_then(|(&len, rest)| split_at(rest, (len as usize).checked_sub(2)?))
+        else {
+            self.options = &[];
+            return None;
+        };
+        self.options = rest;
+
+        Some(match (kind, data) {
+            (Ipv4Option::ROUTER_ALERT, &[a, b]) => {
+                Ipv4Option::RouterAlert(u16::from_be_bytes([a, b]))
+            }
+            (Ipv4Option::RECORD_ROUTE, data) => Ipv4Option::RecordRoute(data),
+            (Ipv4Option::TIMESTAMP, data) => Ipv4Option::Timestamp(data),
+            (Ipv4Option::SECURITY, data) if data.len() == 9 => Ipv4Option::Security(data),
+            (Ipv4Option::STREAM_ID, &[a, b]) => Ipv4Option::StreamId(u16::from_be_bytes([a, b])),
+            (kind, data) => Ipv4Option::Unknown { kind, data },
+        })
     }
 }
 
-#[derive(Debug)]
+/// An option carried by an IPv4 header. [Read more][RFC 791]
+///
+/// Options are type-length-value (TLV) encoded, with the exception of [`Ipv4Option::Eol`] and
+/// [`Ipv4Option::Nop`]. Data carrying options borrow from the header.
+///
+/// [RFC 791]: https://tools.ietf.org/html/rfc791#section-3.1
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
 #[non_exhaustive]
-pub enum Ipv4Option {}
+pub enum Ipv4Option<'a> {
+    /// End of Option List. Remaining bytes of the header are padding.
+    Eol,
+    /// No Operation. A single byte used to align the following option.
+    Nop,
+    /// Routers should examine the packet more closely. Carries the alert value, `0` for "examine
+    /// packet". [Read more][RFC 2113]
+    ///
+    /// [RFC 2113]: https://tools.ietf.org/html/rfc2113
+    RouterAlert(u16),
+    /// Records the route of the packet. Carries a pointer followed by the recorded addresses.
+    RecordRoute(&'a [u8]),
+    /// Records timestamps along the route of the packet. Carries a pointer, overflow and flags
+    /// followed by the recorded timestamps. [Read more][RFC 781]
+    ///
+    /// [RFC 781]: https://tools.ietf.org/html/rfc781
+    Timestamp(&'a [u8]),
+    /// Carries a SATNET stream identifier. Obsolete.
+    StreamId(u16),
+    /// Carries security, compartmentation, handling restrictions, and TCC, 9 bytes in total.
+    /// Obsolete. [Read more][RFC 791]
+    ///
+    /// [RFC 791]: https://tools.ietf.org/html/rfc791#page-17
+    Security(&'a [u8]),
+    /// An unrecognized option, or a recognized option with an unexpected length.
+    Unknown { kind: u8, data: &'a [u8] },
+}
+
+impl<'a> Ipv4Option<'a> {
+    const EOL: u8 = 0;
+    const NOP: u8 = 1;
+    const RECORD_ROUTE: u8 = 7;
+    const TIMESTAMP: u8 = 68;
+    const SECURITY: u8 = 130;
+    const STREAM_ID: u8 = 136;
+    const ROUTER_ALERT: u8 = 148;
+}
 
 #[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
 #[repr(transparent)]
@@ -250,6 +731,19 @@ impl Ipv4Addr {
         &self.0
     }
 
+    /// Returns the address following this one, or `255.255.255.255` if there is none.
+    #[inline]
+    pub const fn saturating_next(&self) -> Self {
+        Self(u32::from_be_bytes(self.0).saturating_add(1).to_be_bytes())
+    }
+
+    /// Returns the address following this one, wrapping around from `255.255.255.255` to
+    /// `0.0.0.0`.
+    #[inline]
+    pub const fn wrapping_next(&self) -> Self {
+        Self(u32::from_be_bytes(self.0).wrapping_add(1).to_be_bytes())
+    }
+
     /// Returns `true` if address is the 'unspecified', also known as the 'any' address.
     #[inline]
     pub const fn is_unspecified(&self) -> bool {
@@ -376,6 +870,129 @@ impl fmt::Display for Ipv4Addr {
     }
 }
 
+/// An IPv4 address with a network prefix, written in CIDR notation as `192.168.1.1/24`.
+/// [Read more][RFC 4632]
+///
+/// [RFC 4632]: https://tools.ietf.org/html/rfc4632
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
+pub struct Ipv4Cidr {
+    addr: Ipv4Addr,
+    prefix_len: u8,
+}
+
+impl Ipv4Cidr {
+    /// Creates an Ipv4Cidr from an address and a prefix length. Returns an error if the prefix
+    /// length is greater than 32.
+    #[inline]
+    pub const fn new(addr: Ipv4Addr, prefix_len: u8) -> Result<Self, ValueToLarge> {
+        if prefix_len <= 32 {
+            Ok(Self { addr, prefix_len })
+        } else {
+            Err(ValueToLarge)
+        }
+    }
+
+    /// Returns the address.
+    #[inline]
+    pub const fn address(&self) -> Ipv4Addr {
+        self.addr
+    }
+
+    /// Returns the length of the network prefix in bits.
+    #[inline]
+    pub const fn prefix_len(&self) -> u8 {
+        self.prefix_len
+    }
+
+    /// Returns the network mask, such as `255.255.255.0` for a `/24`.
+    #[inline]
+    pub const fn netmask(&self) -> Ipv4Addr {
+        Ipv4Addr(self.mask().to_be_bytes())
+    }
+
+    /// Returns the network address, the address with all host bits cleared.
+    #[inline]
+    pub const fn network(&self) -> Ipv4Addr {
+        Ipv4Addr((u32::from_be_bytes(self.addr.0) & self.mask()).to_be_bytes())
+    }
+
+    /// Returns the broadcast address, the address with all host bits set. A `/32` is its own
+    /// broadcast address, while a `/31` point-to-point link has none ([RFC 3021]).
+    ///
+    /// [RFC 3021]: https://tools.ietf.org/html/rfc3021
+    #[inline]
+    pub const fn broadcast(&self) -> Option<Ipv4Addr> {
+        match self.prefix_len {
+            31 => None,
+            _ => Some(Ipv4Addr(
+                (u32::from_be_bytes(self.addr.0) | !self.mask()).to_be_bytes(),
+            )),
+        }
+    }
+
+    /// Returns `true` if `addr` belongs to the network.
+    #[inline]
+    pub const fn contains(&self, addr: &Ipv4Addr) -> bool {
+        u32::from_be_bytes(addr.0) & self.mask() == u32::from_be_bytes(self.network().0)
+    }
+
+    /// Returns an iterator over the usable host addresses of the network. The network and
+    /// broadcast addresses are excluded, except for `/31` point-to-point links ([RFC 3021]) where
+    /// both addresses are hosts, and `/32` where the address is the only host.
+    ///
+    /// [RFC 3021]: https://tools.ietf.org/html/rfc3021
+    #[inline]
+    pub const fn hosts(&self) -> Ipv4Hosts {
+        let network = u32::from_be_bytes(self.network().0);
+        let last = network | !self.mask();
+        match self.prefix_len {
+            31 | 32 => Ipv4Hosts {
+                next: Some(network),
+                last,
+            },
+            _ => Ipv4Hosts {
+                next: Some(network + 1),
+                last: last - 1,
+            },
+        }
+    }
+
+    #[inline]
+    const fn mask(&self) -> u32 {
+        match self.prefix_len {
+            0 => 0,
+            len => !0 << (32 - len),
+        }
+    }
+}
+
+impl fmt::Display for Ipv4Cidr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.addr, self.prefix_len)
+    }
+}
+
+/// An iterator over the host addresses of an [`Ipv4Cidr`]. See [`Ipv4Cidr::hosts`].
+#[derive(Debug, Clone)]
+pub struct Ipv4Hosts {
+    next: Option<u32>,
+    last: u32,
+}
+
+impl Iterator for Ipv4Hosts {
+    type Item = Ipv4Addr;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.next?;
+        self.next = if next < self.last {
+            Some(next + 1)
+        } else {
+            None
+        };
+        Some(Ipv4Addr(next.to_be_bytes()))
+    }
+}
+
 non_exhaustive_enum! {
 /// IPv4 Flags
 ///
@@ -449,6 +1066,20 @@ impl DiffServ {
     pub const fn ecn(&self) -> Ecn {
         Ecn::new((self.0.get() & Self::ECN_MASK) >> Self::ECN_SHIFT)
     }
+
+    #[inline]
+    pub fn set_dscp(&mut self, dscp: Dscp) {
+        let value =
+            (self.0.get() & !Self::DSCP_MASK) | ((dscp.0 << Self::DSCP_SHIFT) & Self::DSCP_MASK);
+        self.0 = U8::new(value);
+    }
+
+    #[inline]
+    pub fn set_ecn(&mut self, ecn: Ecn) {
+        let value =
+            (self.0.get() & !Self::ECN_MASK) | ((ecn.get() << Self::ECN_SHIFT) & Self::ECN_MASK);
+        self.0 = U8::new(value);
+    }
 }
 
 /// ```text
@@ -477,16 +1108,510 @@ impl FlagsFragOffset {
     pub const fn frag_offset(&self) -> u16 {
         (self.0.get() & Self::FRAG_OFFSET_MASK) >> Self::FRAG_OFFSET_SHIFT
     }
+
+    #[inline]
+    pub fn set_flags(&mut self, flags: Ipv4Flags) {
+        let flags = (flags.get() as u16) << Self::FLAG_SHIFT;
+        let value = (self.0.get() & !Self::FLAG_MASK) | (flags & Self::FLAG_MASK);
+        self.0 = U16::new(value);
+    }
+
+    #[inline]
+    pub fn set_frag_offset(&mut self, offset: u16) {
+        let offset = offset << Self::FRAG_OFFSET_SHIFT;
+        let value = (self.0.get() & !Self::FRAG_OFFSET_MASK) | (offset & Self::FRAG_OFFSET_MASK);
+        self.0 = U16::new(value);
+    }
 }
 
 #[cfg(test)]
 mod tests {
 
     use super::*;
+    use crate::header::checksum::verify_checksum;
 
     #[test]
     fn short_header() {
         let bytes = [0; 19];
         assert_eq!(Ipv4::from_bytes(&bytes).unwrap_err(), HeaderTruncated);
     }
+
+    #[test]
+    fn options() {
+        let mut bytes = [0; 40];
+        bytes[0] = 0x4a;
+        bytes[20..].copy_from_slice(&[
+            0x01, 0x94, 0x04, 0x00, 0x00, 0x07, 0x07, 0x04, 0x0a, 0x00, 0x00, 0x01, 0x44, 0x04,
+            0x05, 0x00, 0x1e, 0x03, 0xAA, 0x00,
+        ]);
+        let (header, _) = Ipv4::from_bytes(&bytes).unwrap();
+
+        for (i, option) in header.options().enumerate() {
+            match option {
+                Ipv4Option::Nop => assert_eq!(i, 0),
+                Ipv4Option::RouterAlert(value) => {
+                    assert_eq!(i, 1);
+                    assert_eq!(value, 0);
+                }
+                Ipv4Option::RecordRoute(data) => {
+                    assert_eq!(i, 2);
+                    assert_eq!(data, &[0x04, 0x0a, 0x00, 0x00, 0x01]);
+                }
+                Ipv4Option::Timestamp(data) => {
+                    assert_eq!(i, 3);
+                    assert_eq!(data, &[0x05, 0x00]);
+                }
+                Ipv4Option::Unknown { kind, data } => {
+                    assert_eq!(i, 4);
+                    assert_eq!(kind, 0x1e);
+                    assert_eq!(data, &[0xAA]);
+                }
+                Ipv4Option::Eol => assert_eq!(i, 5),
+                Ipv4Option::StreamId(_) | Ipv4Option::Security(_) => unreachable!(),
+            }
+        }
+        assert_eq!(header.options().count(), 6);
+        assert_eq!(header.options_bytes().len(), header.options_len());
+        assert_eq!(header.options_bytes(), &bytes[20..]);
+    }
+
+    #[test]
+    fn legacy_options() {
+        let mut bytes = [0; 36];
+        bytes[0] = 0x49;
+        bytes[20..].copy_from_slice(&[
+            0x82, 0x0b, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x88, 0x04, 0x12,
+            0x34, 0x00,
+        ]);
+        let (header, _) = Ipv4::from_bytes_strict(&bytes).unwrap();
+        let mut options = header.options();
+        assert_eq!(options.next(), Some(Ipv4Option::Security(&[0; 9])));
+        assert_eq!(options.next(), Some(Ipv4Option::StreamId(0x1234)));
+        assert_eq!(options.next(), Some(Ipv4Option::Eol));
+        assert_eq!(options.next(), None);
+
+        // fixed lengths are enforced
+        bytes[20..].copy_from_slice(&[
+            0x88, 0x05, 0x12, 0x34, 0x56, 0x82, 0x03, 0xAA, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00,
+        ]);
+        let (header, _) = Ipv4::from_bytes(&bytes).unwrap();
+        let mut options = header.options();
+        assert_eq!(
+            options.next(),
+            Some(Ipv4Option::Unknown {
+                kind: 0x88,
+                data: &[0x12, 0x34, 0x56]
+            })
+        );
+        assert_eq!(
+            options.next(),
+            Some(Ipv4Option::Unknown {
+                kind: 0x82,
+                data: &[0xAA]
+            })
+        );
+    }
+
+    #[test]
+    fn option_overruns_header() {
+        let mut bytes = [0; 24];
+        bytes[0] = 0x46;
+        bytes[20..].copy_from_slice(&[0x01, 0x07, 0x07, 0x00]);
+        let (header, _) = Ipv4::from_bytes(&bytes).unwrap();
+
+        let mut options = header.options();
+        assert_eq!(options.next(), Some(Ipv4Option::Nop));
+        assert_eq!(options.next(), None);
+
+        // length too short to cover itself
+        bytes[20..].copy_from_slice(&[0x07, 0x01, 0x00, 0x00]);
+        let (header, _) = Ipv4::from_bytes(&bytes).unwrap();
+        assert_eq!(header.options().next(), None);
+    }
+
+    #[test]
+    fn strict_options() {
+        // NOP, NOP, EOL, padding
+        let mut bytes = [0; 24];
+        bytes[0] = 0x46;
+        bytes[20..].copy_from_slice(&[0x01, 0x01, 0x00, 0x00]);
+        let (header, _) = Ipv4::from_bytes_strict(&bytes).unwrap();
+        assert_eq!(header.options_len(), 4);
+
+        // non-zero byte after EOL
+        bytes[20..].copy_from_slice(&[0x00, 0x00, 0x01, 0x00]);
+        assert!(Ipv4::from_bytes(&bytes).is_ok());
+        assert_eq!(
+            Ipv4::from_bytes_strict(&bytes).unwrap_err(),
+            HeaderInvalid::Malformed
+        );
+
+        // router alert claims 6 bytes, overrunning the header
+        bytes[20..].copy_from_slice(&[0x94, 0x06, 0x00, 0x00]);
+        assert!(Ipv4::from_bytes(&bytes).is_ok());
+        assert_eq!(
+            Ipv4::from_bytes_strict(&bytes).unwrap_err(),
+            HeaderInvalid::Malformed
+        );
+
+        // router alert
+        bytes[20..].copy_from_slice(&[0x94, 0x04, 0x00, 0x00]);
+        assert!(Ipv4::from_bytes_strict(&bytes).is_ok());
+
+        // length too short to cover itself
+        bytes[20..].copy_from_slice(&[0x94, 0x01, 0x00, 0x00]);
+        assert_eq!(
+            Ipv4::from_bytes_strict(&bytes).unwrap_err(),
+            HeaderInvalid::Malformed
+        );
+
+        // missing length
+        bytes[20..].copy_from_slice(&[0x01, 0x01, 0x01, 0x94]);
+        assert_eq!(
+            Ipv4::from_bytes_strict(&bytes).unwrap_err(),
+            HeaderInvalid::Malformed
+        );
+
+        // options cut short by the buffer
+        assert_eq!(
+            Ipv4::from_bytes_strict(&bytes[..22]).unwrap_err(),
+            HeaderInvalid::Truncated
+        );
+        assert_eq!(
+            Ipv4::from_bytes_strict(&bytes[..19]).unwrap_err(),
+            HeaderInvalid::Truncated
+        );
+
+        // IHL below the required portion
+        bytes[0] = 0x44;
+        assert_eq!(
+            Ipv4::from_bytes_strict(&bytes).unwrap_err(),
+            HeaderInvalid::Malformed
+        );
+    }
+
+    #[test]
+    fn ihl_below_minimum() {
+        let mut bytes = [0; 24];
+        bytes[0] = 0x41;
+        assert_eq!(Ipv4::from_bytes(&bytes).unwrap_err(), HeaderTruncated);
+        assert_eq!(
+            Ipv4::from_bytes_mut(&mut bytes).unwrap_err(),
+            HeaderTruncated
+        );
+    }
+
+    #[test]
+    fn const_header() {
+        const HEADER: Ipv4Required = Ipv4::new_const(
+            Ipv4Addr::new([192, 168, 0, 1]),
+            Ipv4Addr::new([192, 168, 0, 199]),
+            IpProtocol::UDP,
+            64,
+            0x73,
+        );
+        const BYTES: &[u8; 20] = HEADER.as_bytes();
+        const _: () = assert!(BYTES[0] == 0x45);
+
+        assert_eq!(crate::header::checksum::verify_checksum(BYTES), Ok(()));
+
+        let (header, payload) = Ipv4::from_bytes(BYTES).unwrap();
+        assert!(payload.is_empty());
+        assert_eq!(header, HEADER.as_ipv4());
+        assert_eq!(header.version(), IpVersion::Ipv4);
+        assert_eq!(header.header_len(), 20);
+        assert_eq!(header.total_len(), 0x73);
+        assert_eq!(header.ttl(), 64);
+        assert_eq!(header.protocol(), IpProtocol::UDP);
+        assert_eq!(header.src(), Ipv4Addr::new([192, 168, 0, 1]));
+        assert_eq!(header.dst(), Ipv4Addr::new([192, 168, 0, 199]));
+    }
+
+    #[test]
+    fn eq_ignore_checksum() {
+        let built = Ipv4::new_const(
+            Ipv4Addr::new([192, 168, 0, 1]),
+            Ipv4Addr::new([192, 168, 0, 199]),
+            IpProtocol::UDP,
+            64,
+            0x73,
+        );
+        let mut bytes = *built.as_bytes();
+        let (mut header, _) = Ipv4::from_bytes_mut(&mut bytes).unwrap();
+        header.set_cks(0);
+
+        let (parsed, _) = Ipv4::from_bytes(&bytes).unwrap();
+        assert_ne!(parsed, built.as_ipv4());
+        assert!(parsed.eq_ignore_checksum(&built.as_ipv4()));
+
+        let (mut header, _) = Ipv4::from_bytes_mut(&mut bytes).unwrap();
+        header.set_ttl(63);
+        let (parsed, _) = Ipv4::from_bytes(&bytes).unwrap();
+        assert!(!parsed.eq_ignore_checksum(&built.as_ipv4()));
+    }
+
+    #[test]
+    fn next_addr() {
+        let addr = Ipv4Addr::new([192, 168, 0, 255]);
+        assert_eq!(addr.saturating_next(), Ipv4Addr::new([192, 168, 1, 0]));
+        assert_eq!(addr.wrapping_next(), Ipv4Addr::new([192, 168, 1, 0]));
+
+        assert_eq!(Ipv4Addr::BROADCAST.saturating_next(), Ipv4Addr::BROADCAST);
+        assert_eq!(Ipv4Addr::BROADCAST.wrapping_next(), Ipv4Addr::UNSPECIFIED);
+    }
+
+    #[test]
+    fn cidr() {
+        let cidr = Ipv4Cidr::new(Ipv4Addr::new([192, 168, 1, 77]), 24).unwrap();
+        assert_eq!(cidr.netmask(), Ipv4Addr::new([255, 255, 255, 0]));
+        assert_eq!(cidr.network(), Ipv4Addr::new([192, 168, 1, 0]));
+        assert!(cidr.contains(&Ipv4Addr::new([192, 168, 1, 255])));
+        assert!(!cidr.contains(&Ipv4Addr::new([192, 168, 2, 1])));
+        assert_eq!(cidr.to_string(), "192.168.1.77/24");
+        assert_eq!(
+            Ipv4Cidr::new(Ipv4Addr::UNSPECIFIED, 33).unwrap_err(),
+            ValueToLarge
+        );
+    }
+
+    #[test]
+    fn cidr_broadcast() {
+        let cidr = Ipv4Cidr::new(Ipv4Addr::new([10, 0, 0, 0]), 24).unwrap();
+        assert_eq!(cidr.broadcast(), Some(Ipv4Addr::new([10, 0, 0, 255])));
+
+        let cidr = Ipv4Cidr::new(Ipv4Addr::new([10, 0, 0, 1]), 32).unwrap();
+        assert_eq!(cidr.broadcast(), Some(Ipv4Addr::new([10, 0, 0, 1])));
+
+        let cidr = Ipv4Cidr::new(Ipv4Addr::new([10, 0, 0, 1]), 31).unwrap();
+        assert_eq!(cidr.broadcast(), None);
+
+        let cidr = Ipv4Cidr::new(Ipv4Addr::new([10, 0, 0, 1]), 0).unwrap();
+        assert_eq!(cidr.broadcast(), Some(Ipv4Addr::BROADCAST));
+    }
+
+    #[test]
+    fn cidr_hosts() {
+        let cidr = Ipv4Cidr::new(Ipv4Addr::new([192, 168, 1, 0]), 29).unwrap();
+        let mut hosts = cidr.hosts();
+        assert_eq!(hosts.next(), Some(Ipv4Addr::new([192, 168, 1, 1])));
+        assert_eq!(hosts.last(), Some(Ipv4Addr::new([192, 168, 1, 6])));
+        assert_eq!(cidr.hosts().count(), 6);
+
+        let cidr = Ipv4Cidr::new(Ipv4Addr::new([10, 0, 0, 1]), 31).unwrap();
+        assert!(cidr
+            .hosts()
+            .eq([Ipv4Addr::new([10, 0, 0, 0]), Ipv4Addr::new([10, 0, 0, 1])]));
+
+        let cidr = Ipv4Cidr::new(Ipv4Addr::BROADCAST, 32).unwrap();
+        assert!(cidr.hosts().eq([Ipv4Addr::BROADCAST]));
+
+        let cidr = Ipv4Cidr::new(Ipv4Addr::UNSPECIFIED, 0).unwrap();
+        assert_eq!(cidr.hosts().nth(1), Some(Ipv4Addr::new([0, 0, 0, 2])));
+    }
+
+    #[test]
+    fn mutate_header() {
+        let mut bytes = [
+            0x45, 0x00, 0x00, 0x73, 0x00, 0x00, 0x40, 0x00, 0x40, 0x11, 0xb8, 0x61, 0xc0, 0xa8,
+            0x00, 0x01, 0xc0, 0xa8, 0x00, 0xc7, 0xAA, 0xAA,
+        ];
+        let (mut header, payload) = Ipv4::from_bytes_mut(&mut bytes).unwrap();
+        header.set_dscp(StdDscp::EF.into());
+        header.set_ecn(Ecn::ECT0);
+        header.set_total_len(22);
+        header.set_id(0x1234);
+        header.set_flags(Ipv4Flags::MF);
+        header.set_offset(0x0123);
+        header.set_ttl(1);
+        header.set_protocol(IpProtocol::TCP);
+        header.set_cks(0xBEEF);
+        header.set_src(Ipv4Addr::new([10, 0, 0, 1]));
+        header.set_dst(Ipv4Addr::new([10, 0, 0, 2]));
+        assert_eq!(header.as_ipv4().ttl(), 1);
+        payload.copy_from_slice(&[0xBB; 2]);
+
+        let (header, payload) = Ipv4::from_bytes(&bytes).unwrap();
+        assert_eq!(header.header_len(), 20);
+        assert_eq!(StdDscp::from(header.dscp()), StdDscp::EF);
+        assert_eq!(header.ecn(), Ecn::ECT0);
+        assert_eq!(header.total_len(), 22);
+        assert_eq!(header.id(), 0x1234);
+        assert_eq!(header.flags(), Ipv4Flags::MF);
+        assert_eq!(header.offset(), 0x0123);
+        assert_eq!(header.ttl(), 1);
+        assert_eq!(header.protocol(), IpProtocol::TCP);
+        assert_eq!(header.cks(), 0xBEEF);
+        assert_eq!(header.src(), Ipv4Addr::new([10, 0, 0, 1]));
+        assert_eq!(header.dst(), Ipv4Addr::new([10, 0, 0, 2]));
+        assert_eq!(payload, &[0xBB; 2]);
+    }
+
+    #[test]
+    fn recompute_checksum() {
+        // with a NOP, NOP, EOL, padding option
+        let mut bytes = [
+            0x46, 0x00, 0x00, 0x18, 0x00, 0x00, 0x40, 0x00, 0x40, 0x11, 0x00, 0x00, 0xc0, 0xa8,
+            0x00, 0x01, 0xc0, 0xa8, 0x00, 0xc7, 0x01, 0x01, 0x00, 0x00,
+        ];
+        let (mut header, _) = Ipv4::from_bytes_mut(&mut bytes).unwrap();
+        header.recompute_checksum();
+        assert_eq!(verify_checksum(&bytes), Ok(()));
+
+        let (mut header, _) = Ipv4::from_bytes_mut(&mut bytes).unwrap();
+        header.set_src(Ipv4Addr::new([10, 0, 0, 1]));
+        assert_eq!(verify_checksum(&bytes), Err(ChecksumAssertion));
+
+        let (mut header, _) = Ipv4::from_bytes_mut(&mut bytes).unwrap();
+        header.recompute_checksum();
+        assert_eq!(verify_checksum(&bytes), Ok(()));
+        assert_eq!(
+            Ipv4::from_bytes(&bytes).unwrap().0.src(),
+            Ipv4Addr::new([10, 0, 0, 1])
+        );
+    }
+
+    #[test]
+    fn verify() {
+        // with a NOP, NOP, EOL, padding option
+        let mut bytes = [
+            0x46, 0x00, 0x00, 0x18, 0x00, 0x00, 0x40, 0x00, 0x40, 0x11, 0x00, 0x00, 0xc0, 0xa8,
+            0x00, 0x01, 0xc0, 0xa8, 0x00, 0xc7, 0x01, 0x01, 0x00, 0x00,
+        ];
+        Ipv4::write_checksum(&mut bytes).unwrap();
+        let (header, _) = Ipv4::from_bytes(&bytes).unwrap();
+        assert_eq!(header.verify(Capabilities::new()), Ok(()));
+
+        // corrupt the checksum
+        bytes[10] ^= 0xff;
+        let (header, _) = Ipv4::from_bytes(&bytes).unwrap();
+        assert_eq!(header.verify(Capabilities::new()), Err(ChecksumAssertion));
+        assert_eq!(header.verify(Capabilities::RX_CHECKSUM_OFFLOAD), Ok(()));
+        assert_eq!(header.verify(Capabilities::TSO), Err(ChecksumAssertion));
+
+        // looped back before the checksum was computed
+        bytes[10..12].copy_from_slice(&[0, 0]);
+        let (header, _) = Ipv4::from_bytes(&bytes).unwrap();
+        assert_eq!(header.verify(Capabilities::new()), Err(ChecksumAssertion));
+        assert_eq!(header.verify(Capabilities::LOOPBACK), Ok(()));
+    }
+
+    #[test]
+    fn builder() {
+        let builder = Ipv4Builder::new(
+            Ipv4Addr::new([192, 168, 0, 10]),
+            Ipv4Addr::new([192, 168, 0, 1]),
+            IpProtocol::UDP,
+        )
+        .ttl(1)
+        .dscp(Dscp(46))
+        .ecn(Ecn::ECT0)
+        .id(0x1234)
+        .flags(Ipv4Flags::DF)
+        .payload_len(8);
+
+        let mut bytes = [0xFF; 30];
+        assert_eq!(builder.write(&mut bytes), Ok(20));
+        assert_eq!(&bytes[20..], &[0xFF; 10]);
+        let (header, _) = Ipv4::from_bytes(&bytes).unwrap();
+        assert_eq!(header.version(), IpVersion::Ipv4);
+        assert_eq!(header.header_len(), 20);
+        assert_eq!(header.dscp(), Dscp(46));
+        assert_eq!(header.ecn(), Ecn::ECT0);
+        assert_eq!(header.total_len(), 28);
+        assert_eq!(header.id(), 0x1234);
+        assert_eq!(header.flags(), Ipv4Flags::DF);
+        assert_eq!(header.offset(), 0);
+        assert_eq!(header.ttl(), 1);
+        assert_eq!(header.protocol(), IpProtocol::UDP);
+        assert_eq!(header.cks(), 0);
+        assert_eq!(header.src(), Ipv4Addr::new([192, 168, 0, 10]));
+        assert_eq!(header.dst(), Ipv4Addr::new([192, 168, 0, 1]));
+
+        let (mut header, _) = Ipv4::from_bytes_mut(&mut bytes).unwrap();
+        header.recompute_checksum();
+        assert_eq!(verify_checksum(&bytes[..20]), Ok(()));
+
+        assert_eq!(builder.write(&mut [0; 19]), Err(HeaderTruncated));
+        assert_eq!(
+            builder.payload_len(65516).write(&mut bytes),
+            Err(HeaderTruncated)
+        );
+        assert_eq!(builder.payload_len(65515).write(&mut bytes), Ok(20));
+    }
+
+    #[test]
+    fn write_checksum() {
+        // 60 byte header of NOPs and an EOL, followed by a payload
+        let mut bytes = [0x01; 64];
+        bytes[..20].copy_from_slice(&[
+            0x4f, 0x00, 0x00, 0x40, 0x00, 0x00, 0x40, 0x00, 0x40, 0x11, 0xAB, 0xCD, 0xc0, 0xa8,
+            0x00, 0x01, 0xc0, 0xa8, 0x00, 0xc7,
+        ]);
+        bytes[59] = 0x00;
+        bytes[60..].copy_from_slice(&[0xDE, 0xAD, 0xBE, 0xEF]);
+        assert_eq!(Ipv4::write_checksum(&mut bytes), Ok(()));
+        assert_eq!(verify_checksum(&bytes[..60]), Ok(()));
+
+        // the payload is not summed
+        bytes[60..].fill(0);
+        assert_eq!(verify_checksum(&bytes[..60]), Ok(()));
+
+        assert_eq!(Ipv4::write_checksum(&mut bytes[..59]), Err(HeaderTruncated));
+        bytes[0] = 0x44;
+        assert_eq!(Ipv4::write_checksum(&mut bytes), Err(HeaderTruncated));
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn round_trip(
+            src: [u8; 4],
+            dst: [u8; 4],
+            protocol: u8,
+            ttl: u8,
+            total_len: u16,
+            id: u16,
+            dscp in 0..64u8,
+            ecn in 0..4u8,
+            flags in 0..8u8,
+            offset in 0..0x2000u16,
+        ) {
+            let header = Ipv4::new_const(
+                Ipv4Addr::new(src),
+                Ipv4Addr::new(dst),
+                IpProtocol::from(protocol),
+                ttl,
+                total_len,
+            );
+            let mut bytes = *header.as_bytes();
+            proptest::prop_assert_eq!(crate::header::checksum::verify_checksum(&bytes), Ok(()));
+
+            let (mut header, _) = Ipv4::from_bytes_mut(&mut bytes).unwrap();
+            header.set_id(id);
+            header.set_dscp(Dscp(dscp));
+            header.set_ecn(Ecn::from(ecn));
+            header.set_flags(Ipv4Flags::from(flags));
+            header.set_offset(offset);
+            header.set_cks(0);
+            let cks = !compute_checksum(&bytes);
+            bytes[10..12].copy_from_slice(&cks.to_ne_bytes());
+
+            proptest::prop_assert_eq!(crate::header::checksum::verify_checksum(&bytes), Ok(()));
+            let (header, payload) = Ipv4::from_bytes(&bytes).unwrap();
+            proptest::prop_assert!(payload.is_empty());
+            proptest::prop_assert_eq!(header.version(), IpVersion::Ipv4);
+            proptest::prop_assert_eq!(header.header_len(), 20);
+            proptest::prop_assert_eq!(header.src(), Ipv4Addr::new(src));
+            proptest::prop_assert_eq!(header.dst(), Ipv4Addr::new(dst));
+            proptest::prop_assert_eq!(header.protocol(), IpProtocol::from(protocol));
+            proptest::prop_assert_eq!(header.ttl(), ttl);
+            proptest::prop_assert_eq!(header.total_len(), total_len);
+            proptest::prop_assert_eq!(header.id(), id);
+            proptest::prop_assert_eq!(header.dscp(), Dscp(dscp));
+            proptest::prop_assert_eq!(header.ecn(), Ecn::from(ecn));
+            proptest::prop_assert_eq!(header.flags(), Ipv4Flags::from(flags));
+            proptest::prop_assert_eq!(header.offset(), offset);
+            proptest::prop_assert_eq!(header.cks(), u16::from_be_bytes(cks.to_ne_bytes()));
+        }
+    }
 }
@@ -0,0 +1,513 @@
+//! [`Ipv6`] header
+//!
+//! [`Ipv6`] header supporting 128-bit addressing (see [`Ipv6Addr`]).
+use core::fmt;
+use core::net::AddrParseError;
+use core::str::FromStr;
+
+use crate::header::error::HeaderTruncated;
+use crate::header::link::EtherAddr;
+use crate::header::primitive::{U16, U32, U8};
+use crate::header::utils::as_header;
+use crate::header::Header;
+
+use super::ip::{Dscp, Ecn, IpProtocol, IpVersion, ProtocolRepr, StdDscp};
+use super::Ipv6ExtHeaders;
+
+/// An IPv6 header. [Read more][RFC 8200]
+///
+/// IPv6 features 128-bit addressing between uniquely addressed nodes on a network. Unlike IPv4, the
+/// IPv6 header is a fixed size. Optional internet layer information, including fragmentation, is
+/// instead carried by extension headers between the IPv6 header and the upper layer header.
+///
+/// [RFC 8200]: https://tools.ietf.org/html/rfc8200#section-3
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[repr(C)]
+pub struct Ipv6 {
+    ver_tc_flow: VerTcFlow,
+    payload_len: U16,
+    next_header: ProtocolRepr,
+    hop_limit: U8,
+    src: Ipv6Addr,
+    dst: Ipv6Addr,
+}
+
+impl Ipv6 {
+    /// Returns an immutable view of `bytes` as an IPv6 header followed by a payload or an error if
+    /// the size or contents do not represent a valid IPv6 header.
+    #[inline]
+    pub const fn from_bytes(bytes: &[u8]) -> Result<(&Self, &[u8]), HeaderTruncated> {
+        as_header!(Ipv6, bytes)
+    }
+
+    /// Always returns [`IpVersion::Ipv6`].
+    #[inline]
+    pub const fn version(&self) -> IpVersion {
+        self.ver_tc_flow.version()
+    }
+
+    /// Returns Differentiated Services codepoint (DSCP) used to classify and manage network traffic.
+    #[inline]
+    pub const fn dscp(&self) -> Dscp {
+        self.ver_tc_flow.dscp()
+    }
+
+    /// Returns Explicit Congestion Notification (ECN) an optional feature used to indicate
+    /// impending network traffic congestion.
+    #[inline]
+    pub const fn ecn(&self) -> Ecn {
+        self.ver_tc_flow.ecn()
+    }
+
+    /// Returns the flow label, a `u20` used to label sequences of packets to be treated as a
+    /// single flow.
+    #[inline]
+    pub const fn flow_label(&self) -> u32 {
+        self.ver_tc_flow.flow_label()
+    }
+
+    /// Returns the length of the IPv6 payload in bytes, including any extension headers.
+    #[inline]
+    pub const fn payload_len(&self) -> u16 {
+        self.payload_len.get()
+    }
+
+    /// Returns the protocol of the header following the IPv6 header. Either an extension header or
+    /// an upper layer protocol.
+    #[inline]
+    pub const fn next_header(&self) -> IpProtocol {
+        self.next_header.get()
+    }
+
+    /// Returns the number of remaining hops a packet may be forwarded before being discarded.
+    #[inline]
+    pub const fn hop_limit(&self) -> u8 {
+        self.hop_limit.get()
+    }
+
+    /// Returns source IPv6 address.
+    #[inline]
+    pub const fn src(&self) -> Ipv6Addr {
+        self.src
+    }
+
+    /// Returns destination IPv6 address.
+    #[inline]
+    pub const fn dst(&self) -> Ipv6Addr {
+        self.dst
+    }
+
+    /// Returns iterator of the extension headers in `payload`, the payload of this header.
+    #[inline]
+    pub const fn ext_headers<'a>(&self, payload: &'a [u8]) -> Ipv6ExtHeaders<'a> {
+        Ipv6ExtHeaders::new(self.next_header(), payload)
+    }
+
+    /// Walks the extension headers in `payload`, the payload of this header, and returns the
+    /// upper layer protocol and its header and payload. Returns an error if an extension header is
+    /// truncated.
+    ///
+    /// The walk stops at a Fragment header, returning [`IpProtocol::IPv6Frag`], since the upper
+    /// layer may be in another fragment. See [`Ipv6ExtHeaders`].
+    pub fn upper_layer<'a>(
+        &self,
+        payload: &'a [u8],
+    ) -> Result<(IpProtocol, &'a [u8]), HeaderTruncated> {
+        let mut headers = self.ext_headers(payload);
+        for header in headers.by_ref() {
+            header?;
+        }
+        Ok((headers.next_header(), headers.payload()))
+    }
+}
+
+impl<'a> Header<'a> for &'a Ipv6 {
+    #[inline]
+    fn from_bytes(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), HeaderTruncated> {
+        Ipv6::from_bytes(bytes)
+    }
+}
+
+impl fmt::Display for Ipv6 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "IPv6 ({}) {} → {} hop_limit={} len={}",
+            self.next_header(),
+            self.src(),
+            self.dst(),
+            self.hop_limit(),
+            self.payload_len(),
+        )?;
+
+        if self.flow_label() != 0 {
+            write!(f, " flow={:#07x}", self.flow_label())?;
+        }
+
+        match self.ecn() {
+            Ecn::NonECT => {}
+            ecn => write!(f, " ecn={ecn}")?,
+        };
+
+        match StdDscp::from(self.dscp()) {
+            StdDscp::CS0 => {}
+            StdDscp::Unknown(_) => write!(f, " dscp={}", self.dscp())?,
+            dscp => write!(f, " dscp={dscp}")?,
+        };
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
+#[repr(transparent)]
+/// A 128-bit IPv6 address. [Read more][RFC 4291]
+///
+/// Many addresses or address blocks cary a special meaning defined by the [IANA].
+///
+/// [RFC 4291]: https://tools.ietf.org/html/rfc4291#section-2
+/// [IANA]: https://www.iana.org/assignments/iana-ipv6-special-registry/iana-ipv6-special-registry.xhtml
+pub struct Ipv6Addr([u8; 16]);
+
+impl Ipv6Addr {
+    /// The 'localhost' IPv6 address pointing to `::1`.
+    pub const LOCALHOST: Ipv6Addr = Ipv6Addr([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]);
+
+    /// The 'unspecified' IPv6 address, also known as the 'any' address, pointing to `::`.
+    pub const UNSPECIFIED: Ipv6Addr = Ipv6Addr([0; 16]);
+
+    /// The link-local all-routers multicast address `ff02::2`.
+    pub const ALL_ROUTERS: Ipv6Addr =
+        Ipv6Addr([0xff, 0x02, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x02]);
+
+    /// Create an Ipv6Addr from sixteen network endian octets.
+    #[inline]
+    pub const fn new(bytes: [u8; 16]) -> Self {
+        Self(bytes)
+    }
+
+    /// Returns the address formed by Stateless Address Autoconfiguration (SLAAC) from the first
+    /// `prefix_len` bits of `prefix` and the modified EUI-64 interface identifier of `mac`.
+    /// [Read more][RFC 4291]
+    ///
+    /// The interface identifier is `mac` split in half by `ff:fe` with the universal/local bit
+    /// flipped. It fills the low 64 bits of the address, so SLAAC applies only to a `prefix_len`
+    /// of 64. Longer prefixes are cut to 64 bits, and bits beyond a shorter prefix are zero.
+    ///
+    /// [RFC 4291]: https://tools.ietf.org/html/rfc4291#appendix-A
+    #[inline]
+    pub const fn from_slaac(prefix: Ipv6Addr, prefix_len: u8, mac: EtherAddr) -> Self {
+        let prefix_len = if prefix_len > 64 { 64 } else { prefix_len };
+        let mask = match prefix_len {
+            0 => 0,
+            len => u128::MAX << (128 - len as u32),
+        };
+
+        let mac = mac.as_bytes();
+        let iid = [
+            mac[0] ^ 0x02,
+            mac[1],
+            mac[2],
+            0xff,
+            0xfe,
+            mac[3],
+            mac[4],
+            mac[5],
+        ];
+
+        let addr = u128::from_be_bytes(prefix.0) & mask | u64::from_be_bytes(iid) as u128;
+        Self(addr.to_be_bytes())
+    }
+
+    /// Returns the solicited-node multicast address of this address, `ff02::1:ff00:0/104` followed
+    /// by the low 24 bits of the address. Neighbor Solicitations for an address are sent to it.
+    /// [Read more][RFC 4291]
+    ///
+    /// [RFC 4291]: https://tools.ietf.org/html/rfc4291#section-2.7.1
+    #[inline]
+    pub const fn solicited_node(&self) -> Self {
+        Self([
+            0xff, 0x02, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x01, 0xff, self.0[13], self.0[14], self.0[15],
+        ])
+    }
+
+    /// Convert Ipv6Addr to a sequence of octets. Bytes are network endian.
+    #[inline]
+    pub const fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Returns `true` if address is the 'unspecified', also known as the 'any' address.
+    #[inline]
+    pub const fn is_unspecified(&self) -> bool {
+        u128::from_be_bytes(self.0) == 0
+    }
+
+    /// Returns `true` if address is the 'loopback' address `::1`.
+    #[inline]
+    pub const fn is_loopback(&self) -> bool {
+        u128::from_be_bytes(self.0) == 1
+    }
+
+    /// Returns `true` if address belongs to the 'multicast' block `ff00::/8`.
+    #[inline]
+    pub const fn is_multicast(&self) -> bool {
+        self.0[0] == 0xff
+    }
+
+    /// Returns `true` if address belongs to the 'link-local' unicast block `fe80::/10`.
+    #[inline]
+    pub const fn is_link_local(&self) -> bool {
+        self.0[0] == 0xfe && (self.0[1] & 0b1100_0000 == 0x80)
+    }
+}
+
+impl From<core::net::Ipv6Addr> for Ipv6Addr {
+    #[inline]
+    fn from(value: core::net::Ipv6Addr) -> Self {
+        Self(value.octets())
+    }
+}
+
+impl From<Ipv6Addr> for core::net::Ipv6Addr {
+    #[inline]
+    fn from(value: Ipv6Addr) -> Self {
+        core::net::Ipv6Addr::from(value.0)
+    }
+}
+
+impl FromStr for Ipv6Addr {
+    type Err = AddrParseError;
+
+    /// Parses the text forms of [RFC 4291], including `::` compression and an embedded IPv4
+    /// address such as `::ffff:192.0.2.1`.
+    ///
+    /// [RFC 4291]: https://tools.ietf.org/html/rfc4291#section-2.2
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        core::net::Ipv6Addr::from_str(s).map(Ipv6Addr::from)
+    }
+}
+
+impl fmt::Display for Ipv6Addr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // text representation recommended by RFC 5952
+        fmt::Display::fmt(&core::net::Ipv6Addr::from(*self), f)
+    }
+}
+
+/// ```text
+///  0                   1                   2                   3
+///  0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |Version| Traffic Class |           Flow Label                  |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// ```
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+struct VerTcFlow(U32);
+
+impl VerTcFlow {
+    const VERSION_MASK: u32 = 0xF000_0000;
+    const VERSION_SHIFT: usize = 28;
+
+    const DSCP_MASK: u32 = 0x0FC0_0000;
+    const DSCP_SHIFT: usize = 22;
+
+    const ECN_MASK: u32 = 0x0030_0000;
+    const ECN_SHIFT: usize = 20;
+
+    const FLOW_LABEL_MASK: u32 = 0x000F_FFFF;
+    const FLOW_LABEL_SHIFT: usize = 0;
+
+    /// Should return [`IpVersion::Ipv6`]
+    #[inline]
+    pub const fn version(&self) -> IpVersion {
+        IpVersion::new(((self.0.get() & Self::VERSION_MASK) >> Self::VERSION_SHIFT) as u8)
+    }
+
+    /// Returns a `u6`
+    #[inline]
+    pub const fn dscp(&self) -> Dscp {
+        Dscp(((self.0.get() & Self::DSCP_MASK) >> Self::DSCP_SHIFT) as u8)
+    }
+
+    /// Returns a `u2`
+    #[inline]
+    pub const fn ecn(&self) -> Ecn {
+        Ecn::new(((self.0.get() & Self::ECN_MASK) >> Self::ECN_SHIFT) as u8)
+    }
+
+    /// Returns a `u20`
+    #[inline]
+    pub const fn flow_label(&self) -> u32 {
+        (self.0.get() & Self::FLOW_LABEL_MASK) >> Self::FLOW_LABEL_SHIFT
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::header::transport::Udp;
+
+    // UDP 2001:db8::1 → 2001:db8::2, hop_limit=64, len=16, traffic class 0xb9, flow label 0x12345
+    const BYTES: [u8; 40] = [
+        0x6b, 0x91, 0x23, 0x45, 0x00, 0x10, 0x11, 0x40, 0x20, 0x01, 0x0d, 0xb8, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x20, 0x01, 0x0d, 0xb8, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02,
+    ];
+
+    #[test]
+    fn short_header() {
+        let bytes = [0; 39];
+        assert_eq!(Ipv6::from_bytes(&bytes).unwrap_err(), HeaderTruncated);
+    }
+
+    #[test]
+    fn fields() {
+        let (header, payload) = Ipv6::from_bytes(&BYTES).unwrap();
+        assert!(payload.is_empty());
+        assert_eq!(header.version(), IpVersion::Ipv6);
+        assert_eq!(header.payload_len(), 16);
+        assert_eq!(header.next_header(), IpProtocol::UDP);
+        assert_eq!(header.hop_limit(), 64);
+        assert_eq!(format!("{}", header.src()), "2001:db8::1");
+        assert_eq!(format!("{}", header.dst()), "2001:db8::2");
+    }
+
+    #[test]
+    fn flow_label() {
+        let (header, _) = Ipv6::from_bytes(&BYTES).unwrap();
+        assert_eq!(header.flow_label(), 0x12345);
+        assert!(format!("{header}").contains(" flow=0x12345"));
+
+        let mut bytes = BYTES;
+        bytes[1..4].copy_from_slice(&[0x90, 0x00, 0x00]);
+        let (header, _) = Ipv6::from_bytes(&bytes).unwrap();
+        assert_eq!(header.flow_label(), 0);
+        assert!(!format!("{header}").contains("flow="));
+    }
+
+    #[test]
+    fn upper_layer() {
+        // Hop-by-Hop with a Router Alert, then UDP
+        let mut bytes = BYTES.to_vec();
+        bytes[6] = 0x00;
+        bytes.extend_from_slice(&[0x11, 0x00, 0x05, 0x02, 0x00, 0x00, 0x01, 0x00]);
+        bytes.extend_from_slice(&[0x13, 0x88, 0x00, 0x35, 0x00, 0x08, 0x00, 0x00]);
+
+        let (header, payload) = Ipv6::from_bytes(&bytes).unwrap();
+        let (protocol, payload) = header.upper_layer(payload).unwrap();
+        assert_eq!(protocol, IpProtocol::UDP);
+        let (udp, _) = Udp::from_bytes(payload).unwrap();
+        assert_eq!(udp.source_port(), 5000);
+        assert_eq!(udp.destination_port(), 53);
+
+        // no extension headers
+        let (header, payload) = Ipv6::from_bytes(&BYTES).unwrap();
+        assert_eq!(header.upper_layer(payload), Ok((IpProtocol::UDP, payload)));
+
+        // truncated Hop-by-Hop
+        let (header, payload) = Ipv6::from_bytes(&bytes[..44]).unwrap();
+        assert_eq!(header.upper_layer(payload), Err(HeaderTruncated));
+    }
+
+    #[test]
+    fn ipv6_addr() {
+        assert!(Ipv6Addr::UNSPECIFIED.is_unspecified());
+        assert!(Ipv6Addr::LOCALHOST.is_loopback());
+
+        let addr = Ipv6Addr::new([0xfe, 0x80, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]);
+        assert!(addr.is_link_local());
+        assert!(!addr.is_multicast());
+        assert_eq!(format!("{addr}"), "fe80::1");
+
+        let addr = Ipv6Addr::new([0xff, 0x02, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xfb]);
+        assert!(addr.is_multicast());
+        assert!(!addr.is_link_local());
+        assert_eq!(format!("{addr}"), "ff02::fb");
+    }
+
+    #[test]
+    fn parse_addr() {
+        let addr: Ipv6Addr = "::1".parse().unwrap();
+        assert_eq!(addr, Ipv6Addr::LOCALHOST);
+
+        let addr: Ipv6Addr = "fe80::1".parse().unwrap();
+        assert_eq!(
+            addr,
+            Ipv6Addr::new([0xfe, 0x80, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1])
+        );
+        assert!(addr.is_link_local());
+
+        let addr: Ipv6Addr = "2001:db8::1".parse().unwrap();
+        assert_eq!(
+            addr,
+            Ipv6Addr::new([0x20, 0x01, 0x0d, 0xb8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1])
+        );
+
+        let addr: Ipv6Addr = "::ffff:192.0.2.1".parse().unwrap();
+        assert_eq!(
+            addr,
+            Ipv6Addr::new([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xff, 0xff, 192, 0, 2, 1])
+        );
+        assert_eq!(format!("{addr}"), "::ffff:192.0.2.1");
+
+        assert!("1::2::3".parse::<Ipv6Addr>().is_err());
+        assert!("2001:db8::1/64".parse::<Ipv6Addr>().is_err());
+    }
+
+    #[test]
+    fn core_net_addr() {
+        let addr = core::net::Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+        let converted = Ipv6Addr::from(addr);
+        assert_eq!(format!("{converted}"), "2001:db8::1");
+        assert_eq!(core::net::Ipv6Addr::from(converted), addr);
+    }
+
+    #[test]
+    fn solicited_node() {
+        let addr = Ipv6Addr::new([
+            0x20, 0x01, 0x0d, 0xb8, 0, 0, 0, 0, 0x02, 0x1b, 0x21, 0xff, 0xfe, 0x3c, 0x4d, 0x5e,
+        ]);
+        let group = addr.solicited_node();
+        assert_eq!(format!("{group}"), "ff02::1:ff3c:4d5e");
+        assert!(group.is_multicast());
+    }
+
+    #[test]
+    fn slaac_addr() {
+        let prefix = Ipv6Addr::new([
+            0x20, 0x01, 0x0d, 0xb8, 0x00, 0x01, 0x00, 0x00, 0, 0, 0, 0, 0, 0, 0, 0,
+        ]);
+        let mac = EtherAddr::new([0x00, 0x1b, 0x21, 0x3c, 0x4d, 0x5e]);
+        let addr = Ipv6Addr::from_slaac(prefix, 64, mac);
+        assert_eq!(format!("{addr}"), "2001:db8:1:0:21b:21ff:fe3c:4d5e");
+
+        // a locally administered MAC has the bit cleared instead
+        let mac = EtherAddr::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x01]);
+        let link_local = Ipv6Addr::new([0xfe, 0x80, 0, 0, 0, 0, 0, 0, 0xAA, 0, 0, 0, 0, 0, 0, 0]);
+        let addr = Ipv6Addr::from_slaac(link_local, 64, mac);
+        assert_eq!(format!("{addr}"), "fe80::ff:fe00:1");
+        assert!(addr.is_link_local());
+
+        // prefix bits beyond the prefix length are dropped
+        let addr = Ipv6Addr::from_slaac(prefix, 16, mac);
+        assert_eq!(format!("{addr}"), "2001::ff:fe00:1");
+    }
+
+    #[test]
+    fn traffic_class() {
+        let (header, _) = Ipv6::from_bytes(&BYTES).unwrap();
+        assert_eq!(StdDscp::from(header.dscp()), StdDscp::EF);
+        assert_eq!(header.ecn(), Ecn::ECT1);
+        assert!(format!("{header}").ends_with(" ecn=ECT1 dscp=EF"));
+
+        let mut bytes = BYTES;
+        bytes[0..2].copy_from_slice(&[0x60, 0x01]);
+        let (header, _) = Ipv6::from_bytes(&bytes).unwrap();
+        assert_eq!(StdDscp::from(header.dscp()), StdDscp::CS0);
+        assert_eq!(header.ecn(), Ecn::NonECT);
+        assert!(!format!("{header}").contains("ecn="));
+        assert!(!format!("{header}").contains("dscp="));
+    }
+}
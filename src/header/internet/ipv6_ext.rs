@@ -0,0 +1,663 @@
+//! IPv6 extension headers
+//!
+//! Extension headers sit between the IPv6 header and the upper layer header. Each one names the
+//! header that follows it, forming a chain that ends at the upper layer protocol.
+use core::fmt;
+use core::mem::size_of;
+
+use crate::header::error::HeaderTruncated;
+use crate::header::primitive::{non_exhaustive_enum, U8};
+use crate::header::utils::{as_header, split_at};
+use crate::header::Header;
+
+use super::ip::{IpProtocol, ProtocolRepr};
+use super::Ipv6Addr;
+
+// Defines a view of an extension header carrying only options, such as Hop-by-Hop Options and
+// Destination Options, which differ in name and meaning but not in layout.
+macro_rules! options_header {
+    ($(#[$attr:meta])* $name:ident, $header:literal, $display:literal) => {
+        $(#[$attr])*
+        #[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+        pub struct $name<'a> {
+            required: &'a ExtRequired,
+            options: &'a [u8],
+        }
+
+        impl<'a> $name<'a> {
+            #[doc = concat!("Returns an immutable view of `bytes` as a ", $header, " header")]
+            /// followed by a payload or an error if the size or contents do not represent a
+            #[doc = concat!("valid ", $header, " header.")]
+            #[inline]
+            pub const fn from_bytes(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), HeaderTruncated> {
+                let (required, options_payload) = match as_header!(ExtRequired, bytes) {
+                    Ok(v) => v,
+                    Err(e) => return Err(e),
+                };
+
+                let options_len = required.len.options_len();
+                let (options, payload) = match split_at(options_payload, options_len) {
+                    Some(v) => v,
+                    None => return Err(HeaderTruncated),
+                };
+
+                Ok(($name { required, options }, payload))
+            }
+
+            /// Returns the protocol of the header following this one.
+            #[inline]
+            pub const fn next_header(&self) -> IpProtocol {
+                self.required.next_header.get()
+            }
+
+            #[doc = concat!("Returns the length of the ", $header, " header in bytes.")]
+            /// The length is encoded in 8-octet units, not including the first 8 octets, so it is
+            /// always a multiple of 8.
+            #[inline]
+            pub const fn header_len(&self) -> usize {
+                self.required.len.header_len()
+            }
+
+            /// Returns iterator of [`Ipv6Option`].
+            #[inline]
+            pub const fn options(&self) -> Ipv6Options<'a> {
+                Ipv6Options {
+                    options: self.options,
+                }
+            }
+        }
+
+        impl<'a> Header<'a> for $name<'a> {
+            #[inline]
+            fn from_bytes(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), HeaderTruncated> {
+                $name::from_bytes(bytes)
+            }
+        }
+
+        impl<'a> fmt::Display for $name<'a> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(
+                    f,
+                    concat!("IPv6 ", $display, " ({}) len={}"),
+                    self.next_header(),
+                    self.header_len()
+                )
+            }
+        }
+    };
+}
+
+options_header! {
+/// An IPv6 Hop-by-Hop Options header. [Read more][RFC 8200]
+///
+/// Carries options that must be examined by every node along a packet's delivery path, such as
+/// Router Alert and Jumbo Payload. When present, it must immediately follow the IPv6 header.
+///
+/// [RFC 8200]: https://tools.ietf.org/html/rfc8200#section-4.3
+HopByHop, "Hop-by-Hop Options", "Hop-by-Hop"
+}
+
+options_header! {
+/// An IPv6 Destination Options header. [Read more][RFC 8200]
+///
+/// Carries options that need only be examined by a packet's destination, such as the Home Address
+/// option of Mobile IPv6. When it precedes a [`Routing`] header, it is also examined by each node
+/// listed in the Routing header.
+///
+/// [RFC 8200]: https://tools.ietf.org/html/rfc8200#section-4.6
+DestinationOptions, "Destination Options", "Destination Options"
+}
+
+/// The leading fields shared by IPv6 extension headers with a Hdr Ext Len field.
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[repr(C)]
+struct ExtRequired {
+    next_header: ProtocolRepr,
+    len: ExtLen,
+}
+
+/// An IPv6 Routing header. [Read more][RFC 8200]
+///
+/// Lists one or more intermediate nodes to be visited on the way to a packet's destination.
+/// Segments left indicates the number of listed nodes still to be visited.
+///
+/// The Type 0 routing header is deprecated by [RFC 5095] due to its use in amplification attacks.
+/// Nodes should not process it, but may wish to detect it.
+///
+/// [RFC 8200]: https://tools.ietf.org/html/rfc8200#section-4.4
+/// [RFC 5095]: https://tools.ietf.org/html/rfc5095
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct Routing<'a> {
+    required: &'a RoutingRequired,
+    data: &'a [u8],
+}
+
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[repr(C)]
+struct RoutingRequired {
+    ext: ExtRequired,
+    routing_type: U8,
+    segments_left: U8,
+}
+
+impl<'a> Routing<'a> {
+    /// Returns an immutable view of `bytes` as a Routing header followed by a payload or an error
+    /// if the size or contents do not represent a valid Routing header.
+    ///
+    /// For routing types carrying addresses, the header extension length must fit a whole number
+    /// of addresses.
+    #[inline]
+    pub const fn from_bytes(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), HeaderTruncated> {
+        let (required, data_payload) = match as_header!(RoutingRequired, bytes) {
+            Ok(v) => v,
+            Err(e) => return Err(e),
+        };
+
+        let data_len = required.ext.len.header_len() - size_of::<RoutingRequired>();
+        let (data, payload) = match split_at(data_payload, data_len) {
+            Some(v) => v,
+            None => return Err(HeaderTruncated),
+        };
+
+        let header = Routing { required, data };
+        match header.addresses_bytes() {
+            Ok(_) => Ok((header, payload)),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Returns the protocol of the header following this one.
+    #[inline]
+    pub const fn next_header(&self) -> IpProtocol {
+        self.required.ext.next_header.get()
+    }
+
+    /// Returns the length of the Routing header in bytes. The length is encoded in 8-octet units,
+    /// not including the first 8 octets, so it is always a multiple of 8.
+    #[inline]
+    pub const fn header_len(&self) -> usize {
+        self.required.ext.len.header_len()
+    }
+
+    /// Returns the variant of Routing header.
+    #[inline]
+    pub const fn routing_type(&self) -> RoutingType {
+        RoutingType::new(self.required.routing_type.get())
+    }
+
+    /// Returns the number of route segments remaining before reaching the final destination.
+    #[inline]
+    pub const fn segments_left(&self) -> u8 {
+        self.required.segments_left.get()
+    }
+
+    /// Returns iterator of the [`Ipv6Addr`]s listed in the header. Only [`RoutingType::Type0`],
+    /// [`RoutingType::Type2`], and [`RoutingType::Segment`] headers carry a list of addresses. For
+    /// any other routing type, iterating immediately returns [`None`].
+    #[inline]
+    pub const fn addresses(&self) -> Ipv6Addrs<'a> {
+        Ipv6Addrs {
+            addrs: match self.addresses_bytes() {
+                Ok(addrs) => addrs,
+                Err(_) => &[],
+            },
+        }
+    }
+
+    // Type specific data begins with 4 bytes of reserved space for types 0 and 2. For type 4, those
+    // 4 bytes contain last entry, flags, and tag. Addresses follow.
+    const fn addresses_bytes(&self) -> Result<&'a [u8], HeaderTruncated> {
+        const PREFIX_LEN: usize = 4;
+        const ADDR_LEN: usize = size_of::<Ipv6Addr>();
+
+        let addrs = match split_at(self.data, PREFIX_LEN) {
+            Some((_, addrs)) => addrs,
+            None => return Err(HeaderTruncated),
+        };
+
+        match self.routing_type() {
+            RoutingType::Type0 | RoutingType::Type2 if addrs.len() % ADDR_LEN == 0 => Ok(addrs),
+            RoutingType::Type0 | RoutingType::Type2 => Err(HeaderTruncated),
+            RoutingType::Segment => {
+                // last entry is the index of the last address in the segment list
+                let len = (self.data[0] as usize + 1) * ADDR_LEN;
+                match split_at(addrs, len) {
+                    Some((addrs, _tlvs)) => Ok(addrs),
+                    None => Err(HeaderTruncated),
+                }
+            }
+            _ => Ok(&[]),
+        }
+    }
+}
+
+impl<'a> Header<'a> for Routing<'a> {
+    #[inline]
+    fn from_bytes(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), HeaderTruncated> {
+        Routing::from_bytes(bytes)
+    }
+}
+
+impl<'a> fmt::Display for Routing<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "IPv6 Routing ({}) type={} segments_left={}",
+            self.next_header(),
+            self.routing_type(),
+            self.segments_left(),
+        )
+    }
+}
+
+non_exhaustive_enum! {
+/// An IPv6 Routing header type. [Read more][IANA]
+///
+/// [IANA]: https://www.iana.org/assignments/ipv6-parameters/ipv6-parameters.xhtml#ipv6-parameters-3
+pub enum RoutingType(u8) {
+    /// Source Route, deprecated by RFC 5095
+    Type0 = 0,
+    /// Nimrod, deprecated
+    Nimrod = 1,
+    /// Type 2 Routing Header used by Mobile IPv6
+    Type2 = 2,
+    /// Routing Protocol for Low-Power and Lossy Networks Source Route Header
+    Rpl = 3,
+    /// Segment Routing Header
+    Segment = 4,
+}
+}
+
+/// Iterator of [`Ipv6Addr`] listed in a [`Routing`] header.
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct Ipv6Addrs<'a> {
+    addrs: &'a [u8],
+}
+
+impl<'a> Iterator for Ipv6Addrs<'a> {
+    type Item = Ipv6Addr;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let addrs = self.addrs;
+        let (addr, rest) = as_header!(Ipv6Addr, addrs).ok()?;
+        self.addrs = rest;
+        Some(*addr)
+    }
+}
+
+/// Iterator of the extension headers following an IPv6 header.
+///
+/// Walks the chain of [`HopByHop`], [`Routing`], and [`DestinationOptions`] headers, stopping at
+/// the first header that is not one of them, such as the upper layer protocol. A Fragment header
+/// also stops the walk, since the headers after it may be in another fragment. Once the iterator
+/// returns [`None`], [`next_header`][Ipv6ExtHeaders::next_header] and
+/// [`payload`][Ipv6ExtHeaders::payload] describe what follows the extension headers.
+///
+/// If an extension header is truncated, the iterator returns an error once and then stops.
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct Ipv6ExtHeaders<'a> {
+    next_header: IpProtocol,
+    payload: &'a [u8],
+    done: bool,
+}
+
+impl<'a> Ipv6ExtHeaders<'a> {
+    /// Constructs an iterator of the extension headers in `payload`, beginning with a header of
+    /// protocol `next_header`, such as the [`next_header`][super::Ipv6::next_header] of an IPv6
+    /// header and its payload.
+    #[inline]
+    pub const fn new(next_header: IpProtocol, payload: &'a [u8]) -> Self {
+        Ipv6ExtHeaders {
+            next_header,
+            payload,
+            done: false,
+        }
+    }
+
+    /// Returns the protocol of the header following the extension headers walked so far.
+    #[inline]
+    pub const fn next_header(&self) -> IpProtocol {
+        self.next_header
+    }
+
+    /// Returns the bytes following the extension headers walked so far.
+    #[inline]
+    pub const fn payload(&self) -> &'a [u8] {
+        self.payload
+    }
+}
+
+impl<'a> Iterator for Ipv6ExtHeaders<'a> {
+    type Item = Result<Ipv6ExtHeader<'a>, HeaderTruncated>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let result = match self.next_header {
+            IpProtocol::HopByHop => HopByHop::from_bytes(self.payload).map(|(header, rest)| {
+                (header.next_header(), Ipv6ExtHeader::HopByHop(header), rest)
+            }),
+            IpProtocol::IPv6Route => Routing::from_bytes(self.payload)
+                .map(|(header, rest)| (header.next_header(), Ipv6ExtHeader::Routing(header), rest)),
+            IpProtocol::IPv6Opts => {
+                DestinationOptions::from_bytes(self.payload).map(|(header, rest)| {
+                    (
+                        header.next_header(),
+                        Ipv6ExtHeader::DestinationOptions(header),
+                        rest,
+                    )
+                })
+            }
+            _ => {
+                self.done = true;
+                return None;
+            }
+        };
+
+        Some(match result {
+            Ok((next_header, header, rest)) => {
+                self.next_header = next_header;
+                self.payload = rest;
+                Ok(header)
+            }
+            Err(e) => {
+                self.done = true;
+                Err(e)
+            }
+        })
+    }
+}
+
+/// An IPv6 extension header yielded by [`Ipv6ExtHeaders`].
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[non_exhaustive]
+pub enum Ipv6ExtHeader<'a> {
+    HopByHop(HopByHop<'a>),
+    Routing(Routing<'a>),
+    DestinationOptions(DestinationOptions<'a>),
+}
+
+impl<'a> fmt::Display for Ipv6ExtHeader<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Ipv6ExtHeader::HopByHop(header) => header.fmt(f),
+            Ipv6ExtHeader::Routing(header) => header.fmt(f),
+            Ipv6ExtHeader::DestinationOptions(header) => header.fmt(f),
+        }
+    }
+}
+
+/// Iterator of [`Ipv6Option`].
+///
+/// Iteration stops early if an option claims more bytes than remain in the header.
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct Ipv6Options<'a> {
+    options: &'a [u8],
+}
+
+impl<'a> Iterator for Ipv6Options<'a> {
+    type Item = Ipv6Option<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (&kind, rest) = self.options.split_first()?;
+        if kind == Ipv6Option::PAD1 {
+            self.options = rest;
+            return Some(Ipv6Option::Pad1);
+        }
+
+        let Some((&len, rest)) = rest.split_first() else {
+            self.options = &[];
+            return None;
+        };
+        let Some((data, rest)) = split_at(rest, len as usize) else {
+            self.options = &[];
+            return None;
+        };
+        self.options = rest;
+
+        Some(match (kind, data) {
+            (Ipv6Option::PADN, data) => Ipv6Option::PadN(data.len() as u8),
+            (Ipv6Option::ROUTER_ALERT, &[a, b]) => {
+                Ipv6Option::RouterAlert(RouterAlert::new(u16::from_be_bytes([a, b])))
+            }
+            (Ipv6Option::JUMBO, &[a, b, c, d]) => {
+                Ipv6Option::Jumbo(u32::from_be_bytes([a, b, c, d]))
+            }
+            (kind, data) => Ipv6Option::Unknown { kind, data },
+        })
+    }
+}
+
+/// An option carried by the Hop-by-Hop Options or Destination Options header. [Read more][RFC
+/// 8200]
+///
+/// Options are type-length-value (TLV) encoded, with the exception of [`Ipv6Option::Pad1`].
+///
+/// [RFC 8200]: https://tools.ietf.org/html/rfc8200#section-4.2
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[non_exhaustive]
+pub enum Ipv6Option<'a> {
+    /// A single byte of padding.
+    Pad1,
+    /// Two or more bytes of padding. Carries the number of padding bytes following the length.
+    PadN(u8),
+    /// Packet contains information that may require special processing by routers along the path.
+    /// [Read more][RFC 2711]
+    ///
+    /// [RFC 2711]: https://tools.ietf.org/html/rfc2711
+    RouterAlert(RouterAlert),
+    /// Length of a packet with a payload larger than 65,535 bytes. [Read more][RFC 2675]
+    ///
+    /// [RFC 2675]: https://tools.ietf.org/html/rfc2675
+    Jumbo(u32),
+    /// An unrecognized option, or a recognized option with an unexpected length.
+    Unknown { kind: u8, data: &'a [u8] },
+}
+
+impl<'a> Ipv6Option<'a> {
+    const PAD1: u8 = 0x00;
+    const PADN: u8 = 0x01;
+    const ROUTER_ALERT: u8 = 0x05;
+    const JUMBO: u8 = 0xC2;
+}
+
+non_exhaustive_enum! {
+/// The value of a Router Alert option. [Read more][IANA]
+///
+/// [IANA]: https://www.iana.org/assignments/ipv6-routeralert-values/ipv6-routeralert-values.xhtml
+pub enum RouterAlert(u16) {
+    /// Datagram contains a Multicast Listener Discovery message
+    Mld = 0,
+    /// Datagram contains RSVP message
+    Rsvp = 1,
+    /// Datagram contains an Active Networks message
+    ActiveNetworks = 2,
+}
+}
+
+/// ```text
+///  0 1 2 3 4 5 6 7
+/// +-+-+-+-+-+-+-+-+
+/// |  Hdr Ext Len  |
+/// +-+-+-+-+-+-+-+-+
+/// ```
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+struct ExtLen(U8);
+
+impl ExtLen {
+    /// Returns extension header length in bytes
+    #[inline]
+    pub const fn header_len(&self) -> usize {
+        (self.0.get() as usize + 1) * 8
+    }
+
+    /// Returns extension header length minus the next header and length fields (2 bytes)
+    #[inline]
+    pub const fn options_len(&self) -> usize {
+        self.header_len() - size_of::<ExtRequired>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_header() {
+        let bytes = [0; 7];
+        assert_eq!(HopByHop::from_bytes(&bytes).unwrap_err(), HeaderTruncated);
+
+        // length of 1 requires 16 bytes
+        let bytes = [0x11, 0x01, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        assert_eq!(HopByHop::from_bytes(&bytes).unwrap_err(), HeaderTruncated);
+    }
+
+    #[test]
+    fn router_alert() {
+        let bytes = [
+            0x3a, 0x00, 0x05, 0x02, 0x00, 0x00, 0x01, 0x00, 0xFF, 0xFF, 0xFF, 0xFF,
+        ];
+        let (header, payload) = HopByHop::from_bytes(&bytes).unwrap();
+        assert_eq!(header.next_header(), IpProtocol::ICMPv6);
+        assert_eq!(header.header_len(), 8);
+        assert_eq!(payload, &[0xFF; 4]);
+
+        let mut options = header.options();
+        assert_eq!(
+            options.next(),
+            Some(Ipv6Option::RouterAlert(RouterAlert::Mld))
+        );
+        assert_eq!(options.next(), Some(Ipv6Option::PadN(0)));
+        assert_eq!(options.next(), None);
+    }
+
+    #[test]
+    fn jumbo() {
+        let bytes = [0x11, 0x00, 0xC2, 0x04, 0x00, 0x01, 0x00, 0x00];
+        let (header, _) = HopByHop::from_bytes(&bytes).unwrap();
+
+        let mut options = header.options();
+        assert_eq!(options.next(), Some(Ipv6Option::Jumbo(0x0001_0000)));
+        assert_eq!(options.next(), None);
+    }
+
+    #[test]
+    fn option_overruns_header() {
+        let bytes = [0x11, 0x00, 0x00, 0x01, 0x01, 0x00, 0x3e, 0x05];
+        let (header, _) = HopByHop::from_bytes(&bytes).unwrap();
+
+        let mut options = header.options();
+        assert_eq!(options.next(), Some(Ipv6Option::Pad1));
+        assert_eq!(options.next(), Some(Ipv6Option::PadN(1)));
+        assert_eq!(options.next(), None);
+        assert_eq!(options.next(), None);
+    }
+
+    #[test]
+    fn destination_options() {
+        let bytes = [
+            0x11, 0x00, 0x01, 0x04, 0x00, 0x00, 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF,
+        ];
+        let (header, payload) = DestinationOptions::from_bytes(&bytes).unwrap();
+        assert_eq!(header.next_header(), IpProtocol::UDP);
+        assert_eq!(header.header_len(), 8);
+        assert_eq!(payload, &[0xFF; 4]);
+        assert_eq!(header.to_string(), "IPv6 Destination Options (UDP) len=8");
+
+        let mut options = header.options();
+        assert_eq!(options.next(), Some(Ipv6Option::PadN(4)));
+        assert_eq!(options.next(), None);
+
+        let bytes = [0x11, 0x01, 0x01, 0x04, 0x00, 0x00, 0x00, 0x00];
+        assert_eq!(
+            DestinationOptions::from_bytes(&bytes).unwrap_err(),
+            HeaderTruncated
+        );
+    }
+
+    #[test]
+    fn ext_header_chain() {
+        let bytes = [
+            // Hop-by-Hop, next Routing
+            0x2b, 0x00, 0x01, 0x04, 0x00, 0x00, 0x00, 0x00,
+            // Routing type 2 with no addresses, next Destination Options
+            0x3c, 0x00, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00,
+            // Destination Options, next UDP
+            0x11, 0x00, 0x01, 0x04, 0x00, 0x00, 0x00, 0x00, // UDP
+            0xAA, 0xAA,
+        ];
+        let mut headers = Ipv6ExtHeaders::new(IpProtocol::HopByHop, &bytes);
+        assert!(matches!(
+            headers.next(),
+            Some(Ok(Ipv6ExtHeader::HopByHop(_)))
+        ));
+        assert!(matches!(
+            headers.next(),
+            Some(Ok(Ipv6ExtHeader::Routing(_)))
+        ));
+        let Some(Ok(header)) = headers.next() else {
+            panic!("expected destination options");
+        };
+        assert_eq!(header.to_string(), "IPv6 Destination Options (UDP) len=8");
+        assert_eq!(headers.next(), None);
+        assert_eq!(headers.next_header(), IpProtocol::UDP);
+        assert_eq!(headers.payload(), &[0xAA, 0xAA]);
+
+        // truncated Destination Options
+        let mut headers = Ipv6ExtHeaders::new(IpProtocol::HopByHop, &bytes[..20]);
+        assert!(headers.next().unwrap().is_ok());
+        assert!(headers.next().unwrap().is_ok());
+        assert_eq!(headers.next(), Some(Err(HeaderTruncated)));
+        assert_eq!(headers.next(), None);
+        assert_eq!(headers.next_header(), IpProtocol::IPv6Opts);
+    }
+
+    #[test]
+    fn routing_two_addresses() {
+        let bytes = [
+            // UDP, 2 addresses, type 0, 1 segment left
+            0x11, 0x04, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, // 2001:db8::1
+            0x20, 0x01, 0x0d, 0xb8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x01, // 2001:db8::2
+            0x20, 0x01, 0x0d, 0xb8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x02,
+        ];
+        let (header, payload) = Routing::from_bytes(&bytes).unwrap();
+        assert!(payload.is_empty());
+        assert_eq!(header.next_header(), IpProtocol::UDP);
+        assert_eq!(header.header_len(), 40);
+        assert_eq!(header.routing_type(), RoutingType::Type0);
+        assert_eq!(header.segments_left(), 1);
+
+        let mut addrs = header.addresses();
+        let mut addr = [
+            0x20, 0x01, 0x0d, 0xb8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x01,
+        ];
+        assert_eq!(addrs.next(), Some(Ipv6Addr::new(addr)));
+        addr[15] = 0x02;
+        assert_eq!(addrs.next(), Some(Ipv6Addr::new(addr)));
+        assert_eq!(addrs.next(), None);
+    }
+
+    #[test]
+    fn routing_partial_address() {
+        // type 0 with 8 bytes of space cannot hold a whole address
+        let bytes = [
+            0x11, 0x01, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00,
+        ];
+        assert_eq!(Routing::from_bytes(&bytes).unwrap_err(), HeaderTruncated);
+
+        // segment routing header with a last entry beyond the header
+        let mut bytes = [0; 24];
+        bytes[..8].copy_from_slice(&[0x11, 0x02, 0x04, 0x01, 0x01, 0x00, 0x00, 0x00]);
+        assert_eq!(Routing::from_bytes(&bytes).unwrap_err(), HeaderTruncated);
+
+        // but is fine when it only holds a single address
+        bytes[4] = 0x00;
+        let (header, _) = Routing::from_bytes(&bytes).unwrap();
+        assert_eq!(header.addresses().count(), 1);
+    }
+}
@@ -7,9 +7,19 @@
 //! [RFC 1122]: https://tools.ietf.org/html/rfc1122#section-3
 
 mod arp;
+mod icmpv4;
+mod icmpv6;
 mod ip;
 mod ipv4;
+mod ipv6;
+mod ipv6_ext;
+mod ndp;
 
 pub use arp::*;
+pub use icmpv4::*;
+pub use icmpv6::*;
 pub use ip::*;
 pub use ipv4::*;
+pub use ipv6::*;
+pub use ipv6_ext::*;
+pub use ndp::*;
@@ -0,0 +1,835 @@
+//! Neighbor Discovery Protocol (NDP) messages
+//!
+//! Typed views of the [`Icmpv6`] messages used by IPv6 nodes to discover routers, prefixes, and
+//! the link-layer addresses of neighbors, along with the [`NdpOption`]s they carry.
+use core::fmt;
+
+use crate::header::error::{HeaderInvalid, HeaderTruncated};
+use crate::header::link::EtherAddr;
+use crate::header::primitive::{U32, U8};
+use crate::header::utils::{as_header, split_at};
+
+use super::icmpv6::icmpv6_checksum;
+use super::{Icmpv6, Icmpv6Type, Ipv6Addr};
+
+impl Icmpv6 {
+    /// Writes a Neighbor Solicitation from `src` for the link-layer address of `target` into
+    /// `buf`, returning the length of the message or an error if `buf` is too small.
+    ///
+    /// The message is addressed to the solicited-node multicast address of `target` (see
+    /// [`Ipv6Addr::solicited_node`]), which the checksum covers, and must be sent with a hop limit
+    /// of 255. A Source Link-Layer Address option carrying `src_mac` is included unless `src` is
+    /// unspecified, as it is for Duplicate Address Detection.
+    pub fn write_neighbor_solicitation(
+        buf: &mut [u8],
+        src: Ipv6Addr,
+        target: Ipv6Addr,
+        src_mac: EtherAddr,
+    ) -> Result<usize, HeaderTruncated> {
+        let option =
+            (!src.is_unspecified()).then_some((NdpOption::SOURCE_LINK_LAYER_ADDR, src_mac));
+        write_neighbor_message(
+            buf,
+            Icmpv6Type::NeighborSolicitation,
+            0,
+            src,
+            target.solicited_node(),
+            target,
+            option,
+        )
+    }
+
+    /// Writes a Neighbor Advertisement from `src` to `dst` announcing that `target` is reachable
+    /// at `target_mac` into `buf`, returning the length of the message or an error if `buf` is too
+    /// small.
+    ///
+    /// A reply to a solicitation is `solicited` and sent to the source of the solicitation, or to
+    /// the all-nodes multicast address `ff02::1` if that source was unspecified. `router` marks the
+    /// sender as a router. The override flag is always set and a Target Link-Layer Address option
+    /// is always included. The message must be sent with a hop limit of 255.
+    pub fn write_neighbor_advertisement(
+        buf: &mut [u8],
+        src: Ipv6Addr,
+        dst: Ipv6Addr,
+        target: Ipv6Addr,
+        target_mac: EtherAddr,
+        router: bool,
+        solicited: bool,
+    ) -> Result<usize, HeaderTruncated> {
+        let mut flags = NeighborAdvertisement::FLAG_OVERRIDE;
+        if router {
+            flags |= NeighborAdvertisement::FLAG_ROUTER;
+        }
+        if solicited {
+            flags |= NeighborAdvertisement::FLAG_SOLICITED;
+        }
+        write_neighbor_message(
+            buf,
+            Icmpv6Type::NeighborAdvertisement,
+            flags,
+            src,
+            dst,
+            target,
+            Some((NdpOption::TARGET_LINK_LAYER_ADDR, target_mac)),
+        )
+    }
+
+    /// Writes a Router Solicitation from `src` into `buf`, returning the length of the message or
+    /// an error if `buf` is too small.
+    ///
+    /// The message is addressed to the all-routers multicast address [`Ipv6Addr::ALL_ROUTERS`],
+    /// which the checksum covers, and must be sent with a hop limit of 255. A Source Link-Layer
+    /// Address option carrying `src_mac` is included unless `src` is unspecified, as it is before
+    /// an address has been assigned.
+    pub fn write_router_solicitation(
+        buf: &mut [u8],
+        src: Ipv6Addr,
+        src_mac: EtherAddr,
+    ) -> Result<usize, HeaderTruncated> {
+        let len = if src.is_unspecified() { 8 } else { 16 };
+        let message = buf.get_mut(..len).ok_or(HeaderTruncated)?;
+
+        message[..8].copy_from_slice(&[Icmpv6Type::RouterSolicitation.get(), 0, 0, 0, 0, 0, 0, 0]);
+        if !src.is_unspecified() {
+            message[8..10].copy_from_slice(&[NdpOption::SOURCE_LINK_LAYER_ADDR, 1]);
+            message[10..16].copy_from_slice(src_mac.as_bytes());
+        }
+
+        let cks = !icmpv6_checksum(&src, &Ipv6Addr::ALL_ROUTERS, message);
+        message[2..4].copy_from_slice(&cks.to_ne_bytes());
+        Ok(len)
+    }
+}
+
+// Writes a Neighbor Solicitation or Advertisement with an optional link-layer address option.
+fn write_neighbor_message(
+    buf: &mut [u8],
+    ty: Icmpv6Type,
+    flags: u8,
+    src: Ipv6Addr,
+    dst: Ipv6Addr,
+    target: Ipv6Addr,
+    option: Option<(u8, EtherAddr)>,
+) -> Result<usize, HeaderTruncated> {
+    let len = if option.is_some() { 32 } else { 24 };
+    let message = buf.get_mut(..len).ok_or(HeaderTruncated)?;
+
+    message[..8].copy_from_slice(&[ty.get(), 0, 0, 0, flags, 0, 0, 0]);
+    message[8..24].copy_from_slice(target.as_bytes());
+    if let Some((kind, mac)) = option {
+        message[24..26].copy_from_slice(&[kind, 1]);
+        message[26..32].copy_from_slice(mac.as_bytes());
+    }
+
+    let cks = !icmpv6_checksum(&src, &dst, message);
+    message[2..4].copy_from_slice(&cks.to_ne_bytes());
+    Ok(len)
+}
+
+/// An ICMPv6 Router Solicitation message. [Read more][RFC 4861]
+///
+/// Hosts solicit routers to send a [`RouterAdvertisement`] right away, rather than waiting for the
+/// next periodic one, such as when an interface comes up. Built with
+/// [`Icmpv6::write_router_solicitation`].
+///
+/// [RFC 4861]: https://tools.ietf.org/html/rfc4861#section-4.1
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct RouterSolicitation<'a> {
+    header: &'a Icmpv6,
+    options: &'a [u8],
+}
+
+impl<'a> RouterSolicitation<'a> {
+    /// Returns a view of an ICMPv6 `header` and its `payload` as a Router Solicitation or
+    /// [`HeaderInvalid::Malformed`] if the message is not a Router Solicitation.
+    ///
+    /// The options span the payload.
+    #[inline]
+    pub const fn from_icmpv6(header: &'a Icmpv6, payload: &'a [u8]) -> Result<Self, HeaderInvalid> {
+        if !matches!(header.message_type(), Icmpv6Type::RouterSolicitation) || header.code() != 0 {
+            return Err(HeaderInvalid::Malformed);
+        }
+
+        Ok(RouterSolicitation {
+            header,
+            options: payload,
+        })
+    }
+
+    /// Returns the ICMPv6 header of the message.
+    #[inline]
+    pub const fn header(&self) -> &'a Icmpv6 {
+        self.header
+    }
+
+    /// Returns iterator of [`NdpOption`].
+    #[inline]
+    pub const fn options(&self) -> NdpOptions<'a> {
+        NdpOptions {
+            options: self.options,
+        }
+    }
+}
+
+impl<'a> fmt::Display for RouterSolicitation<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Router Solicitation")
+    }
+}
+
+/// An ICMPv6 Router Advertisement message. [Read more][RFC 4861]
+///
+/// Routers periodically advertise their presence on a link, along with the parameters hosts need
+/// to configure themselves, such as the hop limit and the on-link prefixes in
+/// [`NdpOption::PrefixInformation`] options. A prefix with the autonomous flag set is used by
+/// Stateless Address Autoconfiguration (SLAAC) to form an address.
+///
+/// [RFC 4861]: https://tools.ietf.org/html/rfc4861#section-4.2
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct RouterAdvertisement<'a> {
+    header: &'a Icmpv6,
+    timers: &'a RouterAdvertisementTimers,
+    options: &'a [u8],
+}
+
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[repr(C)]
+struct RouterAdvertisementTimers {
+    reachable_time: U32,
+    retrans_timer: U32,
+}
+
+impl<'a> RouterAdvertisement<'a> {
+    const FLAG_MANAGED: u8 = 0b1000_0000;
+    const FLAG_OTHER: u8 = 0b0100_0000;
+
+    /// Returns a view of an ICMPv6 `header` and its `payload` as a Router Advertisement or an
+    /// error: [`HeaderInvalid::Malformed`] if the message is not a Router Advertisement, or
+    /// [`HeaderInvalid::Truncated`] if the payload is too short.
+    ///
+    /// The options span the rest of the payload.
+    #[inline]
+    pub const fn from_icmpv6(header: &'a Icmpv6, payload: &'a [u8]) -> Result<Self, HeaderInvalid> {
+        if !matches!(header.message_type(), Icmpv6Type::RouterAdvertisement) || header.code() != 0 {
+            return Err(HeaderInvalid::Malformed);
+        }
+
+        let (timers, options) = match as_header!(RouterAdvertisementTimers, payload) {
+            Ok(v) => v,
+            Err(_) => return Err(HeaderInvalid::Truncated),
+        };
+
+        Ok(RouterAdvertisement {
+            header,
+            timers,
+            options,
+        })
+    }
+
+    /// Returns the hop limit hosts should use for outgoing packets, or `0` if unspecified.
+    #[inline]
+    pub const fn cur_hop_limit(&self) -> u8 {
+        self.header.rest()[0]
+    }
+
+    /// Returns `true` if addresses are available from DHCPv6.
+    #[inline]
+    pub const fn is_managed(&self) -> bool {
+        self.header.rest()[1] & Self::FLAG_MANAGED != 0
+    }
+
+    /// Returns `true` if other configuration, such as DNS servers, is available from DHCPv6.
+    #[inline]
+    pub const fn is_other_config(&self) -> bool {
+        self.header.rest()[1] & Self::FLAG_OTHER != 0
+    }
+
+    /// Returns the lifetime of the router as a default router in seconds. A lifetime of `0` means
+    /// the router is not a default router.
+    #[inline]
+    pub const fn router_lifetime(&self) -> u16 {
+        let rest = self.header.rest();
+        u16::from_be_bytes([rest[2], rest[3]])
+    }
+
+    /// Returns the time in milliseconds a neighbor is assumed reachable after a reachability
+    /// confirmation, or `0` if unspecified.
+    #[inline]
+    pub const fn reachable_time(&self) -> u32 {
+        self.timers.reachable_time.get()
+    }
+
+    /// Returns the time in milliseconds between retransmitted Neighbor Solicitations, or `0` if
+    /// unspecified.
+    #[inline]
+    pub const fn retrans_timer(&self) -> u32 {
+        self.timers.retrans_timer.get()
+    }
+
+    /// Returns iterator of [`NdpOption`].
+    #[inline]
+    pub const fn options(&self) -> NdpOptions<'a> {
+        NdpOptions {
+            options: self.options,
+        }
+    }
+}
+
+impl<'a> fmt::Display for RouterAdvertisement<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Router Advertisement hop_limit={} lifetime={}",
+            self.cur_hop_limit(),
+            self.router_lifetime()
+        )?;
+        if self.is_managed() {
+            write!(f, " managed")?;
+        }
+        if self.is_other_config() {
+            write!(f, " other")?;
+        }
+        Ok(())
+    }
+}
+
+/// An ICMPv6 Neighbor Solicitation message. [Read more][RFC 4861]
+///
+/// Nodes solicit the link-layer address of a neighbor, verify that a neighbor is still reachable,
+/// or detect a duplicate address before using it. Built with
+/// [`Icmpv6::write_neighbor_solicitation`].
+///
+/// [RFC 4861]: https://tools.ietf.org/html/rfc4861#section-4.3
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct NeighborSolicitation<'a> {
+    header: &'a Icmpv6,
+    target: &'a [u8; 16],
+    options: &'a [u8],
+}
+
+impl<'a> NeighborSolicitation<'a> {
+    /// Returns a view of an ICMPv6 `header` and its `payload` as a Neighbor Solicitation or an
+    /// error: [`HeaderInvalid::Malformed`] if the message is not a Neighbor Solicitation, or
+    /// [`HeaderInvalid::Truncated`] if the payload is too short.
+    ///
+    /// The options span the rest of the payload.
+    #[inline]
+    pub const fn from_icmpv6(header: &'a Icmpv6, payload: &'a [u8]) -> Result<Self, HeaderInvalid> {
+        if !matches!(header.message_type(), Icmpv6Type::NeighborSolicitation) || header.code() != 0
+        {
+            return Err(HeaderInvalid::Malformed);
+        }
+
+        let (target, options) = match as_header!([u8; 16], payload) {
+            Ok(v) => v,
+            Err(_) => return Err(HeaderInvalid::Truncated),
+        };
+
+        Ok(NeighborSolicitation {
+            header,
+            target,
+            options,
+        })
+    }
+
+    /// Returns the address whose link-layer address is solicited.
+    #[inline]
+    pub const fn target(&self) -> Ipv6Addr {
+        Ipv6Addr::new(*self.target)
+    }
+
+    /// Returns the ICMPv6 header of the message.
+    #[inline]
+    pub const fn header(&self) -> &'a Icmpv6 {
+        self.header
+    }
+
+    /// Returns iterator of [`NdpOption`].
+    #[inline]
+    pub const fn options(&self) -> NdpOptions<'a> {
+        NdpOptions {
+            options: self.options,
+        }
+    }
+}
+
+impl<'a> fmt::Display for NeighborSolicitation<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Neighbor Solicitation target={}", self.target())
+    }
+}
+
+/// An ICMPv6 Neighbor Advertisement message. [Read more][RFC 4861]
+///
+/// Nodes answer a Neighbor Solicitation for one of their addresses, or announce a change of
+/// link-layer address. Built with [`Icmpv6::write_neighbor_advertisement`].
+///
+/// [RFC 4861]: https://tools.ietf.org/html/rfc4861#section-4.4
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct NeighborAdvertisement<'a> {
+    header: &'a Icmpv6,
+    target: &'a [u8; 16],
+    options: &'a [u8],
+}
+
+impl<'a> NeighborAdvertisement<'a> {
+    const FLAG_ROUTER: u8 = 0b1000_0000;
+    const FLAG_SOLICITED: u8 = 0b0100_0000;
+    const FLAG_OVERRIDE: u8 = 0b0010_0000;
+
+    /// Returns a view of an ICMPv6 `header` and its `payload` as a Neighbor Advertisement or an
+    /// error: [`HeaderInvalid::Malformed`] if the message is not a Neighbor Advertisement, or
+    /// [`HeaderInvalid::Truncated`] if the payload is too short.
+    ///
+    /// The options span the rest of the payload.
+    #[inline]
+    pub const fn from_icmpv6(header: &'a Icmpv6, payload: &'a [u8]) -> Result<Self, HeaderInvalid> {
+        if !matches!(header.message_type(), Icmpv6Type::NeighborAdvertisement) || header.code() != 0
+        {
+            return Err(HeaderInvalid::Malformed);
+        }
+
+        let (target, options) = match as_header!([u8; 16], payload) {
+            Ok(v) => v,
+            Err(_) => return Err(HeaderInvalid::Truncated),
+        };
+
+        Ok(NeighborAdvertisement {
+            header,
+            target,
+            options,
+        })
+    }
+
+    /// Returns `true` if the sender is a router.
+    #[inline]
+    pub const fn is_router(&self) -> bool {
+        self.header.rest()[0] & Self::FLAG_ROUTER != 0
+    }
+
+    /// Returns `true` if the advertisement answers a Neighbor Solicitation.
+    #[inline]
+    pub const fn is_solicited(&self) -> bool {
+        self.header.rest()[0] & Self::FLAG_SOLICITED != 0
+    }
+
+    /// Returns `true` if the advertisement should replace a cached link-layer address.
+    #[inline]
+    pub const fn is_override(&self) -> bool {
+        self.header.rest()[0] & Self::FLAG_OVERRIDE != 0
+    }
+
+    /// Returns the address whose link-layer address is advertised.
+    #[inline]
+    pub const fn target(&self) -> Ipv6Addr {
+        Ipv6Addr::new(*self.target)
+    }
+
+    /// Returns the ICMPv6 header of the message.
+    #[inline]
+    pub const fn header(&self) -> &'a Icmpv6 {
+        self.header
+    }
+
+    /// Returns iterator of [`NdpOption`].
+    #[inline]
+    pub const fn options(&self) -> NdpOptions<'a> {
+        NdpOptions {
+            options: self.options,
+        }
+    }
+}
+
+impl<'a> fmt::Display for NeighborAdvertisement<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Neighbor Advertisement target={}", self.target())?;
+        if self.is_router() {
+            write!(f, " router")?;
+        }
+        if self.is_solicited() {
+            write!(f, " solicited")?;
+        }
+        if self.is_override() {
+            write!(f, " override")?;
+        }
+        Ok(())
+    }
+}
+
+/// Iterator of [`NdpOption`].
+///
+/// Iteration stops early if an option has a length of zero or claims more bytes than remain in the
+/// message.
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct NdpOptions<'a> {
+    options: &'a [u8],
+}
+
+impl<'a> Iterator for NdpOptions<'a> {
+    type Item = NdpOption<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Some((&[kind, len], rest)) = self.options.split_first_chunk::<2>() else {
+            self.options = &[];
+            return None;
+        };
+        // the length counts 8 byte units, including the type and length
+        let Some((data, rest)) = split_at(rest, (len as usize * 8).wrapping_sub(2)) else {
+            self.options = &[];
+            return None;
+        };
+        self.options = rest;
+
+        Some(match kind {
+            NdpOption::SOURCE_LINK_LAYER_ADDR => NdpOption::SourceLinkLayerAddr(data),
+            NdpOption::TARGET_LINK_LAYER_ADDR => NdpOption::TargetLinkLayerAddr(data),
+            NdpOption::PREFIX_INFORMATION => match as_header!(PrefixInformation, data) {
+                Ok((prefix, _)) => NdpOption::PrefixInformation(prefix),
+                Err(_) => NdpOption::Unknown { kind, data },
+            },
+            NdpOption::MTU => match *data {
+                [_, _, a, b, c, d] => NdpOption::Mtu(u32::from_be_bytes([a, b, c, d])),
+                _ => NdpOption::Unknown { kind, data },
+            },
+            kind => NdpOption::Unknown { kind, data },
+        })
+    }
+}
+
+/// An option carried by a Neighbor Discovery message. [Read more][RFC 4861]
+///
+/// Options are type-length-value (TLV) encoded in multiples of 8 bytes.
+///
+/// [RFC 4861]: https://tools.ietf.org/html/rfc4861#section-4.6
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[non_exhaustive]
+pub enum NdpOption<'a> {
+    /// The link-layer address of the sender, including any padding.
+    SourceLinkLayerAddr(&'a [u8]),
+    /// The link-layer address of the target, including any padding.
+    TargetLinkLayerAddr(&'a [u8]),
+    /// An on-link prefix and whether it may be used for autoconfiguration.
+    PrefixInformation(&'a PrefixInformation),
+    /// The MTU of the link, which hosts should use if it is smaller than their own.
+    Mtu(u32),
+    /// An unrecognized option, or a recognized option with an unexpected length.
+    Unknown { kind: u8, data: &'a [u8] },
+}
+
+impl<'a> NdpOption<'a> {
+    const SOURCE_LINK_LAYER_ADDR: u8 = 1;
+    const TARGET_LINK_LAYER_ADDR: u8 = 2;
+    const PREFIX_INFORMATION: u8 = 3;
+    const MTU: u8 = 5;
+}
+
+/// The data of a Prefix Information option. [Read more][RFC 4861]
+///
+/// [RFC 4861]: https://tools.ietf.org/html/rfc4861#section-4.6.2
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[repr(C)]
+pub struct PrefixInformation {
+    prefix_len: U8,
+    flags: U8,
+    valid_lifetime: U32,
+    preferred_lifetime: U32,
+    reserved: [u8; 4],
+    prefix: [u8; 16],
+}
+
+impl PrefixInformation {
+    /// A lifetime of infinity.
+    pub const INFINITE_LIFETIME: u32 = u32::MAX;
+
+    const FLAG_ON_LINK: u8 = 0b1000_0000;
+    const FLAG_AUTONOMOUS: u8 = 0b0100_0000;
+
+    /// Returns the number of leading bits of the prefix that are valid.
+    #[inline]
+    pub const fn prefix_len(&self) -> u8 {
+        self.prefix_len.get()
+    }
+
+    /// Returns `true` if addresses with the prefix are on-link.
+    #[inline]
+    pub const fn is_on_link(&self) -> bool {
+        self.flags.get() & Self::FLAG_ON_LINK != 0
+    }
+
+    /// Returns `true` if the prefix may be used for stateless address autoconfiguration.
+    #[inline]
+    pub const fn is_autonomous(&self) -> bool {
+        self.flags.get() & Self::FLAG_AUTONOMOUS != 0
+    }
+
+    /// Returns the time in seconds the prefix is valid.
+    #[inline]
+    pub const fn valid_lifetime(&self) -> u32 {
+        self.valid_lifetime.get()
+    }
+
+    /// Returns the time in seconds addresses formed from the prefix remain preferred.
+    #[inline]
+    pub const fn preferred_lifetime(&self) -> u32 {
+        self.preferred_lifetime.get()
+    }
+
+    /// Returns the prefix. Bits beyond the prefix length are zero.
+    #[inline]
+    pub const fn prefix(&self) -> Ipv6Addr {
+        Ipv6Addr::new(self.prefix)
+    }
+}
+
+impl fmt::Display for PrefixInformation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "prefix {}/{} valid={} preferred={}",
+            self.prefix(),
+            self.prefix_len(),
+            self.valid_lifetime(),
+            self.preferred_lifetime()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // RA with hop limit 64, other config, lifetime 1800s, and options for the source link-layer
+    // address and the autonomous prefix 2001:db8:1::/64
+    const RA: [u8; 56] = [
+        0x86, 0x00, 0x00, 0x00, 0x40, 0x40, 0x07, 0x08, 0x00, 0x00, 0x75, 0x30, 0x00, 0x00, 0x03,
+        0xe8, 0x01, 0x01, 0x02, 0x00, 0x00, 0x00, 0x00, 0x01, 0x03, 0x04, 0x40, 0xc0, 0x00, 0x27,
+        0x8d, 0x00, 0x00, 0x09, 0x3a, 0x80, 0x00, 0x00, 0x00, 0x00, 0x20, 0x01, 0x0d, 0xb8, 0x00,
+        0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ];
+
+    #[test]
+    fn router_advertisement() {
+        let (icmp, payload) = Icmpv6::from_bytes(&RA).unwrap();
+        let ra = RouterAdvertisement::from_icmpv6(icmp, payload).unwrap();
+        assert_eq!(ra.cur_hop_limit(), 64);
+        assert!(!ra.is_managed());
+        assert!(ra.is_other_config());
+        assert_eq!(ra.router_lifetime(), 1800);
+        assert_eq!(ra.reachable_time(), 30000);
+        assert_eq!(ra.retrans_timer(), 1000);
+
+        let mut options = ra.options();
+        assert_eq!(
+            options.next(),
+            Some(NdpOption::SourceLinkLayerAddr(&[
+                0x02, 0x00, 0x00, 0x00, 0x00, 0x01
+            ]))
+        );
+        let Some(NdpOption::PrefixInformation(prefix)) = options.next() else {
+            panic!("expected prefix information");
+        };
+        assert_eq!(options.next(), None);
+
+        assert_eq!(prefix.prefix_len(), 64);
+        assert!(prefix.is_on_link());
+        assert!(prefix.is_autonomous());
+        assert_eq!(prefix.valid_lifetime(), 2_592_000);
+        assert_eq!(prefix.preferred_lifetime(), 604_800);
+        assert_eq!(
+            prefix.to_string(),
+            "prefix 2001:db8:1::/64 valid=2592000 preferred=604800"
+        );
+    }
+
+    #[test]
+    fn mtu_option() {
+        let mut bytes = [0; 24];
+        bytes[..16].copy_from_slice(&RA[..16]);
+        bytes[16..].copy_from_slice(&[0x05, 0x01, 0x00, 0x00, 0x00, 0x00, 0x05, 0x00]);
+        let (icmp, payload) = Icmpv6::from_bytes(&bytes).unwrap();
+        let ra = RouterAdvertisement::from_icmpv6(icmp, payload).unwrap();
+        let mut options = ra.options();
+        assert_eq!(options.next(), Some(NdpOption::Mtu(1280)));
+        assert_eq!(options.next(), None);
+
+        // MTU option must be 8 bytes long
+        bytes[17] = 2;
+        let mut bytes = bytes.to_vec();
+        bytes.extend_from_slice(&[0; 8]);
+        let (icmp, payload) = Icmpv6::from_bytes(&bytes).unwrap();
+        let ra = RouterAdvertisement::from_icmpv6(icmp, payload).unwrap();
+        assert!(matches!(
+            ra.options().next(),
+            Some(NdpOption::Unknown { kind: 5, .. })
+        ));
+    }
+
+    #[test]
+    fn not_router_advertisement() {
+        let bytes = [
+            0x87, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        let (icmp, payload) = Icmpv6::from_bytes(&bytes).unwrap();
+        assert_eq!(
+            RouterAdvertisement::from_icmpv6(icmp, payload).unwrap_err(),
+            HeaderInvalid::Malformed
+        );
+
+        let (icmp, payload) = Icmpv6::from_bytes(&RA[..15]).unwrap();
+        assert_eq!(
+            RouterAdvertisement::from_icmpv6(icmp, payload).unwrap_err(),
+            HeaderInvalid::Truncated
+        );
+    }
+
+    #[test]
+    fn option_overruns_message() {
+        let (icmp, payload) = Icmpv6::from_bytes(&RA[..50]).unwrap();
+        let ra = RouterAdvertisement::from_icmpv6(icmp, payload).unwrap();
+        let mut options = ra.options();
+        assert!(matches!(
+            options.next(),
+            Some(NdpOption::SourceLinkLayerAddr(_))
+        ));
+        assert_eq!(options.next(), None);
+
+        // zero length option
+        let mut bytes = RA;
+        bytes[17] = 0;
+        let (icmp, payload) = Icmpv6::from_bytes(&bytes).unwrap();
+        let ra = RouterAdvertisement::from_icmpv6(icmp, payload).unwrap();
+        assert_eq!(ra.options().next(), None);
+    }
+
+    const SRC: Ipv6Addr = Ipv6Addr::new([
+        0xfe, 0x80, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xff, 0xfe, 0, 0, 0x01,
+    ]);
+    const TARGET: Ipv6Addr = Ipv6Addr::new([
+        0x20, 0x01, 0x0d, 0xb8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x12, 0x34, 0x56,
+    ]);
+    const MAC: EtherAddr = EtherAddr::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x01]);
+
+    #[test]
+    fn neighbor_solicitation() {
+        let mut buf = [0; 32];
+        let len = Icmpv6::write_neighbor_solicitation(&mut buf, SRC, TARGET, MAC).unwrap();
+        assert_eq!(len, 32);
+
+        let dst = TARGET.solicited_node();
+        assert_eq!(
+            dst,
+            Ipv6Addr::new([0xff, 0x02, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x01, 0xff, 0x12, 0x34, 0x56])
+        );
+        assert_eq!(icmpv6_checksum(&SRC, &dst, &buf[..len]), !0);
+
+        let (icmp, payload) = Icmpv6::from_bytes(&buf[..len]).unwrap();
+        assert_eq!(icmp.message_type(), Icmpv6Type::NeighborSolicitation);
+        assert_eq!(icmp.code(), 0);
+        let ns = NeighborSolicitation::from_icmpv6(icmp, payload).unwrap();
+        assert_eq!(ns.target(), TARGET);
+        let mut options = ns.options();
+        assert_eq!(
+            options.next(),
+            Some(NdpOption::SourceLinkLayerAddr(MAC.as_bytes()))
+        );
+        assert_eq!(options.next(), None);
+
+        assert_eq!(
+            Icmpv6::write_neighbor_solicitation(&mut buf[..31], SRC, TARGET, MAC),
+            Err(HeaderTruncated)
+        );
+    }
+
+    #[test]
+    fn duplicate_address_detection() {
+        let mut buf = [0; 32];
+        let len = Icmpv6::write_neighbor_solicitation(&mut buf, Ipv6Addr::UNSPECIFIED, TARGET, MAC)
+            .unwrap();
+        assert_eq!(len, 24);
+        let dst = TARGET.solicited_node();
+        assert_eq!(
+            icmpv6_checksum(&Ipv6Addr::UNSPECIFIED, &dst, &buf[..len]),
+            !0
+        );
+
+        let (icmp, payload) = Icmpv6::from_bytes(&buf[..len]).unwrap();
+        let ns = NeighborSolicitation::from_icmpv6(icmp, payload).unwrap();
+        assert_eq!(ns.options().next(), None);
+    }
+
+    #[test]
+    fn router_solicitation() {
+        let mut buf = [0; 16];
+        let len = Icmpv6::write_router_solicitation(&mut buf, SRC, MAC).unwrap();
+        assert_eq!(len, 16);
+        assert_eq!(
+            Ipv6Addr::ALL_ROUTERS,
+            "ff02::2".parse::<Ipv6Addr>().unwrap()
+        );
+        assert_eq!(
+            icmpv6_checksum(&SRC, &Ipv6Addr::ALL_ROUTERS, &buf[..len]),
+            !0
+        );
+
+        let (icmp, payload) = Icmpv6::from_bytes(&buf[..len]).unwrap();
+        assert_eq!(icmp.message_type(), Icmpv6Type::RouterSolicitation);
+        let rs = RouterSolicitation::from_icmpv6(icmp, payload).unwrap();
+        let mut options = rs.options();
+        assert_eq!(
+            options.next(),
+            Some(NdpOption::SourceLinkLayerAddr(MAC.as_bytes()))
+        );
+        assert_eq!(options.next(), None);
+        assert_eq!(
+            RouterAdvertisement::from_icmpv6(icmp, payload).unwrap_err(),
+            HeaderInvalid::Malformed
+        );
+
+        // no option before an address is assigned
+        let len = Icmpv6::write_router_solicitation(&mut buf, Ipv6Addr::UNSPECIFIED, MAC).unwrap();
+        assert_eq!(len, 8);
+        let (icmp, payload) = Icmpv6::from_bytes(&buf[..len]).unwrap();
+        let rs = RouterSolicitation::from_icmpv6(icmp, payload).unwrap();
+        assert_eq!(rs.options().next(), None);
+
+        assert_eq!(
+            Icmpv6::write_router_solicitation(&mut buf[..15], SRC, MAC),
+            Err(HeaderTruncated)
+        );
+    }
+
+    #[test]
+    fn neighbor_advertisement() {
+        let mut buf = [0; 32];
+        let len =
+            Icmpv6::write_neighbor_advertisement(&mut buf, TARGET, SRC, TARGET, MAC, false, true)
+                .unwrap();
+        assert_eq!(icmpv6_checksum(&TARGET, &SRC, &buf[..len]), !0);
+
+        let (icmp, payload) = Icmpv6::from_bytes(&buf[..len]).unwrap();
+        let na = NeighborAdvertisement::from_icmpv6(icmp, payload).unwrap();
+        assert!(!na.is_router());
+        assert!(na.is_solicited());
+        assert!(na.is_override());
+        assert_eq!(na.target(), TARGET);
+        assert_eq!(
+            na.options().next(),
+            Some(NdpOption::TargetLinkLayerAddr(MAC.as_bytes()))
+        );
+        assert_eq!(
+            na.to_string(),
+            "Neighbor Advertisement target=2001:db8::12:3456 solicited override"
+        );
+
+        assert_eq!(
+            NeighborSolicitation::from_icmpv6(icmp, payload).unwrap_err(),
+            HeaderInvalid::Malformed
+        );
+
+        let (icmp, payload) = Icmpv6::from_bytes(&buf[..20]).unwrap();
+        assert_eq!(
+            NeighborAdvertisement::from_icmpv6(icmp, payload).unwrap_err(),
+            HeaderInvalid::Truncated
+        );
+    }
+}
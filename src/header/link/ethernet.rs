@@ -4,8 +4,10 @@
 use core::fmt;
 
 use crate::header::error::HeaderTruncated;
+use crate::header::internet::{Ipv4Addr, Ipv6Addr};
 use crate::header::primitive::{non_exhaustive_enum, U16};
-use crate::header::utils::as_header;
+use crate::header::utils::{as_bytes, as_header, as_header_mut};
+use crate::header::Header;
 
 // check len
 // check destination
@@ -26,6 +28,16 @@ pub struct EthernetII {
 }
 
 impl EthernetII {
+    /// Constructs an EthernetII header. Usable in `const` contexts, such as frame templates.
+    #[inline]
+    pub const fn new(src: EtherAddr, dst: EtherAddr, ethertype: EtherType) -> Self {
+        EthernetII {
+            dst,
+            src,
+            ty: EtherTypeRepr(U16::new(ethertype.get())),
+        }
+    }
+
     /// Returns an immutable view of `bytes` as an EthernetII header followed by a payload or an
     /// error if the size or contents do not represent a valid EthernetII header.
     #[inline]
@@ -33,6 +45,19 @@ impl EthernetII {
         as_header!(EthernetII, bytes)
     }
 
+    /// Returns a mutable view of `bytes` as an EthernetII header followed by a payload or an error
+    /// if the size or contents do not represent a valid EthernetII header.
+    #[inline]
+    pub fn from_bytes_mut(bytes: &mut [u8]) -> Result<(&mut Self, &mut [u8]), HeaderTruncated> {
+        as_header_mut!(EthernetII, bytes)
+    }
+
+    /// Returns the EthernetII header as a sequence of network endian bytes.
+    #[inline]
+    pub const fn as_bytes(&self) -> &[u8; 14] {
+        as_bytes!(EthernetII, self)
+    }
+
     /// Returns the source Ethernet address.
     #[inline]
     pub const fn src(&self) -> EtherAddr {
@@ -50,6 +75,31 @@ impl EthernetII {
     pub const fn ethertype(&self) -> EtherType {
         self.ty.get()
     }
+
+    /// Sets the source Ethernet address.
+    #[inline]
+    pub fn set_src(&mut self, src: EtherAddr) {
+        self.src = src;
+    }
+
+    /// Sets the destination Ethernet address.
+    #[inline]
+    pub fn set_dst(&mut self, dst: EtherAddr) {
+        self.dst = dst;
+    }
+
+    /// Sets the EtherType of frame.
+    #[inline]
+    pub fn set_ethertype(&mut self, ethertype: EtherType) {
+        self.ty = EtherTypeRepr::from(ethertype);
+    }
+}
+
+impl<'a> Header<'a> for &'a EthernetII {
+    #[inline]
+    fn from_bytes(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), HeaderTruncated> {
+        EthernetII::from_bytes(bytes)
+    }
 }
 
 impl fmt::Display for EthernetII {
@@ -89,6 +139,35 @@ impl EtherAddr {
         &self.0
     }
 
+    /// Returns a locally administered unicast EtherAddr made from `bytes`, which should be random,
+    /// by clearing the multicast bit and setting the local bit of the first octet. Seeding the
+    /// source of `bytes` gives reproducible addresses, for example in tests.
+    #[inline]
+    pub const fn from_random(mut bytes: [u8; 6]) -> Self {
+        bytes[0] = bytes[0] & !0x01 | 0x02;
+        Self(bytes)
+    }
+
+    /// Returns the multicast EtherAddr of the IPv4 multicast group `addr`, `01:00:5e` followed by
+    /// the low 23 bits of `addr`. [Read more][RFC 1112]
+    ///
+    /// [RFC 1112]: https://tools.ietf.org/html/rfc1112#section-6.4
+    #[inline]
+    pub const fn from_ipv4_multicast(addr: Ipv4Addr) -> Self {
+        let bytes = addr.as_bytes();
+        Self([0x01, 0x00, 0x5e, bytes[1] & 0x7f, bytes[2], bytes[3]])
+    }
+
+    /// Returns the multicast EtherAddr of the IPv6 multicast group `addr`, `33:33` followed by the
+    /// low 32 bits of `addr`. [Read more][RFC 2464]
+    ///
+    /// [RFC 2464]: https://tools.ietf.org/html/rfc2464#section-7
+    #[inline]
+    pub const fn from_ipv6_multicast(addr: Ipv6Addr) -> Self {
+        let bytes = addr.as_bytes();
+        Self([0x33, 0x33, bytes[12], bytes[13], bytes[14], bytes[15]])
+    }
+
     /// Returns `true` if EtherAddr is a individual unicast address.
     #[inline]
     pub const fn is_unicast(&self) -> bool {
@@ -125,6 +204,13 @@ impl EtherAddr {
     }
 }
 
+impl From<EtherAddr> for [u8; 6] {
+    #[inline]
+    fn from(value: EtherAddr) -> Self {
+        value.0
+    }
+}
+
 impl fmt::Display for EtherAddr {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let bytes = self.0;
@@ -146,6 +232,8 @@ pub enum EtherType(u16) {
     Ipv4 = 0x0800,
     Arp = 0x0806,
     Ipv6 = 0x86DD,
+    TransparentEthernetBridging = 0x6558,
+    Mpls = 0x8847,
 }
 }
 
@@ -204,6 +292,66 @@ mod tests {
         assert_eq!(header.ethertype(), EtherType::Ipv6);
     }
 
+    #[test]
+    fn multicast_addr() {
+        let addr = EtherAddr::from_ipv4_multicast(Ipv4Addr::new([224, 0, 0, 251]));
+        assert_eq!(addr, EtherAddr::new([0x01, 0x00, 0x5e, 0x00, 0x00, 0xfb]));
+        assert!(addr.is_multicast());
+
+        let addr = EtherAddr::from_ipv4_multicast(Ipv4Addr::new([239, 255, 1, 2]));
+        assert_eq!(addr, EtherAddr::new([0x01, 0x00, 0x5e, 0x7f, 0x01, 0x02]));
+
+        let mut bytes = [0; 16];
+        bytes[0] = 0xff;
+        bytes[1] = 0x02;
+        bytes[15] = 0x01;
+        let addr = EtherAddr::from_ipv6_multicast(Ipv6Addr::new(bytes));
+        assert_eq!(addr, EtherAddr::new([0x33, 0x33, 0x00, 0x00, 0x00, 0x01]));
+    }
+
+    #[test]
+    fn random_addr() {
+        let addr = EtherAddr::from_random([0xFF; 6]);
+        assert_eq!(addr, EtherAddr::new([0xFE, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]));
+        assert!(addr.is_unicast() && addr.is_local());
+
+        let addr = EtherAddr::from_random([0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+        assert_eq!(addr, EtherAddr::new([0x02, 0x11, 0x22, 0x33, 0x44, 0x55]));
+    }
+
+    #[test]
+    fn const_header() {
+        const HEADER: EthernetII = EthernetII::new(
+            EtherAddr::new([0x02, 0, 0, 0, 0, 0x01]),
+            EtherAddr::BROADCAST,
+            EtherType::Ipv4,
+        );
+        const BYTES: &[u8; 14] = HEADER.as_bytes();
+
+        let (header, payload) = EthernetII::from_bytes(BYTES).unwrap();
+        assert!(payload.is_empty());
+        assert_eq!(header, &HEADER);
+        assert_eq!(header.src(), EtherAddr::new([0x02, 0, 0, 0, 0, 0x01]));
+        assert_eq!(header.dst(), EtherAddr::BROADCAST);
+        assert_eq!(header.ethertype(), EtherType::Ipv4);
+    }
+
+    #[test]
+    fn mutate_header() {
+        let mut bytes = [0; 18];
+        let (header, payload) = EthernetII::from_bytes_mut(&mut bytes).unwrap();
+        header.set_src(EtherAddr::new([0x02, 0, 0, 0, 0, 0x01]));
+        header.set_dst(EtherAddr::BROADCAST);
+        header.set_ethertype(EtherType::Arp);
+        payload.copy_from_slice(&[0xAA; 4]);
+
+        let (header, payload) = EthernetII::from_bytes(&bytes).unwrap();
+        assert_eq!(header.src(), EtherAddr::new([0x02, 0, 0, 0, 0, 0x01]));
+        assert_eq!(header.dst(), EtherAddr::BROADCAST);
+        assert_eq!(header.ethertype(), EtherType::Arp);
+        assert_eq!(payload, &[0xAA; 4]);
+    }
+
     #[test]
     fn ether_addr() {
         let mut addr = EtherAddr([0xFF; 6]);
@@ -231,4 +379,19 @@ mod tests {
         assert_eq!((false, true), (addr.is_local(), addr.is_universal()));
         assert_eq!((false, true), (addr.is_multicast(), addr.is_unicast()));
     }
+
+    proptest::proptest! {
+        #[test]
+        fn round_trip(src: [u8; 6], dst: [u8; 6], ethertype: u16) {
+            let header = EthernetII::new(EtherAddr::new(src), EtherAddr::new(dst), ethertype.into());
+            let bytes = header.as_bytes();
+
+            let (parsed, payload) = EthernetII::from_bytes(bytes).unwrap();
+            proptest::prop_assert_eq!(parsed, &header);
+            proptest::prop_assert_eq!(parsed.src(), EtherAddr::new(src));
+            proptest::prop_assert_eq!(parsed.dst(), EtherAddr::new(dst));
+            proptest::prop_assert_eq!(parsed.ethertype(), EtherType::from(ethertype));
+            proptest::prop_assert!(payload.is_empty());
+        }
+    }
 }
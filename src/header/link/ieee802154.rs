@@ -1,18 +1,199 @@
-use crate::header::primitive::{non_exhaustive_enum, U16};
+//! [`Ieee802154`] header
+//!
+//! [`Ieee802154`] MAC header of low-rate wireless personal area networks, addressed by PAN ID and
+//! short or extended address.
+use core::fmt;
 
+use crate::header::error::HeaderTruncated;
+use crate::header::primitive::non_exhaustive_enum;
+use crate::header::utils::{as_header, split_at};
+use crate::header::Header;
+
+/// An IEEE 802.15.4 MAC header. [Read more][IEEE 802.15.4]
+///
+/// Frames are addressed by an optional PAN ID and a short or extended address for each of the
+/// destination and source. Since these fields vary in length, they are split off from the payload
+/// along with the sequence number.
+///
+/// Only frames of the 2003 and 2006 versions of the standard without security are supported.
+///
+/// [IEEE 802.15.4]: https://standards.ieee.org/ieee/802.15.4/7029/
 #[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
 pub struct Ieee802154<'a> {
-    // Frame control  1/2
-    // Sequence number 0/1
+    frame_control: &'a FrameControl,
+    // Sequence number 1
 
     // Dest PAN ID 0/2
     // Dest Addr 0/2/8
 
     // Src PAN ID 0/2
     // Src Addr 0/2/8
+    fields: &'a [u8],
+}
+
+impl<'a> Ieee802154<'a> {
+    /// Returns an immutable view of `bytes` as an IEEE 802.15.4 MAC header followed by a payload
+    /// or an error if the size or contents do not represent a valid header. Frames with security
+    /// enabled, information elements, a suppressed sequence number, or a frame version after 2006
+    /// are not supported and return an error too.
+    #[inline]
+    pub const fn from_bytes(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), HeaderTruncated> {
+        let (frame_control, rest) = match as_header!(FrameControl, bytes) {
+            Ok(v) => v,
+            Err(e) => return Err(e),
+        };
+        let [dst_pan, dst, src_pan, src] = match frame_control.addressing_len() {
+            Some(v) => v,
+            None => return Err(HeaderTruncated),
+        };
+        let (fields, payload) = match split_at(rest, 1 + dst_pan + dst + src_pan + src) {
+            Some(v) => v,
+            None => return Err(HeaderTruncated),
+        };
+
+        Ok((
+            Ieee802154 {
+                frame_control,
+                fields,
+            },
+            payload,
+        ))
+    }
+
+    /// Returns the length of the header in bytes.
+    #[inline]
+    pub const fn header_len(&self) -> usize {
+        2 + self.fields.len()
+    }
+
+    /// Returns the type of the frame.
+    #[inline]
+    pub const fn frame_type(&self) -> FrameType {
+        self.frame_control.frame_type()
+    }
+
+    /// Returns `true` if the sender has more data for the recipient.
+    #[inline]
+    pub const fn frame_pending(&self) -> bool {
+        self.frame_control.frame_pending()
+    }
+
+    /// Returns `true` if the recipient should acknowledge the frame.
+    #[inline]
+    pub const fn ack_required(&self) -> bool {
+        self.frame_control.ack_required()
+    }
+
+    /// Returns `true` if the source PAN ID is omitted because it is the destination PAN ID.
+    #[inline]
+    pub const fn pan_id_compressed(&self) -> bool {
+        self.frame_control.pan_id_compressed()
+    }
+
+    /// Returns the version of the standard the frame follows.
+    #[inline]
+    pub const fn frame_version(&self) -> FrameVersion {
+        self.frame_control.frame_version()
+    }
+
+    /// Returns the addressing mode of the destination.
+    #[inline]
+    pub const fn dst_addressing_mode(&self) -> AddressingMode {
+        self.frame_control.dst_addressing_mode()
+    }
+
+    /// Returns the addressing mode of the source.
+    #[inline]
+    pub const fn src_addressing_mode(&self) -> AddressingMode {
+        self.frame_control.src_addressing_mode()
+    }
+
+    /// Returns the sequence number, which matches acknowledgments to frames.
+    #[inline]
+    pub const fn sequence_number(&self) -> u8 {
+        self.fields[0]
+    }
+
+    /// Returns the destination PAN ID, if present.
+    #[inline]
+    pub const fn dst_pan_id(&self) -> Option<u16> {
+        let [dst_pan, ..] = self.addressing_len();
+        if dst_pan == 0 {
+            return None;
+        }
+        Some(u16::from_le_bytes([self.fields[1], self.fields[2]]))
+    }
+
+    /// Returns the destination address as transmitted, least significant byte first. Empty if
+    /// omitted, otherwise 2 bytes for a short address or 8 bytes for an extended address.
+    #[inline]
+    pub fn dst_addr(&self) -> &'a [u8] {
+        let [dst_pan, dst, ..] = self.addressing_len();
+        &self.fields[1 + dst_pan..1 + dst_pan + dst]
+    }
+
+    /// Returns the source PAN ID, if present. With [`pan_id_compressed`][Self::pan_id_compressed]
+    /// set, this is the destination PAN ID.
+    #[inline]
+    pub const fn src_pan_id(&self) -> Option<u16> {
+        let [dst_pan, dst, src_pan, src] = self.addressing_len();
+        if src == 0 {
+            return None;
+        }
+        if src_pan == 0 {
+            return self.dst_pan_id();
+        }
+        let start = 1 + dst_pan + dst;
+        Some(u16::from_le_bytes([
+            self.fields[start],
+            self.fields[start + 1],
+        ]))
+    }
 
-    // Aux security header var.
-    bytes: &'a [u8],
+    /// Returns the source address as transmitted, least significant byte first. Empty if omitted,
+    /// otherwise 2 bytes for a short address or 8 bytes for an extended address.
+    #[inline]
+    pub fn src_addr(&self) -> &'a [u8] {
+        let [dst_pan, dst, src_pan, _] = self.addressing_len();
+        &self.fields[1 + dst_pan + dst + src_pan..]
+    }
+
+    // Returns the lengths of the fields following the sequence number, validated on construction.
+    #[inline]
+    const fn addressing_len(&self) -> [usize; 4] {
+        match self.frame_control.addressing_len() {
+            Some(v) => v,
+            None => [0; 4],
+        }
+    }
+}
+
+impl<'a> Header<'a> for Ieee802154<'a> {
+    #[inline]
+    fn from_bytes(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), HeaderTruncated> {
+        Ieee802154::from_bytes(bytes)
+    }
+}
+
+impl<'a> fmt::Display for Ieee802154<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "IEEE 802.15.4 ({}) seq={}",
+            self.frame_type(),
+            self.sequence_number()
+        )?;
+        if let Some(pan_id) = self.dst_pan_id() {
+            write!(f, " dst_pan={pan_id:#06x}")?;
+        }
+        if self.frame_pending() {
+            write!(f, " pending")?;
+        }
+        if self.ack_required() {
+            write!(f, " ack_required")?;
+        }
+        Ok(())
+    }
 }
 
 /// ```text
@@ -32,84 +213,129 @@ pub struct Ieee802154<'a> {
 ///    ┆   └ Security Enabled
 ///    └ Frame Type
 /// ```
+///
+/// Bits are transmitted in order starting from bit 0, the least significant bit of a little endian
+/// `u16`.
 #[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
-struct FrameControl(U16);
+#[repr(C)]
+struct FrameControl([u8; 2]);
 
 impl FrameControl {
-    const FRAME_TYPE_MASK: u16 = 0b1110_0000_0000_0000;
-    const FRAME_TYPE_SHIFT: usize = 13;
+    const FRAME_TYPE_MASK: u16 = 0b0000_0000_0000_0111;
+    const FRAME_TYPE_SHIFT: usize = 0;
 
-    const SECURITY_ENABLED_MASK: u16 = 0b0001_0000_0000_0000;
-    const SECURITY_ENABLED_SHIFT: usize = 12;
+    const SECURITY_ENABLED_MASK: u16 = 0b0000_0000_0000_1000;
+    const SECURITY_ENABLED_SHIFT: usize = 3;
 
-    const FRAME_PENDING_MASK: u16 = 0b0000_1000_0000_0000;
-    const FRAME_PENDING_SHIFT: usize = 11;
+    const FRAME_PENDING_MASK: u16 = 0b0000_0000_0001_0000;
+    const FRAME_PENDING_SHIFT: usize = 4;
 
-    const ACK_REQUIRED_MASK: u16 = 0b0000_0100_0000_0000;
-    const ACK_REQUIRED_SHIFT: usize = 10;
+    const ACK_REQUIRED_MASK: u16 = 0b0000_0000_0010_0000;
+    const ACK_REQUIRED_SHIFT: usize = 5;
 
-    const PAN_ID_COMPRESSED_MASK: u16 = 0b0000_0010_0000_0000;
-    const PAN_ID_COMPRESSED_SHIFT: usize = 9;
+    const PAN_ID_COMPRESSED_MASK: u16 = 0b0000_0000_0100_0000;
+    const PAN_ID_COMPRESSED_SHIFT: usize = 6;
 
-    const SEQ_NUM_SUPPRESSED_MASK: u16 = 0b0000_0000_1000_0000;
-    const SEQ_NUM_SUPPRESSED_SHIFT: usize = 7;
+    const SEQ_NUM_SUPPRESSED_MASK: u16 = 0b0000_0001_0000_0000;
+    const SEQ_NUM_SUPPRESSED_SHIFT: usize = 8;
 
-    const IE_PRESENT_MASK: u16 = 0b0000_0000_0100_0000;
-    const IE_PRESENT_SHIFT: usize = 6;
+    const IE_PRESENT_MASK: u16 = 0b0000_0010_0000_0000;
+    const IE_PRESENT_SHIFT: usize = 9;
 
-    const DEST_ADDRESSING_MODE_MASK: u16 = 0b0000_0000_0011_0000;
-    const DEST_ADDRESSING_MODE_SHIFT: usize = 4;
+    const DEST_ADDRESSING_MODE_MASK: u16 = 0b0000_1100_0000_0000;
+    const DEST_ADDRESSING_MODE_SHIFT: usize = 10;
 
-    const FRAME_VERSION_MASK: u16 = 0b0000_0000_0000_1100;
-    const FRAME_VERSION_SHIFT: usize = 2;
+    const FRAME_VERSION_MASK: u16 = 0b0011_0000_0000_0000;
+    const FRAME_VERSION_SHIFT: usize = 12;
 
-    const SRC_ADDRESSING_MASK: u16 = 0b0000_0000_0000_0011;
-    const SRC_ADDRESSING_SHIFT: usize = 0;
+    const SRC_ADDRESSING_MASK: u16 = 0b1100_0000_0000_0000;
+    const SRC_ADDRESSING_SHIFT: usize = 14;
+
+    #[inline]
+    const fn get(&self) -> u16 {
+        u16::from_le_bytes(self.0)
+    }
 
     pub(crate) const fn frame_type(&self) -> FrameType {
-        FrameType::new(((self.0.get() & Self::FRAME_TYPE_MASK) >> Self::FRAME_TYPE_SHIFT) as u8)
+        FrameType::new(((self.get() & Self::FRAME_TYPE_MASK) >> Self::FRAME_TYPE_SHIFT) as u8)
     }
 
     pub(crate) const fn security_enabled(&self) -> bool {
-        ((self.0.get() & Self::SECURITY_ENABLED_MASK) >> Self::SECURITY_ENABLED_SHIFT) != 0
+        ((self.get() & Self::SECURITY_ENABLED_MASK) >> Self::SECURITY_ENABLED_SHIFT) != 0
     }
 
     pub(crate) const fn frame_pending(&self) -> bool {
-        ((self.0.get() & Self::FRAME_PENDING_MASK) >> Self::FRAME_PENDING_SHIFT) != 0
+        ((self.get() & Self::FRAME_PENDING_MASK) >> Self::FRAME_PENDING_SHIFT) != 0
     }
 
     pub(crate) const fn ack_required(&self) -> bool {
-        ((self.0.get() & Self::ACK_REQUIRED_MASK) >> Self::ACK_REQUIRED_SHIFT) != 0
+        ((self.get() & Self::ACK_REQUIRED_MASK) >> Self::ACK_REQUIRED_SHIFT) != 0
     }
 
     pub(crate) const fn pan_id_compressed(&self) -> bool {
-        ((self.0.get() & Self::PAN_ID_COMPRESSED_MASK) >> Self::PAN_ID_COMPRESSED_SHIFT) != 0
+        ((self.get() & Self::PAN_ID_COMPRESSED_MASK) >> Self::PAN_ID_COMPRESSED_SHIFT) != 0
     }
 
     pub(crate) const fn seq_num_suppressed(&self) -> bool {
-        ((self.0.get() & Self::SEQ_NUM_SUPPRESSED_MASK) >> Self::SEQ_NUM_SUPPRESSED_SHIFT) != 0
+        ((self.get() & Self::SEQ_NUM_SUPPRESSED_MASK) >> Self::SEQ_NUM_SUPPRESSED_SHIFT) != 0
     }
 
     pub(crate) const fn ie_present(&self) -> bool {
-        ((self.0.get() & Self::IE_PRESENT_MASK) >> Self::IE_PRESENT_SHIFT) != 0
+        ((self.get() & Self::IE_PRESENT_MASK) >> Self::IE_PRESENT_SHIFT) != 0
     }
 
-    pub(crate) const fn dest_addressing_mode(&self) -> AddressingMode {
+    pub(crate) const fn dst_addressing_mode(&self) -> AddressingMode {
         AddressingMode::new(
-            ((self.0.get() & Self::DEST_ADDRESSING_MODE_MASK) >> Self::DEST_ADDRESSING_MODE_SHIFT)
+            ((self.get() & Self::DEST_ADDRESSING_MODE_MASK) >> Self::DEST_ADDRESSING_MODE_SHIFT)
                 as u8,
         )
     }
     pub(crate) const fn frame_version(&self) -> FrameVersion {
         FrameVersion::new(
-            ((self.0.get() & Self::FRAME_VERSION_MASK) >> Self::FRAME_VERSION_SHIFT) as u8,
+            ((self.get() & Self::FRAME_VERSION_MASK) >> Self::FRAME_VERSION_SHIFT) as u8,
         )
     }
     pub(crate) const fn src_addressing_mode(&self) -> AddressingMode {
         AddressingMode::new(
-            ((self.0.get() & Self::SRC_ADDRESSING_MASK) >> Self::SRC_ADDRESSING_SHIFT) as u8,
+            ((self.get() & Self::SRC_ADDRESSING_MASK) >> Self::SRC_ADDRESSING_SHIFT) as u8,
         )
     }
+
+    // Returns the lengths of the destination PAN ID, destination address, source PAN ID and
+    // source address, or `None` if the frame uses features that are not supported.
+    const fn addressing_len(&self) -> Option<[usize; 4]> {
+        if self.security_enabled() || self.seq_num_suppressed() || self.ie_present() {
+            return None;
+        }
+        match self.frame_version() {
+            FrameVersion::Ieee802154_2003 | FrameVersion::Ieee802154_2006 => {}
+            _ => return None,
+        }
+
+        let (dst, src) = match (
+            Self::addr_len(self.dst_addressing_mode()),
+            Self::addr_len(self.src_addressing_mode()),
+        ) {
+            (Some(dst), Some(src)) => (dst, src),
+            _ => return None,
+        };
+        let dst_pan = if dst != 0 { 2 } else { 0 };
+        let src_pan = if src != 0 && !self.pan_id_compressed() {
+            2
+        } else {
+            0
+        };
+        Some([dst_pan, dst, src_pan, src])
+    }
+
+    const fn addr_len(mode: AddressingMode) -> Option<usize> {
+        match mode {
+            AddressingMode::Omitted => Some(0),
+            AddressingMode::Short => Some(2),
+            AddressingMode::Extended => Some(8),
+            _ => None,
+        }
+    }
 }
 
 non_exhaustive_enum! {
@@ -139,3 +365,88 @@ pub enum FrameVersion(u8) {
     Ieee802154 = 0b10,
 }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_header() {
+        let bytes = [0x41, 0x88, 0x01, 0xcd, 0xab, 0xff, 0xff, 0x01];
+        assert_eq!(Ieee802154::from_bytes(&bytes).unwrap_err(), HeaderTruncated);
+        assert_eq!(
+            Ieee802154::from_bytes(&bytes[..1]).unwrap_err(),
+            HeaderTruncated
+        );
+    }
+
+    #[test]
+    fn short_addresses() {
+        let bytes = [0x41, 0x88, 0x01, 0xcd, 0xab, 0xff, 0xff, 0x01, 0x00, 0x7a];
+        let (header, payload) = Ieee802154::from_bytes(&bytes).unwrap();
+        assert_eq!(header.header_len(), 9);
+        assert_eq!(header.frame_type(), FrameType::Data);
+        assert!(!header.frame_pending());
+        assert!(!header.ack_required());
+        assert!(header.pan_id_compressed());
+        assert_eq!(header.frame_version(), FrameVersion::Ieee802154_2003);
+        assert_eq!(header.dst_addressing_mode(), AddressingMode::Short);
+        assert_eq!(header.src_addressing_mode(), AddressingMode::Short);
+        assert_eq!(header.sequence_number(), 1);
+        assert_eq!(header.dst_pan_id(), Some(0xabcd));
+        assert_eq!(header.dst_addr(), [0xff, 0xff]);
+        assert_eq!(header.src_pan_id(), Some(0xabcd));
+        assert_eq!(header.src_addr(), [0x01, 0x00]);
+        assert_eq!(payload, [0x7a]);
+        assert_eq!(
+            header.to_string(),
+            "IEEE 802.15.4 (Data) seq=1 dst_pan=0xabcd"
+        );
+    }
+
+    #[test]
+    fn extended_addresses() {
+        let bytes = [
+            0x21, 0xdc, 0x05, 0xcd, 0xab, 1, 2, 3, 4, 5, 6, 7, 8, 0x34, 0x12, 9, 10, 11, 12, 13,
+            14, 15, 16,
+        ];
+        let (header, payload) = Ieee802154::from_bytes(&bytes).unwrap();
+        assert_eq!(header.header_len(), bytes.len());
+        assert!(header.ack_required());
+        assert!(!header.pan_id_compressed());
+        assert_eq!(header.frame_version(), FrameVersion::Ieee802154_2006);
+        assert_eq!(header.dst_addressing_mode(), AddressingMode::Extended);
+        assert_eq!(header.src_addressing_mode(), AddressingMode::Extended);
+        assert_eq!(header.dst_pan_id(), Some(0xabcd));
+        assert_eq!(header.dst_addr(), [1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(header.src_pan_id(), Some(0x1234));
+        assert_eq!(header.src_addr(), [9, 10, 11, 12, 13, 14, 15, 16]);
+        assert!(payload.is_empty());
+    }
+
+    #[test]
+    fn ack() {
+        let bytes = [0x02, 0x00, 0x2a];
+        let (header, payload) = Ieee802154::from_bytes(&bytes).unwrap();
+        assert_eq!(header.frame_type(), FrameType::Ack);
+        assert_eq!(header.sequence_number(), 42);
+        assert_eq!(header.dst_pan_id(), None);
+        assert!(header.dst_addr().is_empty());
+        assert_eq!(header.src_pan_id(), None);
+        assert!(header.src_addr().is_empty());
+        assert!(payload.is_empty());
+    }
+
+    #[test]
+    fn unsupported() {
+        // security enabled
+        let bytes = [0x49, 0x88, 0x01, 0xcd, 0xab, 0xff, 0xff, 0x01, 0x00];
+        assert_eq!(Ieee802154::from_bytes(&bytes).unwrap_err(), HeaderTruncated);
+        // 2015 frame version
+        let bytes = [0x41, 0xa8, 0x01, 0xcd, 0xab, 0xff, 0xff, 0x01, 0x00];
+        assert_eq!(Ieee802154::from_bytes(&bytes).unwrap_err(), HeaderTruncated);
+        // reserved addressing mode
+        let bytes = [0x41, 0x84, 0x01, 0xcd, 0xab, 0xff, 0xff, 0x01, 0x00];
+        assert_eq!(Ieee802154::from_bytes(&bytes).unwrap_err(), HeaderTruncated);
+    }
+}
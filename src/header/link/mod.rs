@@ -7,5 +7,7 @@
 
 mod ethernet;
 mod ieee802154;
+mod mpls;
 pub use ethernet::*;
 pub use ieee802154::*;
+pub use mpls::*;
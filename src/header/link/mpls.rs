@@ -0,0 +1,190 @@
+//! [`MplsLabel`] stack
+//!
+//! [`MplsStack`] of [`MplsLabel`]s inserted between the link layer header and the payload.
+use core::fmt;
+use core::mem::size_of;
+
+use crate::header::error::HeaderTruncated;
+use crate::header::primitive::U32;
+use crate::header::utils::{as_header, split_at};
+use crate::header::Header;
+
+/// A Multiprotocol Label Switching (MPLS) label stack entry. [Read more][RFC 3032]
+///
+/// Routers along a label switched path forward packets by the label on top of the stack rather
+/// than by the network layer header. Frames with [`EtherType::Mpls`][super::EtherType::Mpls] carry
+/// one or more labels, the last of which has the bottom of stack bit set.
+///
+/// [RFC 3032]: https://tools.ietf.org/html/rfc3032#section-2.1
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[repr(C)]
+pub struct MplsLabel(U32);
+
+impl MplsLabel {
+    /// Returns an immutable view of `bytes` as a single MPLS label stack entry followed by a
+    /// payload or an error if the size or contents do not represent a valid MPLS label.
+    #[inline]
+    pub const fn from_bytes(bytes: &[u8]) -> Result<(&Self, &[u8]), HeaderTruncated> {
+        as_header!(MplsLabel, bytes)
+    }
+
+    /// Returns the label, a `u20` identifying the forwarding equivalence class of the packet.
+    #[inline]
+    pub const fn label(&self) -> u32 {
+        self.0.get() >> 12
+    }
+
+    /// Returns the traffic class, a `u3` used for QoS and ECN.
+    #[inline]
+    pub const fn traffic_class(&self) -> u8 {
+        ((self.0.get() >> 9) & 0b111) as u8
+    }
+
+    /// Returns `true` if this is the last entry of the label stack.
+    #[inline]
+    pub const fn is_bottom_of_stack(&self) -> bool {
+        (self.0.get() >> 8) & 1 == 1
+    }
+
+    /// Returns the time to live.
+    #[inline]
+    pub const fn ttl(&self) -> u8 {
+        self.0.get() as u8
+    }
+}
+
+impl<'a> Header<'a> for &'a MplsLabel {
+    #[inline]
+    fn from_bytes(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), HeaderTruncated> {
+        MplsLabel::from_bytes(bytes)
+    }
+}
+
+impl fmt::Display for MplsLabel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "MPLS label={} tc={} ttl={}",
+            self.label(),
+            self.traffic_class(),
+            self.ttl()
+        )?;
+        if self.is_bottom_of_stack() {
+            write!(f, " bos")?;
+        }
+        Ok(())
+    }
+}
+
+/// A complete MPLS label stack, up to and including the entry with the bottom of stack bit set.
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct MplsStack<'a> {
+    labels: &'a [u8],
+}
+
+impl<'a> MplsStack<'a> {
+    /// Returns an immutable view of `bytes` as an MPLS label stack followed by a payload or an
+    /// error if the bytes end before a label with the bottom of stack bit set.
+    #[inline]
+    pub const fn from_bytes(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), HeaderTruncated> {
+        let mut rest = bytes;
+        loop {
+            let (label, next) = match MplsLabel::from_bytes(rest) {
+                Ok(v) => v,
+                Err(e) => return Err(e),
+            };
+            rest = next;
+            if label.is_bottom_of_stack() {
+                break;
+            }
+        }
+
+        match split_at(bytes, bytes.len() - rest.len()) {
+            Some((labels, payload)) => Ok((MplsStack { labels }, payload)),
+            None => Err(HeaderTruncated),
+        }
+    }
+
+    /// Returns the number of labels in the stack.
+    #[inline]
+    #[allow(clippy::len_without_is_empty)]
+    pub const fn len(&self) -> usize {
+        self.labels.len() / size_of::<MplsLabel>()
+    }
+
+    /// Returns iterator of [`MplsLabel`], from the top to the bottom of the stack.
+    #[inline]
+    pub const fn labels(&self) -> MplsLabels<'a> {
+        MplsLabels {
+            labels: self.labels,
+        }
+    }
+}
+
+impl<'a> Header<'a> for MplsStack<'a> {
+    #[inline]
+    fn from_bytes(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), HeaderTruncated> {
+        MplsStack::from_bytes(bytes)
+    }
+}
+
+/// Iterator of [`MplsLabel`].
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct MplsLabels<'a> {
+    labels: &'a [u8],
+}
+
+impl<'a> Iterator for MplsLabels<'a> {
+    type Item = &'a MplsLabel;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (label, rest) = MplsLabel::from_bytes(self.labels).ok()?;
+        self.labels = rest;
+        Some(label)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::header::link::{EtherType, EthernetII};
+
+    #[test]
+    fn short_header() {
+        let bytes = [0; 3];
+        assert_eq!(MplsLabel::from_bytes(&bytes).unwrap_err(), HeaderTruncated);
+
+        // no bottom of stack
+        let bytes = [0x00, 0x01, 0x00, 0x40, 0x00, 0x02, 0x00, 0x40];
+        assert_eq!(MplsStack::from_bytes(&bytes).unwrap_err(), HeaderTruncated);
+    }
+
+    #[test]
+    fn two_label_stack() {
+        let bytes = [
+            0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x00, 0x01, 0x88, 0x47,
+            0x00, 0x01, 0x0a, 0x40, 0x00, 0x02, 0x07, 0x3f, 0x45, 0x00,
+        ];
+        let (frame, payload) = EthernetII::from_bytes(&bytes).unwrap();
+        assert_eq!(frame.ethertype(), EtherType::Mpls);
+
+        let (stack, payload) = MplsStack::from_bytes(payload).unwrap();
+        assert_eq!(stack.len(), 2);
+        assert_eq!(payload, &[0x45, 0x00]);
+
+        let mut labels = stack.labels();
+        let label = labels.next().unwrap();
+        assert_eq!(label.label(), 16);
+        assert_eq!(label.traffic_class(), 5);
+        assert!(!label.is_bottom_of_stack());
+        assert_eq!(label.ttl(), 64);
+        assert_eq!(label.to_string(), "MPLS label=16 tc=5 ttl=64");
+
+        let label = labels.next().unwrap();
+        assert_eq!(label.label(), 32);
+        assert_eq!(label.traffic_class(), 3);
+        assert!(label.is_bottom_of_stack());
+        assert_eq!(label.ttl(), 63);
+        assert_eq!(labels.next(), None);
+    }
+}
@@ -13,11 +13,108 @@
 //!  
 //! [RFC 1122]: https://tools.ietf.org/html/rfc1122
 
+pub mod application;
 pub mod internet;
 pub mod link;
 pub mod transport;
+pub mod tunnel;
+
+mod template;
+
+pub use error::HeaderInvalid;
+pub use template::PacketTemplate;
 
 pub(crate) mod checksum;
 pub(crate) mod error;
 pub(crate) mod primitive;
 pub(crate) mod utils;
+
+#[cfg(feature = "heapless")]
+use error::CapacityExceeded;
+use error::HeaderTruncated;
+
+/// A view of a slice of bytes as a header followed by a payload.
+///
+/// Every header type offers an inherent `from_bytes` function with the same signature. This trait
+/// ties them together for generic code. Header types cast directly from bytes implement the trait
+/// as a reference (e.g. `&EthernetII`), while header types with dynamic fields implement it
+/// directly (e.g. `Ipv4`).
+pub trait Header<'a>: Sized {
+    /// Returns an immutable view of `bytes` as a header followed by a payload or an error if the
+    /// size or contents do not represent a valid header.
+    fn from_bytes(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), HeaderTruncated>;
+}
+
+/// Collects an iterator of header options, such as [`Ipv6Options`][internet::Ipv6Options], into a
+/// fixed-capacity [`heapless::Vec`]. Returns an error if there are more than `N` options.
+#[cfg(feature = "heapless")]
+pub fn collect_options<I: IntoIterator, const N: usize>(
+    options: I,
+) -> Result<heapless::Vec<I::Item, N>, CapacityExceeded> {
+    let mut collected = heapless::Vec::new();
+    for option in options {
+        collected.push(option).map_err(|_| CapacityExceeded)?;
+    }
+    Ok(collected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::internet::{Arp, Ipv4, Ipv6};
+    use super::link::EthernetII;
+    use super::transport::{Tcp, Udp};
+    use super::*;
+
+    fn header_len<'a, H: Header<'a>>(bytes: &'a [u8]) -> Result<usize, HeaderTruncated> {
+        let (_, payload) = H::from_bytes(bytes)?;
+        Ok(bytes.len() - payload.len())
+    }
+
+    #[test]
+    fn generic_header() {
+        let mut bytes = [0; 64];
+        assert_eq!(header_len::<&EthernetII>(&bytes), Ok(14));
+        assert_eq!(header_len::<&Arp>(&bytes), Ok(28));
+        assert_eq!(header_len::<&Udp>(&bytes), Ok(8));
+        assert_eq!(header_len::<&Ipv6>(&bytes), Ok(40));
+        assert_eq!(header_len::<&Udp>(&bytes[..7]), Err(HeaderTruncated));
+
+        // IPv4 with 4 bytes of options
+        bytes[0] = 0x46;
+        assert_eq!(header_len::<Ipv4>(&bytes), Ok(24));
+        assert_eq!(header_len::<Ipv4>(&bytes[..23]), Err(HeaderTruncated));
+
+        // TCP with 4 bytes of options
+        bytes[12] = 0x60;
+        assert_eq!(header_len::<Tcp>(&bytes), Ok(24));
+        assert_eq!(header_len::<Tcp>(&bytes[..23]), Err(HeaderTruncated));
+    }
+
+    #[cfg(feature = "heapless")]
+    #[test]
+    fn collect_options_capacity() {
+        use super::internet::Ipv4Option;
+
+        // NOP, router alert, NOP, EOL, padding
+        let mut bytes = [0; 28];
+        bytes[0] = 0x47;
+        bytes[20..].copy_from_slice(&[0x01, 0x94, 0x04, 0x00, 0x00, 0x01, 0x00, 0x00]);
+        let (header, _) = Ipv4::from_bytes(&bytes).unwrap();
+
+        let options = collect_options::<_, 4>(header.options()).unwrap();
+        assert_eq!(
+            options,
+            [
+                Ipv4Option::Nop,
+                Ipv4Option::RouterAlert(0),
+                Ipv4Option::Nop,
+                Ipv4Option::Eol
+            ]
+        );
+
+        assert_eq!(
+            collect_options::<_, 3>(header.options()),
+            Err(CapacityExceeded)
+        );
+    }
+}
@@ -105,3 +105,4 @@ macro_rules! primitive {
 primitive!(U8, u8, 1);
 primitive!(U16, u16, 2);
 primitive!(U32, u32, 4);
+primitive!(U64, u64, 8);
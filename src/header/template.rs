@@ -0,0 +1,349 @@
+//! [`PacketTemplate`] for repeated sends
+//!
+//! [`PacketTemplate`] holding a pre-built IPv4 and UDP header, so packets of the same shape can be
+//! sent repeatedly while only rewriting the fields that change.
+
+use core::mem::size_of;
+
+use crate::header::checksum::{compute_checksum, compute_checksum_chained, update_checksum};
+use crate::header::error::HeaderTruncated;
+use crate::header::internet::{IpProtocol, Ipv4, Ipv4Addr, Ipv4Mut, Ipv4Required};
+use crate::header::transport::Udp;
+use crate::netdev::Capabilities;
+
+const IPV4_LEN: usize = size_of::<Ipv4Required>();
+const HEADERS_LEN: usize = IPV4_LEN + size_of::<Udp>();
+
+/// A pre-built IPv4 and UDP header followed by room for a payload, in a frame of `N` bytes.
+///
+/// Built once from headers such as [`Ipv4::new_const`] and [`Udp::new`], the template only
+/// rewrites the fields that change between sends. Setters update the IPv4 header checksum
+/// incrementally, along with a running sum of the UDP pseudo-header and header. The UDP checksum
+/// itself is completed over the payload only, by [`fix_checksums`][PacketTemplate::fix_checksums]
+/// or [`with_payload`][PacketTemplate::with_payload].
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct PacketTemplate<const N: usize> {
+    frame: [u8; N],
+    len: usize,
+    checksum_offload: bool,
+    // UDP checksum over the pseudo-header and the UDP header without its checksum field, as read
+    // from a header field
+    header_cks: u16,
+}
+
+impl<const N: usize> PacketTemplate<N> {
+    /// Constructs a template from an IPv4 header without options and a UDP header, without a
+    /// payload. Returns an error if `N` is too small to hold both headers.
+    pub fn new(ipv4: &Ipv4Required, udp: &Udp) -> Result<Self, HeaderTruncated> {
+        let mut frame = [0; N];
+        frame
+            .get_mut(..IPV4_LEN)
+            .ok_or(HeaderTruncated)?
+            .copy_from_slice(ipv4.as_bytes());
+        frame
+            .get_mut(IPV4_LEN..HEADERS_LEN)
+            .ok_or(HeaderTruncated)?
+            .copy_from_slice(udp.as_bytes());
+
+        let mut template = PacketTemplate {
+            frame,
+            len: HEADERS_LEN,
+            checksum_offload: false,
+            header_cks: 0,
+        };
+        let (mut ipv4, udp, _) = template.headers();
+        ipv4.set_total_len(HEADERS_LEN as u16);
+        ipv4.recompute_checksum();
+        udp.set_len(size_of::<Udp>() as u16);
+        udp.set_checksum(0);
+
+        let bytes = &template.frame;
+        let pseudo = [0, IpProtocol::UDP.get(), 0, size_of::<Udp>() as u8];
+        let sum = compute_checksum_chained(&[&bytes[12..IPV4_LEN], &pseudo, &bytes[IPV4_LEN..]]);
+        template.header_cks = !network_endian(sum);
+        template.fix_checksums();
+        Ok(template)
+    }
+
+    /// Returns the packet as it would currently be sent.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.frame[..self.len]
+    }
+
+    /// Sets the source IPv4 address.
+    #[inline]
+    pub fn set_src(&mut self, src: Ipv4Addr) {
+        let (mut ipv4, _, _) = self.headers();
+        let old = ipv4.as_ipv4().src();
+        ipv4.set_src(src);
+        self.update_addr(old, src);
+    }
+
+    /// Sets the destination IPv4 address.
+    #[inline]
+    pub fn set_dst(&mut self, dst: Ipv4Addr) {
+        let (mut ipv4, _, _) = self.headers();
+        let old = ipv4.as_ipv4().dst();
+        ipv4.set_dst(dst);
+        self.update_addr(old, dst);
+    }
+
+    /// Sets the UDP source port.
+    #[inline]
+    pub fn set_src_port(&mut self, port: u16) {
+        let (_, udp, _) = self.headers();
+        let old = udp.source_port();
+        udp.set_source_port(port);
+        self.header_cks = update_checksum(self.header_cks, &old.to_be_bytes(), &port.to_be_bytes());
+    }
+
+    /// Sets the UDP destination port.
+    #[inline]
+    pub fn set_dst_port(&mut self, port: u16) {
+        let (_, udp, _) = self.headers();
+        let old = udp.destination_port();
+        udp.set_destination_port(port);
+        self.header_cks = update_checksum(self.header_cks, &old.to_be_bytes(), &port.to_be_bytes());
+    }
+
+    /// Adapts the packet to the [`Capabilities`] of the sending device. If the device reports
+    /// [`Capabilities::TX_CHECKSUM_OFFLOAD`], checksums are left for the device to compute: the
+    /// IPv4 header checksum is left zero and the UDP checksum holds the partial sum of the
+    /// pseudo-header. The IPv4 header checksum is updated right away, the UDP checksum on the next
+    /// [`fix_checksums`][PacketTemplate::fix_checksums].
+    pub fn set_capabilities(&mut self, caps: Capabilities) {
+        let offload = caps.tx_checksum_offload();
+        self.checksum_offload = offload;
+        let (mut ipv4, _, _) = self.headers();
+        if offload {
+            ipv4.set_cks(0);
+        } else {
+            ipv4.recompute_checksum();
+        }
+    }
+
+    /// Completes the UDP checksum over the payload, or only writes the partial UDP checksum if
+    /// checksums are offloaded. The IPv4 header checksum is always kept up to date.
+    pub fn fix_checksums(&mut self) {
+        let (header_cks, offload) = (self.header_cks, self.checksum_offload);
+        let (_, udp, payload) = self.headers();
+
+        if offload {
+            // the device sums the UDP header itself, so leave it out of the partial sum
+            let ports_len = &udp.as_bytes()[..6];
+            udp.set_checksum(!update_checksum(header_cks, ports_len, &[0; 6]));
+            return;
+        }
+
+        let sum = add(!header_cks, network_endian(compute_checksum(payload)));
+        // a computed checksum of zero is sent as all ones, since zero means unused
+        udp.set_checksum(match !sum {
+            0 => !0,
+            cks => cks,
+        });
+    }
+
+    /// Replaces the payload with `payload`, updates the lengths and checksums, and returns the
+    /// packet ready to send. Returns an error if the payload does not fit in the frame, or if the
+    /// packet would be longer than an IPv4 total length can represent.
+    pub fn with_payload(&mut self, payload: &[u8]) -> Result<&[u8], HeaderTruncated> {
+        let len = HEADERS_LEN + payload.len();
+        let total_len = u16::try_from(len).map_err(|_| HeaderTruncated)?;
+        self.frame
+            .get_mut(HEADERS_LEN..len)
+            .ok_or(HeaderTruncated)?
+            .copy_from_slice(payload);
+        self.len = len;
+
+        let offload = self.checksum_offload;
+        let udp_len = total_len - IPV4_LEN as u16;
+        let (mut ipv4, udp, _) = self.headers();
+        let old_total_len = ipv4.as_ipv4().total_len();
+        ipv4.set_total_len(total_len);
+        if !offload {
+            let cks = ipv4.as_ipv4().cks();
+            let cks = update_checksum(cks, &old_total_len.to_be_bytes(), &total_len.to_be_bytes());
+            ipv4.set_cks(cks);
+        }
+        let old_udp_len = udp.len();
+        udp.set_len(udp_len);
+        // the UDP length is covered twice, by the pseudo-header and the header
+        let (old, new) = (old_udp_len.to_be_bytes(), udp_len.to_be_bytes());
+        for _ in 0..2 {
+            self.header_cks = update_checksum(self.header_cks, &old, &new);
+        }
+
+        self.fix_checksums();
+        Ok(self.as_bytes())
+    }
+
+    // Returns views of the headers and the payload, which the frame holds from construction on.
+    fn headers(&mut self) -> (Ipv4Mut<'_>, &mut Udp, &mut [u8]) {
+        let Ok((ipv4, rest)) = Ipv4::from_bytes_mut(&mut self.frame[..self.len]) else {
+            unreachable!("template holds an IPv4 header");
+        };
+        let Ok((udp, payload)) = Udp::from_bytes_mut(rest) else {
+            unreachable!("template holds a UDP header");
+        };
+        (ipv4, udp, payload)
+    }
+
+    // Updates the checksums for a change of address, which the pseudo-header covers too.
+    fn update_addr(&mut self, old: Ipv4Addr, new: Ipv4Addr) {
+        self.header_cks = update_checksum(self.header_cks, old.as_bytes(), new.as_bytes());
+        if !self.checksum_offload {
+            let (mut ipv4, _, _) = self.headers();
+            let cks = update_checksum(ipv4.as_ipv4().cks(), old.as_bytes(), new.as_bytes());
+            ipv4.set_cks(cks);
+        }
+    }
+}
+
+// Reads a sum of native endian words, as returned by `compute_checksum`, as a network endian value.
+fn network_endian(sum: u16) -> u16 {
+    u16::from_be_bytes(sum.to_ne_bytes())
+}
+
+// Adds two sums with ones' complement addition.
+fn add(a: u16, b: u16) -> u16 {
+    let sum = a as u32 + b as u32;
+    ((sum >> 16) + (sum & 0xffff)) as u16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::header::checksum::verify_checksum;
+    use crate::header::internet::Ipv4;
+
+    fn udp_checksum_valid(bytes: &[u8]) -> bool {
+        let pseudo = [0x00, 0x11, bytes[24], bytes[25]];
+        compute_checksum_chained(&[&bytes[12..20], &pseudo, &bytes[20..]]) == !0
+    }
+
+    #[test]
+    fn send_variants() {
+        const IPV4: Ipv4Required = Ipv4::new_const(
+            Ipv4Addr::new([192, 168, 0, 10]),
+            Ipv4Addr::new([192, 168, 0, 1]),
+            IpProtocol::UDP,
+            64,
+            0,
+        );
+        const UDP: Udp = Udp::new(5000, 9, 0, 0);
+        let mut template = PacketTemplate::<64>::new(&IPV4, &UDP).unwrap();
+        assert_eq!(template.as_bytes().len(), 28);
+
+        let bytes = template.with_payload(&[0xAA; 4]).unwrap().to_vec();
+        let (ipv4, payload) = Ipv4::from_bytes(&bytes).unwrap();
+        assert_eq!(ipv4.total_len(), 32);
+        assert_eq!(verify_checksum(&bytes[..20]), Ok(()));
+        assert!(udp_checksum_valid(&bytes));
+        let (udp, payload) = Udp::from_bytes(payload).unwrap();
+        assert_eq!(udp.destination_port(), 9);
+        assert_eq!(udp.len(), 12);
+        assert_eq!(payload, &[0xAA; 4]);
+
+        template.set_dst(Ipv4Addr::new([192, 168, 0, 2]));
+        template.set_src_port(5001);
+        template.set_dst_port(7);
+        let bytes = template.with_payload(&[0xBB; 8]).unwrap().to_vec();
+        let (ipv4, payload) = Ipv4::from_bytes(&bytes).unwrap();
+        assert_eq!(ipv4.dst(), Ipv4Addr::new([192, 168, 0, 2]));
+        assert_eq!(ipv4.total_len(), 36);
+        assert_eq!(verify_checksum(&bytes[..20]), Ok(()));
+        assert!(udp_checksum_valid(&bytes));
+        let (udp, payload) = Udp::from_bytes(payload).unwrap();
+        assert_eq!(udp.source_port(), 5001);
+        assert_eq!(udp.destination_port(), 7);
+        assert_eq!(payload, &[0xBB; 8]);
+
+        assert_eq!(
+            template.with_payload(&[0; 37]).unwrap_err(),
+            HeaderTruncated
+        );
+        assert_eq!(
+            PacketTemplate::<27>::new(&IPV4, &UDP).unwrap_err(),
+            HeaderTruncated
+        );
+    }
+
+    #[test]
+    fn checksum_offload() {
+        const IPV4: Ipv4Required = Ipv4::new_const(
+            Ipv4Addr::new([192, 168, 0, 10]),
+            Ipv4Addr::new([192, 168, 0, 1]),
+            IpProtocol::UDP,
+            64,
+            0,
+        );
+        let mut template = PacketTemplate::<64>::new(&IPV4, &Udp::new(5000, 9, 0, 0)).unwrap();
+        template.set_capabilities(Capabilities::TX_CHECKSUM_OFFLOAD);
+
+        let bytes = template.with_payload(&[0xAA; 4]).unwrap();
+        let (ipv4, payload) = Ipv4::from_bytes(bytes).unwrap();
+        assert_eq!(ipv4.cks(), 0);
+        let (udp, _) = Udp::from_bytes(payload).unwrap();
+        let pseudo = [0x00, 0x11, 0x00, 0x0c];
+        let partial = compute_checksum_chained(&[&bytes[12..20], &pseudo]);
+        assert_eq!(udp.checksum(), u16::from_be(partial));
+
+        // the device completes the checksum by summing the UDP header and payload
+        let mut bytes = bytes.to_vec();
+        let cks = !compute_checksum(&bytes[20..]);
+        bytes[26..28].copy_from_slice(&cks.to_ne_bytes());
+        assert!(udp_checksum_valid(&bytes));
+    }
+
+    #[test]
+    fn incremental_checksums() {
+        const IPV4: Ipv4Required = Ipv4::new_const(
+            Ipv4Addr::new([192, 168, 0, 10]),
+            Ipv4Addr::new([192, 168, 0, 1]),
+            IpProtocol::UDP,
+            64,
+            0,
+        );
+        let mut template = PacketTemplate::<64>::new(&IPV4, &Udp::new(5000, 9, 0, 0)).unwrap();
+        template.with_payload(&[0xAA; 5]).unwrap();
+
+        // the IPv4 header checksum is kept up to date by the setters alone
+        template.set_src(Ipv4Addr::new([10, 0, 0, 1]));
+        template.set_dst(Ipv4Addr::new([10, 0, 0, 2]));
+        assert_eq!(verify_checksum(&template.as_bytes()[..20]), Ok(()));
+
+        template.set_src_port(1);
+        template.fix_checksums();
+        assert!(udp_checksum_valid(template.as_bytes()));
+
+        // and restored when checksums are no longer offloaded
+        template.set_capabilities(Capabilities::TX_CHECKSUM_OFFLOAD);
+        assert_eq!(&template.as_bytes()[10..12], &[0, 0]);
+        template.set_dst(Ipv4Addr::new([10, 0, 0, 3]));
+        template.set_capabilities(Capabilities::new());
+        let bytes = template.with_payload(&[0xBB; 3]).unwrap();
+        assert_eq!(verify_checksum(&bytes[..20]), Ok(()));
+        assert!(udp_checksum_valid(bytes));
+    }
+
+    #[test]
+    fn payload_exceeds_total_len() {
+        const IPV4: Ipv4Required = Ipv4::new_const(
+            Ipv4Addr::new([192, 168, 0, 10]),
+            Ipv4Addr::new([192, 168, 0, 1]),
+            IpProtocol::UDP,
+            64,
+            0,
+        );
+        let mut template = PacketTemplate::<70_000>::new(&IPV4, &Udp::new(5000, 9, 0, 0)).unwrap();
+        let payload = [0xAA; 65_508];
+        assert_eq!(
+            template.with_payload(&payload).unwrap_err(),
+            HeaderTruncated
+        );
+        let bytes = template.with_payload(&payload[1..]).unwrap();
+        assert_eq!(Ipv4::from_bytes(bytes).unwrap().0.total_len(), u16::MAX);
+        assert!(udp_checksum_valid(bytes));
+    }
+}
@@ -8,5 +8,7 @@
 //!
 //! [RFC 1122]: https://tools.ietf.org/html/rfc1122#section-4
 
+mod tcp;
 mod udp;
+pub use tcp::*;
 pub use udp::*;
@@ -0,0 +1,982 @@
+//! [`Tcp`] header
+//!
+//! [`Tcp`] header supporting reliable, ordered transport of byte streams between ports.
+//! [`TcpSeqNumber`] compares and offsets the 32-bit sequence numbers of TCP, which wrap around.
+use core::cmp::Ordering;
+use core::fmt;
+use core::mem::size_of;
+use core::ops::{Add, AddAssign, Sub, SubAssign};
+
+use crate::header::checksum::{compute_checksum_chained, update_checksum};
+use crate::header::error::{ChecksumAssertion, HeaderTruncated};
+use crate::header::internet::IpProtocol;
+use crate::header::primitive::U16;
+use crate::header::utils::{as_bytes, as_header, as_header_mut, split_at, split_at_mut};
+use crate::header::Header;
+use crate::netdev::Capabilities;
+
+/// A TCP header. [Read more][RFC 9293]
+///
+/// TCP provides reliable, ordered and flow controlled transport of a stream of bytes between two
+/// ports. Each segment carries the sequence number of its first byte and acknowledges the bytes
+/// received from the peer so far.
+///
+/// Since TCP options are dynamic in length, they are not included in the header and are instead
+/// split off from the payload.
+///
+/// [RFC 9293]: https://tools.ietf.org/html/rfc9293#section-3.1
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct Tcp<'a> {
+    required: &'a TcpRequired,
+    options: &'a [u8],
+}
+
+/// The required portion of a TCP header, without options.
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[repr(C)]
+struct TcpRequired {
+    src_port: U16,
+    dst_port: U16,
+    seq: [u8; 4],
+    ack: [u8; 4],
+    offset_flags: OffsetFlags,
+    window: U16,
+    checksum: U16,
+    urgent: U16,
+}
+
+impl<'a> Tcp<'a> {
+    /// Returns an immutable view of `bytes` as a TCP header followed by a payload or an error if
+    /// the size or contents do not represent a valid TCP header. Options are split off from the
+    /// payload by the data offset.
+    #[inline]
+    pub const fn from_bytes(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), HeaderTruncated> {
+        let (required, options_payload) = match as_header!(TcpRequired, bytes) {
+            Ok(v) => v,
+            Err(e) => return Err(e),
+        };
+
+        // data offset counts the required portion of the header too
+        if required.offset_flags.header_len() < size_of::<TcpRequired>() {
+            return Err(HeaderTruncated);
+        }
+
+        let options_len = required.offset_flags.header_len() - size_of::<TcpRequired>();
+        let (options, payload) = match split_at(options_payload, options_len) {
+            Some(v) => v,
+            None => return Err(HeaderTruncated),
+        };
+
+        Ok((Tcp { required, options }, payload))
+    }
+
+    /// Returns a mutable view of `bytes` as a TCP header followed by a payload or an error if the
+    /// size or contents do not represent a valid TCP header. See [`TcpMut`].
+    #[inline]
+    pub fn from_bytes_mut(
+        bytes: &'a mut [u8],
+    ) -> Result<(TcpMut<'a>, &'a mut [u8]), HeaderTruncated> {
+        let (required, options_payload) = as_header_mut!(TcpRequired, bytes)?;
+        if required.offset_flags.header_len() < size_of::<TcpRequired>() {
+            return Err(HeaderTruncated);
+        }
+        let options_len = required.offset_flags.header_len() - size_of::<TcpRequired>();
+        let (options, payload) =
+            split_at_mut(options_payload, options_len).ok_or(HeaderTruncated)?;
+
+        Ok((TcpMut { required, options }, payload))
+    }
+
+    /// Returns the source port.
+    #[inline]
+    pub const fn source_port(&self) -> u16 {
+        self.required.src_port.get()
+    }
+
+    /// Returns the destination port.
+    #[inline]
+    pub const fn destination_port(&self) -> u16 {
+        self.required.dst_port.get()
+    }
+
+    /// Returns the sequence number of the first byte of the payload, or the initial sequence
+    /// number if SYN is set.
+    #[inline]
+    pub const fn sequence(&self) -> TcpSeqNumber {
+        TcpSeqNumber(u32::from_be_bytes(self.required.seq))
+    }
+
+    /// Returns the next sequence number the sender expects to receive. Only meaningful if ACK is
+    /// set.
+    #[inline]
+    pub const fn ack_number(&self) -> TcpSeqNumber {
+        TcpSeqNumber(u32::from_be_bytes(self.required.ack))
+    }
+
+    /// Returns the length of the TCP header in 32-bit words. At least 5, for a header without
+    /// options, and at most 15.
+    #[inline]
+    pub const fn data_offset(&self) -> u8 {
+        self.required.offset_flags.data_offset()
+    }
+
+    /// Returns the length of the TCP header in bytes, including options. Between 20 and 60 bytes.
+    #[inline]
+    pub const fn header_len(&self) -> usize {
+        self.required.offset_flags.header_len()
+    }
+
+    /// Returns the length of the TCP options in bytes. A header may have up to 40 bytes of options.
+    #[inline]
+    pub const fn options_len(&self) -> usize {
+        self.options.len()
+    }
+
+    /// Returns `true` if the FIN flag is set. The sender has no more data to send.
+    #[inline]
+    pub const fn fin(&self) -> bool {
+        self.required.offset_flags.flag(OffsetFlags::FIN)
+    }
+
+    /// Returns `true` if the SYN flag is set. The sender synchronizes sequence numbers to open a
+    /// connection.
+    #[inline]
+    pub const fn syn(&self) -> bool {
+        self.required.offset_flags.flag(OffsetFlags::SYN)
+    }
+
+    /// Returns `true` if the RST flag is set. The sender resets the connection.
+    #[inline]
+    pub const fn rst(&self) -> bool {
+        self.required.offset_flags.flag(OffsetFlags::RST)
+    }
+
+    /// Returns `true` if the PSH flag is set. The receiver should pass buffered data on promptly.
+    #[inline]
+    pub const fn psh(&self) -> bool {
+        self.required.offset_flags.flag(OffsetFlags::PSH)
+    }
+
+    /// Returns `true` if the ACK flag is set. The acknowledgment number is meaningful.
+    #[inline]
+    pub const fn ack(&self) -> bool {
+        self.required.offset_flags.flag(OffsetFlags::ACK)
+    }
+
+    /// Returns `true` if the URG flag is set. The urgent pointer is meaningful.
+    #[inline]
+    pub const fn urg(&self) -> bool {
+        self.required.offset_flags.flag(OffsetFlags::URG)
+    }
+
+    /// Returns `true` if the ECE flag is set. The sender is ECN capable, or echoes congestion
+    /// experienced by the peer. See [RFC 3168].
+    ///
+    /// [RFC 3168]: https://tools.ietf.org/html/rfc3168#section-6.1
+    #[inline]
+    pub const fn ece(&self) -> bool {
+        self.required.offset_flags.flag(OffsetFlags::ECE)
+    }
+
+    /// Returns `true` if the CWR flag is set. The sender reduced its congestion window in response
+    /// to ECE. See [RFC 3168].
+    ///
+    /// [RFC 3168]: https://tools.ietf.org/html/rfc3168#section-6.1
+    #[inline]
+    pub const fn cwr(&self) -> bool {
+        self.required.offset_flags.flag(OffsetFlags::CWR)
+    }
+
+    /// Returns the number of bytes the sender is willing to receive, before any window scaling.
+    #[inline]
+    pub const fn window(&self) -> u16 {
+        self.required.window.get()
+    }
+
+    /// Returns the checksum over the pseudo-header, header and payload.
+    #[inline]
+    pub const fn checksum(&self) -> u16 {
+        self.required.checksum.get()
+    }
+
+    /// Returns the offset from the sequence number to the end of urgent data. Only meaningful if
+    /// URG is set.
+    #[inline]
+    pub const fn urgent_pointer(&self) -> u16 {
+        self.required.urgent.get()
+    }
+
+    /// Returns `true` if the segment carries urgent data, the URG flag is set and the urgent
+    /// pointer is non-zero.
+    #[inline]
+    pub const fn urgent_data_present(&self) -> bool {
+        self.urg() && self.urgent_pointer() != 0
+    }
+
+    /// Returns iterator of TCP [`TcpOption`]
+    #[inline]
+    pub const fn options(&self) -> TcpOptions<'a> {
+        TcpOptions {
+            options: self.options,
+        }
+    }
+
+    /// Returns the options of the header as raw bytes, including any padding.
+    #[inline]
+    pub const fn options_bytes(&self) -> &'a [u8] {
+        self.options
+    }
+
+    /// Verifies the checksum of a received segment, unless `caps` reports the receiving device
+    /// already verified it or loops packets back. Returns an error if the checksum is invalid.
+    ///
+    /// `src` and `dst` are the network endian bytes of the IPv4 or IPv6 addresses of the enclosing
+    /// packet, which the checksum covers along with the header and payload.
+    #[inline]
+    pub fn verify(
+        &self,
+        src: &[u8],
+        dst: &[u8],
+        payload: &[u8],
+        caps: Capabilities,
+    ) -> Result<(), ChecksumAssertion> {
+        // a 32-bit length followed by the protocol sums the same as the 16-bit length of IPv4
+        let len = (self.header_len() + payload.len()) as u32;
+        let [a, b, c, d] = len.to_be_bytes();
+        let pseudo = [a, b, c, d, 0, 0, 0, IpProtocol::TCP.get()];
+        let header = as_bytes!(TcpRequired, self.required);
+        if caps.skips_rx_checksum()
+            || compute_checksum_chained(&[src, dst, &pseudo, header, self.options, payload]) == !0
+        {
+            Ok(())
+        } else {
+            Err(ChecksumAssertion)
+        }
+    }
+}
+
+/// A mutable view of a TCP header.
+///
+/// Only the fields rewritten by middleboxes are settable. Unless noted otherwise, setters leave
+/// the checksum stale.
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TcpMut<'a> {
+    required: &'a mut TcpRequired,
+    options: &'a mut [u8],
+}
+
+impl<'a> TcpMut<'a> {
+    /// Returns an immutable view of the TCP header for reading fields.
+    #[inline]
+    pub fn as_tcp(&self) -> Tcp<'_> {
+        Tcp {
+            required: self.required,
+            options: self.options,
+        }
+    }
+
+    /// Sets the checksum over the pseudo-header, header and payload.
+    #[inline]
+    pub fn set_checksum(&mut self, checksum: u16) {
+        self.required.checksum = U16::new(checksum);
+    }
+
+    /// Rewrites the value of the first [`TcpOption::Mss`] to `mss` and updates the checksum
+    /// incrementally. Returns `false`, leaving the header untouched, if there is no such option.
+    pub fn set_mss(&mut self, mss: u16) -> bool {
+        let mut options = TcpOptions {
+            options: self.options,
+        };
+        let pos = loop {
+            let pos = self.options.len() - options.options.len();
+            match options.next() {
+                Some(TcpOption::Mss(_)) => break pos + 2,
+                Some(_) => {}
+                None => return false,
+            }
+        };
+
+        // options start at an even offset and the checksum sums aligned words, so update over the
+        // words holding the value
+        let (start, end) = (pos & !1, (pos + 3) & !1);
+        let mut old = [0; 4];
+        old[..end - start].copy_from_slice(&self.options[start..end]);
+        self.options[pos..pos + 2].copy_from_slice(&mss.to_be_bytes());
+        let cks = update_checksum(
+            self.required.checksum.get(),
+            &old[..end - start],
+            &self.options[start..end],
+        );
+        self.set_checksum(cks);
+        true
+    }
+}
+
+impl<'a> Header<'a> for Tcp<'a> {
+    #[inline]
+    fn from_bytes(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), HeaderTruncated> {
+        Tcp::from_bytes(bytes)
+    }
+}
+
+impl<'a> fmt::Display for Tcp<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "TCP {} -> {} [",
+            self.source_port(),
+            self.destination_port()
+        )?;
+        for (set, flag) in [
+            (self.syn(), 'S'),
+            (self.ack(), 'A'),
+            (self.fin(), 'F'),
+            (self.rst(), 'R'),
+            (self.psh(), 'P'),
+            (self.urg(), 'U'),
+            (self.ece(), 'E'),
+            (self.cwr(), 'W'),
+        ] {
+            if set {
+                write!(f, "{flag}")?;
+            }
+        }
+        write!(f, "] seq={}", self.sequence())?;
+        if self.ack() {
+            write!(f, " ack={}", self.ack_number())?;
+        }
+        write!(f, " win={}", self.window())?;
+        if self.options_len() != 0 {
+            write!(f, " options={}", self.options_len())?;
+        }
+        Ok(())
+    }
+}
+
+/// Iterator of [`TcpOption`].
+///
+/// Iteration stops after [`TcpOption::Eol`], or early if an option claims more bytes than remain
+/// in the header.
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct TcpOptions<'a> {
+    options: &'a [u8],
+}
+
+impl<'a> Iterator for TcpOptions<'a> {
+    type Item = TcpOption<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (&kind, rest) = self.options.split_first()?;
+        match kind {
+            TcpOption::EOL => {
+                self.options = &[];
+                return Some(TcpOption::Eol);
+            }
+            TcpOption::NOP => {
+                self.options = rest;
+                return Some(TcpOption::Nop);
+            }
+            _ => {}
+        }
+
+        // length covers kind and length bytes
+        let Some((data, rest)) = rest
+            .split_first()
+            .and_then(|(&len, rest)| split_at(rest, (len as usize).checked_sub(2)?))
+        else {
+            self.options = &[];
+            return None;
+        };
+        self.options = rest;
+
+        Some(match (kind, data) {
+            (TcpOption::MSS, &[a, b]) => TcpOption::Mss(u16::from_be_bytes([a, b])),
+            (TcpOption::WINDOW_SCALE, &[shift]) => TcpOption::WindowScale(shift),
+            (TcpOption::SACK_PERMITTED, &[]) => TcpOption::SackPermitted,
+            (TcpOption::SACK, data) if !data.is_empty() && data.len() % 8 == 0 => {
+                TcpOption::Sack(TcpSackBlocks { blocks: data })
+            }
+            (TcpOption::TIMESTAMPS, &[a, b, c, d, e, f, g, h]) => TcpOption::Timestamps {
+                value: u32::from_be_bytes([a, b, c, d]),
+                echo: u32::from_be_bytes([e, f, g, h]),
+            },
+            (kind, data) => TcpOption::Unknown { kind, data },
+        })
+    }
+}
+
+/// An option carried by a TCP header. [Read more][RFC 9293]
+///
+/// Options are type-length-value (TLV) encoded, with the exception of [`TcpOption::Eol`] and
+/// [`TcpOption::Nop`]. Data carrying options borrow from the header.
+///
+/// [RFC 9293]: https://tools.ietf.org/html/rfc9293#section-3.2
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[non_exhaustive]
+pub enum TcpOption<'a> {
+    /// End of Option List. Remaining bytes of the header are padding.
+    Eol,
+    /// No Operation. A single byte used to align the following option.
+    Nop,
+    /// The largest segment the sender is willing to receive, in bytes. Only sent with SYN.
+    Mss(u16),
+    /// The number of bits to left shift the window by. Only sent with SYN. [Read more][RFC 7323]
+    ///
+    /// [RFC 7323]: https://tools.ietf.org/html/rfc7323#section-2
+    WindowScale(u8),
+    /// The sender can receive [`TcpOption::Sack`]. Only sent with SYN. [Read more][RFC 2018]
+    ///
+    /// [RFC 2018]: https://tools.ietf.org/html/rfc2018#section-2
+    SackPermitted,
+    /// Blocks of data received out of order. [Read more][RFC 2018]
+    ///
+    /// [RFC 2018]: https://tools.ietf.org/html/rfc2018#section-3
+    Sack(TcpSackBlocks<'a>),
+    /// A timestamp of the sender, and the most recent timestamp received from the peer.
+    /// [Read more][RFC 7323]
+    ///
+    /// [RFC 7323]: https://tools.ietf.org/html/rfc7323#section-3
+    Timestamps { value: u32, echo: u32 },
+    /// An unrecognized option, or a recognized option with an unexpected length.
+    Unknown { kind: u8, data: &'a [u8] },
+}
+
+impl<'a> TcpOption<'a> {
+    const EOL: u8 = 0;
+    const NOP: u8 = 1;
+    const MSS: u8 = 2;
+    const WINDOW_SCALE: u8 = 3;
+    const SACK_PERMITTED: u8 = 4;
+    const SACK: u8 = 5;
+    const TIMESTAMPS: u8 = 8;
+}
+
+/// Iterator of the blocks of a [`TcpOption::Sack`], each the sequence number of the first byte of
+/// the block and the sequence number following the last byte.
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub struct TcpSackBlocks<'a> {
+    blocks: &'a [u8],
+}
+
+impl<'a> Iterator for TcpSackBlocks<'a> {
+    type Item = (TcpSeqNumber, TcpSeqNumber);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (block, rest) = self.blocks.split_first_chunk::<8>()?;
+        self.blocks = rest;
+        let [a, b, c, d, e, f, g, h] = *block;
+        Some((
+            TcpSeqNumber(u32::from_be_bytes([a, b, c, d])),
+            TcpSeqNumber(u32::from_be_bytes([e, f, g, h])),
+        ))
+    }
+}
+
+/// ```text
+///  0 1 2 3 4 5 6 7 8 9 A B C D E F
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |Offset |  Rsvd |C|E|U|A|P|R|S|F|
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// ```
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+struct OffsetFlags(U16);
+
+impl OffsetFlags {
+    const OFFSET_MASK: u16 = 0b1111_0000_0000_0000;
+    const OFFSET_SHIFT: usize = 12;
+
+    const FIN: u16 = 0b0000_0001;
+    const SYN: u16 = 0b0000_0010;
+    const RST: u16 = 0b0000_0100;
+    const PSH: u16 = 0b0000_1000;
+    const ACK: u16 = 0b0001_0000;
+    const URG: u16 = 0b0010_0000;
+    const ECE: u16 = 0b0100_0000;
+    const CWR: u16 = 0b1000_0000;
+
+    /// Returns a `u4`
+    #[inline]
+    pub const fn data_offset(&self) -> u8 {
+        ((self.0.get() & Self::OFFSET_MASK) >> Self::OFFSET_SHIFT) as u8
+    }
+
+    /// Returns TCP header length in bytes
+    #[inline]
+    pub const fn header_len(&self) -> usize {
+        self.data_offset() as usize * 4
+    }
+
+    #[inline]
+    pub const fn flag(&self, flag: u16) -> bool {
+        (self.0.get() & flag) != 0
+    }
+}
+
+/// A TCP sequence number. [Read more][RFC 9293]
+///
+/// Sequence numbers wrap around at 2^32, so they are compared with serial number arithmetic
+/// ([RFC 1982]): `a < b` if `b` is less than 2^31 ahead of `a`, even across the wrap. A
+/// consequence is that the ordering is partial. Two numbers exactly 2^31 apart are neither less
+/// nor greater than each other, and the ordering is not transitive over more than 2^31.
+///
+/// Adding or subtracting a `usize` offsets the number by that many bytes, wrapping around.
+///
+/// [RFC 9293]: https://tools.ietf.org/html/rfc9293#section-3.4
+/// [RFC 1982]: https://tools.ietf.org/html/rfc1982#section-3.2
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy, Default)]
+pub struct TcpSeqNumber(u32);
+
+impl TcpSeqNumber {
+    /// Constructs a sequence number.
+    #[inline]
+    pub const fn new(value: u32) -> Self {
+        TcpSeqNumber(value)
+    }
+
+    /// Returns the sequence number as a `u32`.
+    #[inline]
+    pub const fn get(&self) -> u32 {
+        self.0
+    }
+
+    /// Returns the signed distance from `other` to this sequence number, positive if this
+    /// sequence number is greater.
+    #[inline]
+    pub const fn distance(&self, other: TcpSeqNumber) -> i32 {
+        self.0.wrapping_sub(other.0) as i32
+    }
+
+    // Offsets should be well within half of the sequence space, or comparisons become meaningless.
+    fn offset(rhs: usize) -> u32 {
+        match i32::try_from(rhs) {
+            Ok(rhs) => rhs as u32,
+            Err(_) => panic!("sequence number offset {rhs} exceeds i32::MAX"),
+        }
+    }
+}
+
+impl PartialOrd for TcpSeqNumber {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match self.distance(*other) {
+            0 => Some(Ordering::Equal),
+            i32::MIN => None,
+            distance if distance > 0 => Some(Ordering::Greater),
+            _ => Some(Ordering::Less),
+        }
+    }
+}
+
+impl Add<usize> for TcpSeqNumber {
+    type Output = TcpSeqNumber;
+
+    /// # Panics
+    ///
+    /// Panics if `rhs` exceeds `i32::MAX`.
+    #[inline]
+    fn add(self, rhs: usize) -> TcpSeqNumber {
+        TcpSeqNumber(self.0.wrapping_add(Self::offset(rhs)))
+    }
+}
+
+impl AddAssign<usize> for TcpSeqNumber {
+    #[inline]
+    fn add_assign(&mut self, rhs: usize) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub<usize> for TcpSeqNumber {
+    type Output = TcpSeqNumber;
+
+    /// # Panics
+    ///
+    /// Panics if `rhs` exceeds `i32::MAX`.
+    #[inline]
+    fn sub(self, rhs: usize) -> TcpSeqNumber {
+        TcpSeqNumber(self.0.wrapping_sub(Self::offset(rhs)))
+    }
+}
+
+impl SubAssign<usize> for TcpSeqNumber {
+    #[inline]
+    fn sub_assign(&mut self, rhs: usize) {
+        *self = *self - rhs;
+    }
+}
+
+impl From<u32> for TcpSeqNumber {
+    #[inline]
+    fn from(value: u32) -> Self {
+        TcpSeqNumber(value)
+    }
+}
+
+impl From<TcpSeqNumber> for u32 {
+    #[inline]
+    fn from(value: TcpSeqNumber) -> Self {
+        value.0
+    }
+}
+
+impl fmt::Display for TcpSeqNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // SYN, ACK 80 → 5000 with MSS 1460, NOP, NOP, SACK permitted options and 2 bytes of payload
+    const SYN_ACK: [u8; 30] = [
+        0x00, 0x50, 0x13, 0x88, 0x12, 0x34, 0x56, 0x78, 0x00, 0x00, 0x03, 0xe9, 0x70, 0x12, 0xfa,
+        0xf0, 0xAB, 0xCD, 0x00, 0x00, 0x02, 0x04, 0x05, 0xb4, 0x01, 0x01, 0x04, 0x02, 0xAA, 0xBB,
+    ];
+
+    #[test]
+    fn short_header() {
+        assert_eq!(
+            Tcp::from_bytes(&SYN_ACK[..19]).unwrap_err(),
+            HeaderTruncated
+        );
+        // options run past the end
+        assert_eq!(
+            Tcp::from_bytes(&SYN_ACK[..27]).unwrap_err(),
+            HeaderTruncated
+        );
+
+        // data offset less than 5
+        let mut bytes = SYN_ACK;
+        bytes[12] = 0x40;
+        assert_eq!(Tcp::from_bytes(&bytes).unwrap_err(), HeaderTruncated);
+    }
+
+    #[test]
+    fn fields() {
+        let (header, payload) = Tcp::from_bytes(&SYN_ACK).unwrap();
+        assert_eq!(header.source_port(), 80);
+        assert_eq!(header.destination_port(), 5000);
+        assert_eq!(header.sequence(), TcpSeqNumber::new(0x1234_5678));
+        assert_eq!(header.ack_number(), TcpSeqNumber::new(1001));
+        assert_eq!(header.data_offset(), 7);
+        assert_eq!(header.header_len(), 28);
+        assert_eq!(header.options_len(), 8);
+        assert_eq!(
+            header.options_bytes(),
+            &[0x02, 0x04, 0x05, 0xb4, 0x01, 0x01, 0x04, 0x02]
+        );
+        assert!(header.syn() && header.ack());
+        assert!(!header.fin() && !header.rst() && !header.psh() && !header.urg());
+        assert!(!header.ece() && !header.cwr());
+        assert_eq!(header.window(), 64240);
+        assert_eq!(header.checksum(), 0xABCD);
+        assert_eq!(header.urgent_pointer(), 0);
+        assert_eq!(payload, &[0xAA, 0xBB]);
+        assert_eq!(
+            header.to_string(),
+            "TCP 80 -> 5000 [SA] seq=305419896 ack=1001 win=64240 options=8"
+        );
+
+        let mut bytes = SYN_ACK;
+        bytes[13] = 0xC9;
+        let (header, _) = Tcp::from_bytes(&bytes).unwrap();
+        assert!(header.fin() && header.psh() && header.ece() && header.cwr());
+        assert!(!header.syn() && !header.ack() && !header.rst() && !header.urg());
+        assert_eq!(
+            header.to_string(),
+            "TCP 80 -> 5000 [FPEW] seq=305419896 win=64240 options=8"
+        );
+    }
+
+    #[test]
+    fn display_syn() {
+        // SYN 1234 → 80 without options
+        let bytes = [
+            0x04, 0xd2, 0x00, 0x50, 0x00, 0x00, 0x00, 0x64, 0x00, 0x00, 0x00, 0x00, 0x50, 0x02,
+            0xff, 0xff, 0x00, 0x00, 0x00, 0x00,
+        ];
+        let (header, _) = Tcp::from_bytes(&bytes).unwrap();
+        assert_eq!(header.to_string(), "TCP 1234 -> 80 [S] seq=100 win=65535");
+    }
+
+    #[test]
+    fn urgent_data() {
+        assert!(!Tcp::from_bytes(&SYN_ACK).unwrap().0.urgent_data_present());
+
+        // URG, ACK, PSH with the urgent data ending after the first payload byte
+        let mut bytes = SYN_ACK;
+        bytes[13] = 0x38;
+        bytes[18..20].copy_from_slice(&[0x00, 0x01]);
+        let (header, payload) = Tcp::from_bytes(&bytes).unwrap();
+        assert!(header.urg() && header.psh());
+        assert_eq!(header.urgent_pointer(), 1);
+        assert!(header.urgent_data_present());
+        assert_eq!(&payload[..header.urgent_pointer() as usize], &[0xAA]);
+
+        // URG without a pointer
+        bytes[18..20].copy_from_slice(&[0x00, 0x00]);
+        assert!(!Tcp::from_bytes(&bytes).unwrap().0.urgent_data_present());
+
+        // a pointer without URG
+        bytes[13] = 0x18;
+        bytes[18..20].copy_from_slice(&[0x00, 0x01]);
+        assert!(!Tcp::from_bytes(&bytes).unwrap().0.urgent_data_present());
+    }
+
+    #[test]
+    fn options() {
+        let (header, _) = Tcp::from_bytes(&SYN_ACK).unwrap();
+        let mut options = header.options();
+        assert_eq!(options.next(), Some(TcpOption::Mss(1460)));
+        assert_eq!(options.next(), Some(TcpOption::Nop));
+        assert_eq!(options.next(), Some(TcpOption::Nop));
+        assert_eq!(options.next(), Some(TcpOption::SackPermitted));
+        assert_eq!(options.next(), None);
+
+        // window scale, timestamps, SACK with 2 blocks, EOL followed by padding
+        let options = [
+            0x03, 0x03, 0x07, 0x08, 0x0a, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x02, 0x05,
+            0x12, 0x00, 0x00, 0x00, 0x0a, 0x00, 0x00, 0x00, 0x14, 0x00, 0x00, 0x00, 0x1e, 0x00,
+            0x00, 0x00, 0x28, 0x00, 0xAA,
+        ];
+        let mut options = TcpOptions { options: &options };
+        assert_eq!(options.next(), Some(TcpOption::WindowScale(7)));
+        assert_eq!(
+            options.next(),
+            Some(TcpOption::Timestamps { value: 1, echo: 2 })
+        );
+        let Some(TcpOption::Sack(blocks)) = options.next() else {
+            panic!("expected SACK option");
+        };
+        let blocks: Vec<_> = blocks.map(|(l, r)| (l.get(), r.get())).collect();
+        assert_eq!(blocks, [(10, 20), (30, 40)]);
+        assert_eq!(options.next(), Some(TcpOption::Eol));
+        assert_eq!(options.next(), None);
+
+        // MSS with the wrong length and an unknown option without data
+        let options = [0x02, 0x03, 0x05, 0xfe, 0x02];
+        let mut options = TcpOptions { options: &options };
+        assert_eq!(
+            options.next(),
+            Some(TcpOption::Unknown {
+                kind: 2,
+                data: &[0x05]
+            })
+        );
+        assert_eq!(
+            options.next(),
+            Some(TcpOption::Unknown {
+                kind: 0xfe,
+                data: &[]
+            })
+        );
+        assert_eq!(options.next(), None);
+    }
+
+    #[test]
+    fn verify() {
+        // 192.168.0.1 → 192.168.0.10
+        let src = [192, 168, 0, 1];
+        let dst = [192, 168, 0, 10];
+        let mut bytes = SYN_ACK;
+        bytes[16..18].copy_from_slice(&[0, 0]);
+        let pseudo = [0x00, 0x06, 0x00, 0x1e];
+        let cks = !compute_checksum_chained(&[&src, &dst, &pseudo, &bytes]);
+        bytes[16..18].copy_from_slice(&cks.to_ne_bytes());
+
+        let caps = Capabilities::new();
+        let (header, payload) = Tcp::from_bytes(&bytes).unwrap();
+        assert_eq!(header.verify(&src, &dst, payload, caps), Ok(()));
+
+        // corrupt the payload
+        bytes[29] ^= 0xff;
+        let (header, payload) = Tcp::from_bytes(&bytes).unwrap();
+        let result = header.verify(&src, &dst, payload, caps);
+        assert_eq!(result, Err(ChecksumAssertion));
+        let result = header.verify(&src, &dst, payload, Capabilities::LOOPBACK);
+        assert_eq!(result, Ok(()));
+        let result = header.verify(&src, &dst, payload, Capabilities::RX_CHECKSUM_OFFLOAD);
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn set_mss() {
+        let mut bytes = SYN_ACK;
+        let (mut header, _) = Tcp::from_bytes_mut(&mut bytes).unwrap();
+        assert!(header.set_mss(1400));
+        assert_eq!(header.as_tcp().options().next(), Some(TcpOption::Mss(1400)));
+        // 1460 → 1400 lowers the sum by 60
+        assert_eq!(header.as_tcp().checksum(), 0xABCD + 60);
+
+        let mut bytes = SYN_ACK;
+        bytes[20..24].copy_from_slice(&[0x01, 0x01, 0x01, 0x01]);
+        let (mut header, _) = Tcp::from_bytes_mut(&mut bytes).unwrap();
+        assert!(!header.set_mss(1400));
+        assert_eq!(header.as_tcp().checksum(), 0xABCD);
+    }
+
+    #[test]
+    fn option_overruns_header() {
+        // timestamps claims 10 bytes, overrunning the header
+        let options = [0x01, 0x08, 0x0a, 0x00, 0x00, 0x00, 0x01];
+        let mut options = TcpOptions { options: &options };
+        assert_eq!(options.next(), Some(TcpOption::Nop));
+        assert_eq!(options.next(), None);
+        assert_eq!(options.next(), None);
+
+        // length too small to cover kind and length, or missing entirely
+        for options in [&[0x02, 0x01, 0x05, 0xb4][..], &[0x02]] {
+            assert_eq!(TcpOptions { options }.next(), None);
+        }
+    }
+
+    #[test]
+    fn compare_across_wrap() {
+        let before = TcpSeqNumber::new(u32::MAX - 10);
+        let after = before + 20;
+        assert_eq!(after, TcpSeqNumber::new(9));
+        assert!(before < after);
+        assert!(after > before);
+        assert!(before <= before);
+        assert_eq!(after.distance(before), 20);
+        assert_eq!(before.distance(after), -20);
+        assert_eq!(after - 20, before);
+
+        let mut seq = TcpSeqNumber::new(u32::MAX);
+        seq += 1;
+        assert_eq!(seq, TcpSeqNumber::new(0));
+        seq -= 2;
+        assert_eq!(seq.get(), u32::MAX - 1);
+    }
+
+    #[test]
+    fn compare_half_space() {
+        let a = TcpSeqNumber::new(0);
+        assert!(a < TcpSeqNumber::new(0x7FFF_FFFF));
+        assert!(a > TcpSeqNumber::new(0x8000_0001));
+        // exactly half the sequence space apart is undefined
+        let b = TcpSeqNumber::new(0x8000_0000);
+        assert_eq!(a.partial_cmp(&b), None);
+        assert_eq!(b.partial_cmp(&a), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn offset_too_large() {
+        let _ = TcpSeqNumber::new(0) + (1 << 31);
+    }
+
+    fn tcp_option() -> impl proptest::strategy::Strategy<Value = TcpOption<'static>> {
+        use proptest::prelude::*;
+
+        prop_oneof![
+            Just(TcpOption::Nop),
+            any::<u16>().prop_map(TcpOption::Mss),
+            any::<u8>().prop_map(TcpOption::WindowScale),
+            Just(TcpOption::SackPermitted),
+            any::<(u32, u32)>().prop_map(|(value, echo)| TcpOption::Timestamps { value, echo }),
+        ]
+    }
+
+    fn emit_option(option: &TcpOption<'_>, bytes: &mut Vec<u8>) {
+        match *option {
+            TcpOption::Nop => bytes.push(TcpOption::NOP),
+            TcpOption::Mss(mss) => {
+                bytes.extend_from_slice(&[TcpOption::MSS, 4]);
+                bytes.extend_from_slice(&mss.to_be_bytes());
+            }
+            TcpOption::WindowScale(shift) => {
+                bytes.extend_from_slice(&[TcpOption::WINDOW_SCALE, 3, shift])
+            }
+            TcpOption::SackPermitted => bytes.extend_from_slice(&[TcpOption::SACK_PERMITTED, 2]),
+            TcpOption::Timestamps { value, echo } => {
+                bytes.extend_from_slice(&[TcpOption::TIMESTAMPS, 10]);
+                bytes.extend_from_slice(&value.to_be_bytes());
+                bytes.extend_from_slice(&echo.to_be_bytes());
+            }
+            _ => unreachable!("not generated"),
+        }
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn round_trip(
+            src_port: u16,
+            dst_port: u16,
+            seq: u32,
+            ack: u32,
+            flags: u8,
+            window: u16,
+            urgent: u16,
+            options in proptest::collection::vec(tcp_option(), 0..=4),
+            mss: u16,
+            payload: Vec<u8>,
+        ) {
+            let mut options_bytes = Vec::new();
+            for option in &options {
+                emit_option(option, &mut options_bytes);
+            }
+            let mut expected = options.clone();
+            if options_bytes.len() % 4 != 0 {
+                options_bytes.push(TcpOption::EOL);
+                options_bytes.resize(options_bytes.len().next_multiple_of(4), 0);
+                expected.push(TcpOption::Eol);
+            }
+            let data_offset = (20 + options_bytes.len()) as u8 / 4;
+
+            let mut bytes = Vec::new();
+            bytes.extend_from_slice(&src_port.to_be_bytes());
+            bytes.extend_from_slice(&dst_port.to_be_bytes());
+            bytes.extend_from_slice(&seq.to_be_bytes());
+            bytes.extend_from_slice(&ack.to_be_bytes());
+            bytes.extend_from_slice(&[data_offset << 4, flags]);
+            bytes.extend_from_slice(&window.to_be_bytes());
+            bytes.extend_from_slice(&[0, 0]);
+            bytes.extend_from_slice(&urgent.to_be_bytes());
+            bytes.extend_from_slice(&options_bytes);
+            bytes.extend_from_slice(&payload);
+
+            // 192.168.0.1 → 192.168.0.10
+            let (src, dst) = ([192, 168, 0, 1], [192, 168, 0, 10]);
+            let [len_hi, len_lo] = (bytes.len() as u16).to_be_bytes();
+            let pseudo = [0x00, 0x06, len_hi, len_lo];
+            let cks = !compute_checksum_chained(&[&src, &dst, &pseudo, &bytes]);
+            bytes[16..18].copy_from_slice(&cks.to_ne_bytes());
+
+            let (header, rest) = Tcp::from_bytes(&bytes).unwrap();
+            proptest::prop_assert_eq!(header.source_port(), src_port);
+            proptest::prop_assert_eq!(header.destination_port(), dst_port);
+            proptest::prop_assert_eq!(header.sequence(), TcpSeqNumber::new(seq));
+            proptest::prop_assert_eq!(header.ack_number(), TcpSeqNumber::new(ack));
+            proptest::prop_assert_eq!(header.data_offset(), data_offset);
+            proptest::prop_assert_eq!(header.fin(), flags & 0x01 != 0);
+            proptest::prop_assert_eq!(header.syn(), flags & 0x02 != 0);
+            proptest::prop_assert_eq!(header.rst(), flags & 0x04 != 0);
+            proptest::prop_assert_eq!(header.psh(), flags & 0x08 != 0);
+            proptest::prop_assert_eq!(header.ack(), flags & 0x10 != 0);
+            proptest::prop_assert_eq!(header.urg(), flags & 0x20 != 0);
+            proptest::prop_assert_eq!(header.ece(), flags & 0x40 != 0);
+            proptest::prop_assert_eq!(header.cwr(), flags & 0x80 != 0);
+            proptest::prop_assert_eq!(header.window(), window);
+            proptest::prop_assert_eq!(header.urgent_pointer(), urgent);
+            proptest::prop_assert_eq!(header.options_bytes(), &options_bytes[..]);
+            proptest::prop_assert_eq!(header.options().collect::<Vec<_>>(), expected);
+            proptest::prop_assert_eq!(rest, &payload[..]);
+            let caps = Capabilities::new();
+            proptest::prop_assert_eq!(header.verify(&src, &dst, rest, caps), Ok(()));
+
+            // rewriting the MSS keeps the checksum valid
+            let has_mss = options.iter().any(|option| matches!(option, TcpOption::Mss(_)));
+            let (mut header, _) = Tcp::from_bytes_mut(&mut bytes).unwrap();
+            proptest::prop_assert_eq!(header.set_mss(mss), has_mss);
+            let (header, rest) = Tcp::from_bytes(&bytes).unwrap();
+            proptest::prop_assert_eq!(header.verify(&src, &dst, rest, caps), Ok(()));
+            if has_mss {
+                let first = header.options().find(|option| matches!(option, TcpOption::Mss(_)));
+                proptest::prop_assert_eq!(first, Some(TcpOption::Mss(mss)));
+            }
+        }
+    }
+}
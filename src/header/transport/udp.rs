@@ -5,9 +5,13 @@
 
 use core::fmt;
 
-use crate::header::error::HeaderTruncated;
+use crate::header::checksum::compute_checksum_chained;
+use crate::header::error::{ChecksumAssertion, HeaderTruncated};
+use crate::header::internet::IpProtocol;
 use crate::header::primitive::U16;
-use crate::header::utils::as_header;
+use crate::header::utils::{as_bytes, as_header, as_header_mut};
+use crate::header::Header;
+use crate::netdev::Capabilities;
 
 /// A UDP header. [Read more][RFC 768]
 ///
@@ -27,11 +31,35 @@ pub struct Udp {
 }
 
 impl Udp {
+    /// Constructs a UDP header. Usable in `const` contexts, such as packet templates.
+    #[inline]
+    pub const fn new(src_port: u16, dst_port: u16, len: u16, checksum: u16) -> Self {
+        Udp {
+            src_port: U16::new(src_port),
+            dst_port: U16::new(dst_port),
+            len: U16::new(len),
+            checksum: U16::new(checksum),
+        }
+    }
+
     #[inline]
     pub const fn from_bytes(bytes: &[u8]) -> Result<(&Self, &[u8]), HeaderTruncated> {
         as_header!(Udp, bytes)
     }
 
+    /// Returns a mutable view of `bytes` as a UDP header followed by a payload or an error if the
+    /// size or contents do not represent a valid UDP header.
+    #[inline]
+    pub fn from_bytes_mut(bytes: &mut [u8]) -> Result<(&mut Self, &mut [u8]), HeaderTruncated> {
+        as_header_mut!(Udp, bytes)
+    }
+
+    /// Returns the UDP header as a sequence of network endian bytes.
+    #[inline]
+    pub const fn as_bytes(&self) -> &[u8; 8] {
+        as_bytes!(Udp, self)
+    }
+
     // Returns the source port.
     #[inline]
     pub const fn source_port(&self) -> u16 {
@@ -54,9 +82,99 @@ impl Udp {
 
     /// Returns the checksum of the UDP header. If unused, field will carry all zeros.
     #[inline]
-    pub(crate) const fn _checksum(&self) -> u16 {
+    pub const fn checksum(&self) -> u16 {
         self.checksum.get()
     }
+
+    /// Returns `true` if both headers have the same ports and length, disregarding the checksum.
+    /// Useful to compare a built header against a parsed one.
+    #[inline]
+    pub const fn eq_ignore_checksum(&self, other: &Udp) -> bool {
+        self.source_port() == other.source_port()
+            && self.destination_port() == other.destination_port()
+            && self.len() == other.len()
+    }
+
+    /// Sets the source port.
+    #[inline]
+    pub fn set_source_port(&mut self, port: u16) {
+        self.src_port = U16::new(port);
+    }
+
+    /// Sets the destination port.
+    #[inline]
+    pub fn set_destination_port(&mut self, port: u16) {
+        self.dst_port = U16::new(port);
+    }
+
+    /// Sets the length of the UDP header and payload in bytes.
+    #[inline]
+    pub fn set_len(&mut self, len: u16) {
+        self.len = U16::new(len);
+    }
+
+    /// Sets the checksum of the UDP header. Set to all zeros if unused.
+    #[inline]
+    pub fn set_checksum(&mut self, checksum: u16) {
+        self.checksum = U16::new(checksum);
+    }
+
+    /// Returns a [`Display`][fmt::Display] of the header that also reports whether the checksum is
+    /// unused, valid, or invalid for `payload`, as `cksum none`, `cksum ok`, or `cksum bad`.
+    ///
+    /// `src` and `dst` are the network endian bytes of the IPv4 or IPv6 addresses of the enclosing
+    /// packet, which the checksum covers along with the header and payload.
+    #[inline]
+    pub fn display_with<'a>(
+        &'a self,
+        src: &'a [u8],
+        dst: &'a [u8],
+        payload: &'a [u8],
+    ) -> UdpDisplay<'a> {
+        UdpDisplay {
+            header: self,
+            src,
+            dst,
+            payload,
+        }
+    }
+
+    /// Verifies the checksum of a received datagram, unless `caps` reports the receiving device
+    /// already verified it or loops packets back. Returns an error if the checksum is invalid.
+    ///
+    /// `src` and `dst` are the network endian bytes of the IPv4 or IPv6 addresses of the enclosing
+    /// packet. An unused checksum of all zeros is only accepted over IPv4.
+    #[inline]
+    pub fn verify(
+        &self,
+        src: &[u8],
+        dst: &[u8],
+        payload: &[u8],
+        caps: Capabilities,
+    ) -> Result<(), ChecksumAssertion> {
+        if caps.skips_rx_checksum()
+            || (self.checksum() == 0 && src.len() == 4)
+            || self.is_checksum_valid(src, dst, payload)
+        {
+            Ok(())
+        } else {
+            Err(ChecksumAssertion)
+        }
+    }
+
+    // Returns `true` if the checksum over the pseudo-header, header and payload is valid.
+    fn is_checksum_valid(&self, src: &[u8], dst: &[u8], payload: &[u8]) -> bool {
+        let [len_hi, len_lo] = self.len().to_be_bytes();
+        let pseudo = [0, IpProtocol::UDP.get(), len_hi, len_lo];
+        compute_checksum_chained(&[src, dst, &pseudo, self.as_bytes(), payload]) == !0
+    }
+}
+
+impl<'a> Header<'a> for &'a Udp {
+    #[inline]
+    fn from_bytes(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), HeaderTruncated> {
+        Udp::from_bytes(bytes)
+    }
 }
 
 impl fmt::Display for Udp {
@@ -71,6 +189,32 @@ impl fmt::Display for Udp {
     }
 }
 
+/// [`Display`][fmt::Display] of a [`Udp`] header annotated with the state of its checksum.
+/// Created by [`Udp::display_with`].
+#[derive(Debug, Clone)]
+pub struct UdpDisplay<'a> {
+    header: &'a Udp,
+    src: &'a [u8],
+    dst: &'a [u8],
+    payload: &'a [u8],
+}
+
+impl<'a> fmt::Display for UdpDisplay<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cksum = if self.header.checksum() == 0 {
+            "none"
+        } else if self
+            .header
+            .is_checksum_valid(self.src, self.dst, self.payload)
+        {
+            "ok"
+        } else {
+            "bad"
+        };
+        write!(f, "{}, cksum {cksum}", self.header)
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -81,4 +225,131 @@ mod tests {
         let bytes = [0; 7];
         assert_eq!(Udp::from_bytes(&bytes).unwrap_err(), HeaderTruncated);
     }
+
+    #[test]
+    fn const_header() {
+        const HEADER: Udp = Udp::new(12345, 53, 8, 0);
+        const BYTES: &[u8; 8] = HEADER.as_bytes();
+
+        let (header, _) = Udp::from_bytes(BYTES).unwrap();
+        assert_eq!(header, &HEADER);
+        assert_eq!(header.source_port(), 12345);
+        assert_eq!(header.destination_port(), 53);
+        assert_eq!(header.len(), 8);
+        assert_eq!(header.checksum(), 0);
+    }
+
+    #[test]
+    fn mutate_header() {
+        let mut bytes = [0; 12];
+        let (header, payload) = Udp::from_bytes_mut(&mut bytes).unwrap();
+        header.set_source_port(12345);
+        header.set_destination_port(53);
+        header.set_len(12);
+        header.set_checksum(0xBEEF);
+        payload.copy_from_slice(&[0xAA; 4]);
+
+        let (header, payload) = Udp::from_bytes(&bytes).unwrap();
+        assert_eq!(header.source_port(), 12345);
+        assert_eq!(header.destination_port(), 53);
+        assert_eq!(header.len(), 12);
+        assert_eq!(header.checksum(), 0xBEEF);
+        assert_eq!(payload, &[0xAA; 4]);
+    }
+
+    #[test]
+    fn eq_ignore_checksum() {
+        let built = Udp::new(12345, 53, 12, 0);
+        let bytes = [
+            0x30, 0x39, 0x00, 0x35, 0x00, 0x0c, 0xbe, 0xef, 0xAA, 0xAA, 0xAA, 0xAA,
+        ];
+
+        let (parsed, _) = Udp::from_bytes(&bytes).unwrap();
+        assert_ne!(parsed, &built);
+        assert!(parsed.eq_ignore_checksum(&built));
+        assert!(!parsed.eq_ignore_checksum(&Udp::new(12345, 53, 8, 0)));
+    }
+
+    #[test]
+    fn display_checksum() {
+        // 192.168.0.10:5000 → 8.8.8.8:53
+        let src = [192, 168, 0, 10];
+        let dst = [8, 8, 8, 8];
+        let mut bytes = [
+            0x13, 0x88, 0x00, 0x35, 0x00, 0x0c, 0x00, 0x00, 0xDE, 0xAD, 0xBE, 0xEF,
+        ];
+
+        let (header, payload) = Udp::from_bytes(&bytes).unwrap();
+        assert_eq!(
+            header.display_with(&src, &dst, payload).to_string(),
+            "UDP src_port: 5000, dst_port: 53, len: 12, cksum none"
+        );
+
+        let pseudo = [0x00, 0x11, 0x00, 0x0c];
+        let cks = !compute_checksum_chained(&[&src, &dst, &pseudo, &bytes]);
+        bytes[6..8].copy_from_slice(&cks.to_ne_bytes());
+        let (header, payload) = Udp::from_bytes(&bytes).unwrap();
+        assert_eq!(
+            header.display_with(&src, &dst, payload).to_string(),
+            "UDP src_port: 5000, dst_port: 53, len: 12, cksum ok"
+        );
+
+        bytes[11] ^= 0xff;
+        let (header, payload) = Udp::from_bytes(&bytes).unwrap();
+        assert_eq!(
+            header.display_with(&src, &dst, payload).to_string(),
+            "UDP src_port: 5000, dst_port: 53, len: 12, cksum bad"
+        );
+    }
+
+    #[test]
+    fn verify() {
+        // 192.168.0.10:5000 → 8.8.8.8:53
+        let src = [192, 168, 0, 10];
+        let dst = [8, 8, 8, 8];
+        let mut bytes = [
+            0x13, 0x88, 0x00, 0x35, 0x00, 0x0c, 0x00, 0x00, 0xDE, 0xAD, 0xBE, 0xEF,
+        ];
+
+        // unused checksum is only allowed over IPv4
+        let (header, payload) = Udp::from_bytes(&bytes).unwrap();
+        let caps = Capabilities::new();
+        assert_eq!(header.verify(&src, &dst, payload, caps), Ok(()));
+        let src6 = [0xfe, 0x80, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1];
+        let dst6 = [0xfe, 0x80, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2];
+        let result = header.verify(&src6, &dst6, payload, caps);
+        assert_eq!(result, Err(ChecksumAssertion));
+        let result = header.verify(&src6, &dst6, payload, Capabilities::LOOPBACK);
+        assert_eq!(result, Ok(()));
+
+        let pseudo = [0x00, 0x11, 0x00, 0x0c];
+        let cks = !compute_checksum_chained(&[&src, &dst, &pseudo, &bytes]);
+        bytes[6..8].copy_from_slice(&cks.to_ne_bytes());
+        let (header, payload) = Udp::from_bytes(&bytes).unwrap();
+        assert_eq!(header.verify(&src, &dst, payload, caps), Ok(()));
+
+        bytes[11] ^= 0xff;
+        let (header, payload) = Udp::from_bytes(&bytes).unwrap();
+        let result = header.verify(&src, &dst, payload, caps);
+        assert_eq!(result, Err(ChecksumAssertion));
+        let result = header.verify(&src, &dst, payload, Capabilities::LOOPBACK);
+        assert_eq!(result, Ok(()));
+        let result = header.verify(&src, &dst, payload, Capabilities::RX_CHECKSUM_OFFLOAD);
+        assert_eq!(result, Ok(()));
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn round_trip(src_port: u16, dst_port: u16, len: u16, checksum: u16, payload: Vec<u8>) {
+            let mut bytes = Udp::new(src_port, dst_port, len, checksum).as_bytes().to_vec();
+            bytes.extend_from_slice(&payload);
+
+            let (header, rest) = Udp::from_bytes(&bytes).unwrap();
+            proptest::prop_assert_eq!(header.source_port(), src_port);
+            proptest::prop_assert_eq!(header.destination_port(), dst_port);
+            proptest::prop_assert_eq!(header.len(), len);
+            proptest::prop_assert_eq!(header.checksum(), checksum);
+            proptest::prop_assert_eq!(rest, &payload[..]);
+        }
+    }
 }
@@ -0,0 +1,287 @@
+//! [`Geneve`] header
+//!
+//! [`Geneve`] header identifying the virtual network and protocol of an encapsulated payload,
+//! followed by variable length [`GeneveOption`]s.
+use core::fmt;
+
+use crate::header::error::HeaderTruncated;
+use crate::header::link::EtherType;
+use crate::header::primitive::{U16, U8};
+use crate::header::utils::{as_header, split_at};
+use crate::header::Header;
+
+/// A Generic Network Virtualization Encapsulation (Geneve) header. [Read more][RFC 8926]
+///
+/// Geneve is carried over UDP, destination port [`Geneve::PORT`]. Like VXLAN, it identifies a
+/// virtual network with a 24-bit VNI, but it also names the protocol of the encapsulated payload
+/// and may carry metadata as TLV encoded options. Since options are dynamic in length, they are
+/// split from the required portion of the header.
+///
+/// [RFC 8926]: https://tools.ietf.org/html/rfc8926#section-3
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct Geneve<'a> {
+    required: &'a GeneveRequired,
+    options: &'a [u8],
+}
+
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[repr(C)]
+struct GeneveRequired {
+    ver_opt_len: U8,
+    flags: U8,
+    protocol_type: U16,
+    vni: [u8; 3],
+    reserved: U8,
+}
+
+impl<'a> Geneve<'a> {
+    /// The well known UDP port of Geneve.
+    pub const PORT: u16 = 6081;
+
+    const FLAG_OAM: u8 = 0b1000_0000;
+    const FLAG_CRITICAL: u8 = 0b0100_0000;
+
+    /// Returns an immutable view of `bytes` as a Geneve header followed by the encapsulated
+    /// payload or an error if the size or contents do not represent a valid Geneve header.
+    ///
+    /// Only version 0 is recognized.
+    #[inline]
+    pub const fn from_bytes(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), HeaderTruncated> {
+        let (required, options_payload) = match as_header!(GeneveRequired, bytes) {
+            Ok(v) => v,
+            Err(e) => return Err(e),
+        };
+
+        let header = Geneve {
+            required,
+            options: &[],
+        };
+        if header.version() != 0 {
+            return Err(HeaderTruncated);
+        }
+
+        let (options, payload) = match split_at(options_payload, header.options_len()) {
+            Some(v) => v,
+            None => return Err(HeaderTruncated),
+        };
+
+        Ok((Geneve { required, options }, payload))
+    }
+
+    /// Returns the version of the Geneve header. Always `0`.
+    #[inline]
+    pub const fn version(&self) -> u8 {
+        self.required.ver_opt_len.get() >> 6
+    }
+
+    /// Returns the length of the options in bytes. The length is encoded in 4-octet units.
+    #[inline]
+    pub const fn options_len(&self) -> usize {
+        (self.required.ver_opt_len.get() & 0b0011_1111) as usize * 4
+    }
+
+    /// Returns the length of the Geneve header, including options, in bytes.
+    #[inline]
+    pub const fn header_len(&self) -> usize {
+        core::mem::size_of::<GeneveRequired>() + self.options_len()
+    }
+
+    /// Returns `true` if the payload is a control message rather than tenant data.
+    #[inline]
+    pub const fn is_oam(&self) -> bool {
+        self.required.flags.get() & Self::FLAG_OAM != 0
+    }
+
+    /// Returns `true` if one or more options have the critical bit set. Tunnel endpoints that do
+    /// not recognize a critical option must drop the packet.
+    #[inline]
+    pub const fn has_critical_options(&self) -> bool {
+        self.required.flags.get() & Self::FLAG_CRITICAL != 0
+    }
+
+    /// Returns the protocol of the encapsulated payload, such as
+    /// [`EtherType::TransparentEthernetBridging`] for an Ethernet frame.
+    #[inline]
+    pub const fn protocol_type(&self) -> EtherType {
+        EtherType::new(self.required.protocol_type.get())
+    }
+
+    /// Returns the Virtual Network Identifier (VNI), a `u24` identifying the virtual network of
+    /// the payload.
+    #[inline]
+    pub const fn vni(&self) -> u32 {
+        let vni = self.required.vni;
+        u32::from_be_bytes([0, vni[0], vni[1], vni[2]])
+    }
+
+    /// Returns iterator of [`GeneveOption`].
+    #[inline]
+    pub const fn options(&self) -> GeneveOptions<'a> {
+        GeneveOptions {
+            options: self.options,
+        }
+    }
+}
+
+impl<'a> Header<'a> for Geneve<'a> {
+    #[inline]
+    fn from_bytes(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), HeaderTruncated> {
+        Geneve::from_bytes(bytes)
+    }
+}
+
+impl<'a> fmt::Display for Geneve<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Geneve vni={} protocol={}",
+            self.vni(),
+            self.protocol_type()
+        )?;
+        if self.is_oam() {
+            write!(f, " oam")?;
+        }
+        if self.options_len() != 0 {
+            write!(f, " options_len={}", self.options_len())?;
+        }
+        Ok(())
+    }
+}
+
+/// Iterator of [`GeneveOption`].
+///
+/// Iteration stops early if an option claims more bytes than remain in the header.
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct GeneveOptions<'a> {
+    options: &'a [u8],
+}
+
+impl<'a> Iterator for GeneveOptions<'a> {
+    type Item = GeneveOption<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Some((&[c0, c1, ty, len], rest)) = self.options.split_first_chunk::<4>() else {
+            self.options = &[];
+            return None;
+        };
+        let Some((data, rest)) = split_at(rest, (len & 0b0001_1111) as usize * 4) else {
+            self.options = &[];
+            return None;
+        };
+        self.options = rest;
+
+        Some(GeneveOption {
+            class: u16::from_be_bytes([c0, c1]),
+            ty,
+            data,
+        })
+    }
+}
+
+/// A TLV encoded option carried by a [`Geneve`] header. [Read more][RFC 8926]
+///
+/// The meaning of an option is determined by its class, which is assigned by [IANA], and a type
+/// defined by the owner of the class.
+///
+/// [RFC 8926]: https://tools.ietf.org/html/rfc8926#section-3.5
+/// [IANA]: https://www.iana.org/assignments/nvo3/nvo3.xhtml
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub struct GeneveOption<'a> {
+    class: u16,
+    ty: u8,
+    data: &'a [u8],
+}
+
+impl<'a> GeneveOption<'a> {
+    /// Returns the namespace of the option type.
+    #[inline]
+    pub const fn class(&self) -> u16 {
+        self.class
+    }
+
+    /// Returns the type of the option within its class, including the critical bit.
+    #[inline]
+    pub const fn option_type(&self) -> u8 {
+        self.ty
+    }
+
+    /// Returns `true` if the packet must be dropped by a tunnel endpoint that does not recognize
+    /// the option.
+    #[inline]
+    pub const fn is_critical(&self) -> bool {
+        self.ty & 0b1000_0000 != 0
+    }
+
+    /// Returns the option data. Always a multiple of 4 bytes.
+    #[inline]
+    pub const fn data(&self) -> &'a [u8] {
+        self.data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::header::link::{EtherAddr, EthernetII};
+
+    #[test]
+    fn short_header() {
+        let bytes = [0; 7];
+        assert_eq!(Geneve::from_bytes(&bytes).unwrap_err(), HeaderTruncated);
+
+        // options length of 1 requires 12 bytes
+        let bytes = [0x01, 0, 0x65, 0x58, 0, 0, 0x01, 0, 0, 0, 0];
+        assert_eq!(Geneve::from_bytes(&bytes).unwrap_err(), HeaderTruncated);
+    }
+
+    #[test]
+    fn unknown_version() {
+        let bytes = [0x40, 0, 0x65, 0x58, 0, 0, 0x01, 0];
+        assert_eq!(Geneve::from_bytes(&bytes).unwrap_err(), HeaderTruncated);
+    }
+
+    #[test]
+    fn one_option() {
+        let bytes = [
+            0x02, 0x40, 0x65, 0x58, 0x00, 0x30, 0x39, 0x00, 0x01, 0x02, 0x80, 0x01, 0xDE, 0xAD,
+            0xBE, 0xEF, 0x02, 0x00, 0x00, 0x00, 0x00, 0x02, 0x02, 0x00, 0x00, 0x00, 0x00, 0x01,
+            0x08, 0x00,
+        ];
+        let (header, payload) = Geneve::from_bytes(&bytes).unwrap();
+        assert_eq!(header.version(), 0);
+        assert_eq!(header.vni(), 12345);
+        assert_eq!(
+            header.protocol_type(),
+            EtherType::TransparentEthernetBridging
+        );
+        assert_eq!(header.header_len(), 16);
+        assert!(!header.is_oam());
+        assert!(header.has_critical_options());
+        assert_eq!(
+            header.to_string(),
+            "Geneve vni=12345 protocol=TransparentEthernetBridging options_len=8"
+        );
+
+        let mut options = header.options();
+        let option = options.next().unwrap();
+        assert_eq!(option.class(), 0x0102);
+        assert_eq!(option.option_type(), 0x80);
+        assert!(option.is_critical());
+        assert_eq!(option.data(), &[0xDE, 0xAD, 0xBE, 0xEF]);
+        assert_eq!(options.next(), None);
+
+        let (inner, _) = EthernetII::from_bytes(payload).unwrap();
+        assert_eq!(inner.src(), EtherAddr::new([0x02, 0, 0, 0, 0, 0x01]));
+        assert_eq!(inner.ethertype(), EtherType::Ipv4);
+    }
+
+    #[test]
+    fn option_overruns_header() {
+        let bytes = [
+            0x02, 0x00, 0x65, 0x58, 0x00, 0x00, 0x01, 0x00, 0x01, 0x02, 0x03, 0x02, 0xDE, 0xAD,
+            0xBE, 0xEF,
+        ];
+        let (header, _) = Geneve::from_bytes(&bytes).unwrap();
+        assert_eq!(header.options().next(), None);
+    }
+}
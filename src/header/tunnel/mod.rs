@@ -0,0 +1,14 @@
+//! Overlay tunnels carried over the transport layer
+//!
+//! Tunnel protocols encapsulate a complete frame or packet in the payload of a transport layer
+//! datagram, stretching a virtual network across another. Each header here is followed by the
+//! encapsulated traffic, which can be parsed with the headers of the other layers. For more info,
+//! see [RFC 7348].
+//!
+//! [RFC 7348]: https://tools.ietf.org/html/rfc7348
+
+mod geneve;
+mod vxlan;
+
+pub use geneve::*;
+pub use vxlan::*;
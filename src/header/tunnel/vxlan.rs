@@ -0,0 +1,106 @@
+//! [`Vxlan`] header
+//!
+//! [`Vxlan`] header identifying the virtual network of an encapsulated Ethernet frame.
+use core::fmt;
+
+use crate::header::error::HeaderTruncated;
+use crate::header::primitive::U8;
+use crate::header::utils::as_header;
+use crate::header::Header;
+
+/// A Virtual eXtensible Local Area Network (VXLAN) header. [Read more][RFC 7348]
+///
+/// VXLAN carries Ethernet frames over UDP, destination port [`Vxlan::PORT`]. The header identifies
+/// which of up to 16 million virtual networks the frame belongs to with a 24-bit VXLAN Network
+/// Identifier (VNI). The payload is the inner frame, starting with an
+/// [`EthernetII`][crate::header::link::EthernetII] header.
+///
+/// [RFC 7348]: https://tools.ietf.org/html/rfc7348#section-5
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[repr(C)]
+pub struct Vxlan {
+    flags: U8,
+    reserved: [u8; 3],
+    vni: [u8; 3],
+    reserved2: U8,
+}
+
+impl Vxlan {
+    /// The well known UDP port of VXLAN.
+    pub const PORT: u16 = 4789;
+
+    const FLAG_I: u8 = 0b0000_1000;
+
+    /// Returns an immutable view of `bytes` as a VXLAN header followed by the inner frame or an
+    /// error if the size or contents do not represent a valid VXLAN header.
+    ///
+    /// The I flag, marking the VNI as valid, must be set.
+    #[inline]
+    pub const fn from_bytes(bytes: &[u8]) -> Result<(&Self, &[u8]), HeaderTruncated> {
+        let (header, payload) = match as_header!(Vxlan, bytes) {
+            Ok(v) => v,
+            Err(e) => return Err(e),
+        };
+
+        if header.flags.get() & Self::FLAG_I == 0 {
+            return Err(HeaderTruncated);
+        }
+
+        Ok((header, payload))
+    }
+
+    /// Returns the VXLAN Network Identifier (VNI), a `u24` identifying the virtual network of the
+    /// inner frame.
+    #[inline]
+    pub const fn vni(&self) -> u32 {
+        u32::from_be_bytes([0, self.vni[0], self.vni[1], self.vni[2]])
+    }
+}
+
+impl<'a> Header<'a> for &'a Vxlan {
+    #[inline]
+    fn from_bytes(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), HeaderTruncated> {
+        Vxlan::from_bytes(bytes)
+    }
+}
+
+impl fmt::Display for Vxlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "VXLAN vni={}", self.vni())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::header::link::{EtherAddr, EtherType, EthernetII};
+
+    #[test]
+    fn short_header() {
+        let bytes = [0x08, 0, 0, 0, 0, 0, 0];
+        assert_eq!(Vxlan::from_bytes(&bytes).unwrap_err(), HeaderTruncated);
+    }
+
+    #[test]
+    fn missing_vni_flag() {
+        let bytes = [0x00, 0, 0, 0, 0x12, 0x34, 0x56, 0];
+        assert_eq!(Vxlan::from_bytes(&bytes).unwrap_err(), HeaderTruncated);
+    }
+
+    #[test]
+    fn inner_frame() {
+        let bytes = [
+            0x08, 0x00, 0x00, 0x00, 0x12, 0x34, 0x56, 0x00, 0x02, 0x00, 0x00, 0x00, 0x00, 0x02,
+            0x02, 0x00, 0x00, 0x00, 0x00, 0x01, 0x08, 0x06, 0xAA,
+        ];
+        let (header, payload) = Vxlan::from_bytes(&bytes).unwrap();
+        assert_eq!(header.vni(), 0x123456);
+        assert_eq!(header.to_string(), "VXLAN vni=1193046");
+
+        let (inner, payload) = EthernetII::from_bytes(payload).unwrap();
+        assert_eq!(inner.src(), EtherAddr::new([0x02, 0, 0, 0, 0, 0x01]));
+        assert_eq!(inner.dst(), EtherAddr::new([0x02, 0, 0, 0, 0, 0x02]));
+        assert_eq!(inner.ethertype(), EtherType::Arp);
+        assert_eq!(payload, &[0xAA]);
+    }
+}
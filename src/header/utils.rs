@@ -44,6 +44,54 @@ macro_rules! as_header {
 }
 pub(crate) use as_header;
 
+/// Unsafe cast of a mutable slice of bytes to a mutable header type and payload.
+///
+/// Upholds the same invariants as [`as_header`]. Additionally, every field of a header type must be
+/// valid for any bit pattern, since the bytes may be written through the header or the slice.
+macro_rules! as_header_mut {
+    ($header:ty, $bytes:ident) => {{
+        // check if header type is unaligned at compile time
+        const _: () = if ::core::mem::align_of::<$header>() != 1 {
+            panic!("{}", stringify!(align_of<$header> != 1))
+        };
+
+        if let Some((header, payload)) = $crate::header::utils::split_at_mut($bytes, ::core::mem::size_of::<$header>()) {
+            // Safety: There are enough $bytes to fill $header and $header meets alignment and padding
+            // constraints. The mutable borrow of $bytes is split, so the header and payload do not
+            // alias.
+            #[allow(unsafe_code)]
+            let header = unsafe { &mut *(header.as_mut_ptr() as *mut $header) };
+            Ok((header, payload))
+        } else {
+            Err($crate::header::error::HeaderTruncated)
+        }
+    }};
+}
+pub(crate) use as_header_mut;
+
+/// Unsafe cast of an immutable header type to an immutable array of bytes.
+///
+/// The inverse of [`as_header`], upholding the same alignment and padding invariants. Since the
+/// header type is already constructed, there is no size check.
+macro_rules! as_bytes {
+    ($header:ty, $value:expr) => {{
+        // check if header type is unaligned at compile time
+        const _: () = if ::core::mem::align_of::<$header>() != 1 {
+        panic!("{}", stringify!(align_of<$header> != 1))
+        };
+
+        let header: &$header = $value;
+        // Safety: $header is unaligned and contains no padding, so it is exactly
+        // ::core::mem::size_of::<$header>() initialized bytes.
+        #[allow(unsafe_code)]
+        let bytes = unsafe {
+            &*(header as *const $header as *const [u8; ::core::mem::size_of::<$header>()])
+        };
+        bytes
+    }};
+}
+pub(crate) use as_bytes;
+
 #[inline]
 pub(crate) const fn split_word(slice: &[u8]) -> Option<(u16, &[u8])> {
     if slice.len() < core::mem::size_of::<u16>() {
@@ -66,6 +114,15 @@ pub(crate) const fn split_at(slice: &[u8], mid: usize) -> Option<(&[u8], &[u8])>
     }
 }
 
+#[inline]
+pub(crate) fn split_at_mut(slice: &mut [u8], mid: usize) -> Option<(&mut [u8], &mut [u8])> {
+    if slice.len() < mid {
+        None
+    } else {
+        Some(slice.split_at_mut(mid))
+    }
+}
+
 // `const` version of `core::slice::from_raw_parts` copied from
 // https://github.com/rust-lang/rust/pull/100076.
 // Use this until #![feature(const_slice_split_at_not_mut)] is stable.
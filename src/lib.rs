@@ -17,4 +17,7 @@
 //! [smoltcp]: https://docs.rs/smoltcp/latest/smoltcp/
 
 pub mod header;
+pub mod nat;
 pub mod netdev;
+pub mod pmtu;
+pub mod time;
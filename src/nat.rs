@@ -0,0 +1,682 @@
+//! Network Address Translation (NAT)
+//!
+//! Rewrites the addresses and ports of IPv4 packets in place, as a NAT router does at the boundary
+//! of a private network. Checksums of both the IPv4 header and the transport header are updated
+//! incrementally. [Read more][RFC 3022]
+//!
+//! Forwarded TCP handshakes can also have their Maximum Segment Size clamped to the MTU of the
+//! egress link with [`clamp_mss`].
+//!
+//! A [`ConntrackTable`] keeps the state of a Network Address Port Translator (NAPT), mapping flows
+//! of inside hosts onto ports of a single public address.
+//!
+//! [RFC 3022]: https://tools.ietf.org/html/rfc3022
+use core::time::Duration;
+
+use crate::header::checksum::update_checksum;
+use crate::header::error::HeaderTruncated;
+use crate::header::internet::{IpProtocol, Ipv4Addr, Ipv4Mut};
+use crate::header::transport::{Tcp, TcpOption};
+use crate::time::Instant;
+
+/// Rewrites the source address of an IPv4 packet, and the source port of a UDP or TCP packet if
+/// `port` is given, as a NAT router does on egress. `payload` is the payload of `header`.
+///
+/// Returns an error if `payload` is too short to hold the transport checksum and ports. A port is
+/// ignored for protocols other than UDP and TCP, and for fragments other than the first, which
+/// carry no transport header. Only the address of such fragments is rewritten.
+#[inline]
+pub fn translate_src(
+    header: &mut Ipv4Mut<'_>,
+    payload: &mut [u8],
+    addr: Ipv4Addr,
+    port: Option<u16>,
+) -> Result<(), HeaderTruncated> {
+    let old = header.as_ipv4().src();
+    translate(header, payload, old, addr, port, 0)?;
+    header.set_src(addr);
+    Ok(())
+}
+
+/// Rewrites the destination address of an IPv4 packet, and the destination port of a UDP or TCP
+/// packet if `port` is given, as a NAT router does on ingress. `payload` is the payload of
+/// `header`.
+///
+/// Returns an error if `payload` is too short to hold the transport checksum and ports. A port is
+/// ignored for protocols other than UDP and TCP, and for fragments other than the first, which
+/// carry no transport header. Only the address of such fragments is rewritten.
+#[inline]
+pub fn translate_dst(
+    header: &mut Ipv4Mut<'_>,
+    payload: &mut [u8],
+    addr: Ipv4Addr,
+    port: Option<u16>,
+) -> Result<(), HeaderTruncated> {
+    let old = header.as_ipv4().dst();
+    translate(header, payload, old, addr, port, 2)?;
+    header.set_dst(addr);
+    Ok(())
+}
+
+// Updates the checksums for a change of address, rewriting the port at `port_offset` of the
+// transport header along the way.
+fn translate(
+    header: &mut Ipv4Mut<'_>,
+    payload: &mut [u8],
+    old: Ipv4Addr,
+    new: Ipv4Addr,
+    port: Option<u16>,
+    port_offset: usize,
+) -> Result<(), HeaderTruncated> {
+    let ipv4 = header.as_ipv4();
+    let (protocol, cks) = (ipv4.protocol(), ipv4.cks());
+
+    // UDP and TCP checksums cover the addresses through the pseudo-header. The payload of a
+    // fragment other than the first is data, not a transport header.
+    let cks_offset = match protocol {
+        _ if ipv4.offset() != 0 => None,
+        IpProtocol::UDP => Some(6),
+        IpProtocol::TCP => Some(16),
+        _ => None,
+    };
+    if let Some(cks_offset) = cks_offset {
+        let cks_bytes = payload
+            .get(cks_offset..cks_offset + 2)
+            .ok_or(HeaderTruncated)?;
+        let mut transport_cks = u16::from_be_bytes([cks_bytes[0], cks_bytes[1]]);
+        // a UDP checksum of zero means no checksum was computed
+        let unused = protocol == IpProtocol::UDP && transport_cks == 0;
+
+        transport_cks = update_checksum(transport_cks, old.as_bytes(), new.as_bytes());
+        if let Some(port) = port {
+            let port_bytes = &mut payload[port_offset..port_offset + 2];
+            transport_cks = update_checksum(transport_cks, port_bytes, &port.to_be_bytes());
+            port_bytes.copy_from_slice(&port.to_be_bytes());
+        }
+
+        if !unused {
+            if protocol == IpProtocol::UDP && transport_cks == 0 {
+                transport_cks = 0xFFFF;
+            }
+            payload[cks_offset..cks_offset + 2].copy_from_slice(&transport_cks.to_be_bytes());
+        }
+    }
+
+    header.set_cks(update_checksum(cks, old.as_bytes(), new.as_bytes()));
+    Ok(())
+}
+
+/// Lowers the Maximum Segment Size (MSS) option of a forwarded TCP SYN segment to fit an egress
+/// link of `egress_mtu` bytes, `egress_mtu - 40`, and updates the TCP checksum incrementally.
+/// `payload` is the payload of `header`.
+///
+/// Hosts choose the MSS from the MTU of their own link, so without clamping, segments too large for
+/// a smaller link further along the path may be silently dropped when ICMP is filtered. Returns
+/// `Ok(true)` if the MSS was lowered. Segments that are not TCP, do not have the SYN flag, or have
+/// no larger MSS option are left untouched, as are fragments other than the first. Returns an error
+/// if `payload` is too short to hold the TCP header.
+pub fn clamp_mss(
+    header: &Ipv4Mut<'_>,
+    payload: &mut [u8],
+    egress_mtu: usize,
+) -> Result<bool, HeaderTruncated> {
+    let ipv4 = header.as_ipv4();
+    // only the first fragment carries the TCP header
+    if ipv4.protocol() != IpProtocol::TCP || ipv4.offset() != 0 {
+        return Ok(false);
+    }
+    let (mut tcp, _) = Tcp::from_bytes_mut(payload)?;
+    if !tcp.as_tcp().syn() {
+        return Ok(false);
+    }
+
+    let max_mss = u16::try_from(egress_mtu.saturating_sub(40)).unwrap_or(u16::MAX);
+    let mss = tcp.as_tcp().options().find_map(|option| match option {
+        TcpOption::Mss(mss) => Some(mss),
+        _ => None,
+    });
+    match mss {
+        Some(mss) if mss > max_mss => Ok(tcp.set_mss(max_mss)),
+        _ => Ok(false),
+    }
+}
+
+/// A connection tracking table of a Network Address Port Translator (NAPT) with room for `N`
+/// flows.
+///
+/// Each UDP or TCP flow from an inside host is assigned an outside port of the public address,
+/// allocated from a range of ports separately per protocol. Packets leaving the inside network are
+/// rewritten to the public address and outside port, and replies are rewritten back to the inside
+/// host. Flows idle for longer than the timeout expire.
+///
+/// The table has no clock of its own. Every call takes `now`, read from a
+/// [`Clock`][crate::time::Clock].
+#[derive(Debug, Clone)]
+pub struct ConntrackTable<const N: usize> {
+    flows: [Option<Flow>; N],
+    public: Ipv4Addr,
+    first_port: u16,
+    last_port: u16,
+    next_port: u16,
+    timeout: Duration,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Flow {
+    protocol: IpProtocol,
+    inside_addr: Ipv4Addr,
+    inside_port: u16,
+    outside_port: u16,
+    last_seen: Instant,
+}
+
+impl Flow {
+    fn is_expired(&self, now: Instant, timeout: Duration) -> bool {
+        now - self.last_seen > timeout
+    }
+}
+
+impl<const N: usize> ConntrackTable<N> {
+    /// Constructs an empty table translating to the `public` address, allocating outside ports
+    /// from `first_port` through `last_port` and expiring flows idle for longer than `timeout`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `first_port` is greater than `last_port`.
+    #[inline]
+    pub const fn new(public: Ipv4Addr, first_port: u16, last_port: u16, timeout: Duration) -> Self {
+        assert!(first_port <= last_port, "empty port range");
+        ConntrackTable {
+            flows: [None; N],
+            public,
+            first_port,
+            last_port,
+            next_port: first_port,
+            timeout,
+        }
+    }
+
+    /// Returns the public address packets are translated to.
+    #[inline]
+    pub const fn public_addr(&self) -> Ipv4Addr {
+        self.public
+    }
+
+    /// Returns the number of tracked flows, including idle flows not yet expired.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.flows.iter().flatten().count()
+    }
+
+    /// Returns `true` if no flows are tracked.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.flows.iter().all(Option::is_none)
+    }
+
+    /// Forgets flows idle for longer than the timeout at time `now`.
+    pub fn expire(&mut self, now: Instant) {
+        for slot in &mut self.flows {
+            if slot.is_some_and(|flow| flow.is_expired(now, self.timeout)) {
+                *slot = None;
+            }
+        }
+    }
+
+    /// Translates a packet leaving the inside network, rewriting its source address and port to
+    /// the public address and the outside port of its flow. A flow is created for the first packet
+    /// from an inside address and port. `payload` is the payload of `header`.
+    ///
+    /// Fragments other than the first carry no ports to identify their flow. Since every flow
+    /// leaves from the public address, only their source address is rewritten.
+    ///
+    /// Returns `Ok(false)`, leaving the packet untouched, if it is neither UDP nor TCP or a flow
+    /// could not be created because the table or the port range is exhausted. Returns an error if
+    /// `payload` is too short to hold the transport checksum and ports.
+    pub fn egress(
+        &mut self,
+        header: &mut Ipv4Mut<'_>,
+        payload: &mut [u8],
+        now: Instant,
+    ) -> Result<bool, HeaderTruncated> {
+        let protocol = header.as_ipv4().protocol();
+        if !is_tracked(protocol) {
+            return Ok(false);
+        }
+        if header.as_ipv4().offset() != 0 {
+            translate_src(header, payload, self.public, None)?;
+            return Ok(true);
+        }
+        let inside_addr = header.as_ipv4().src();
+        let inside_port = port(payload, 0)?;
+
+        let timeout = self.timeout;
+        let found = self.flows.iter_mut().flatten().find(|flow| {
+            flow.protocol == protocol
+                && flow.inside_addr == inside_addr
+                && flow.inside_port == inside_port
+        });
+        let outside_port = match found {
+            Some(flow) if !flow.is_expired(now, timeout) => {
+                flow.last_seen = now;
+                flow.outside_port
+            }
+            _ => match self.insert(protocol, inside_addr, inside_port, now) {
+                Some(outside_port) => outside_port,
+                None => return Ok(false),
+            },
+        };
+
+        translate_src(header, payload, self.public, Some(outside_port))?;
+        Ok(true)
+    }
+
+    /// Translates a packet arriving from the outside network, rewriting its destination address
+    /// and port back to the inside host of its flow. `payload` is the payload of `header`.
+    ///
+    /// Returns `Ok(false)`, leaving the packet untouched, if it is neither UDP nor TCP, is not
+    /// addressed to the public address, or does not belong to a tracked flow. Fragments other than
+    /// the first carry no ports to identify their flow, so they are left untouched too and must be
+    /// reassembled before translation. Returns an error if `payload` is too short to hold the
+    /// transport checksum and ports.
+    pub fn ingress(
+        &mut self,
+        header: &mut Ipv4Mut<'_>,
+        payload: &mut [u8],
+        now: Instant,
+    ) -> Result<bool, HeaderTruncated> {
+        let protocol = header.as_ipv4().protocol();
+        if !is_tracked(protocol)
+            || header.as_ipv4().dst() != self.public
+            || header.as_ipv4().offset() != 0
+        {
+            return Ok(false);
+        }
+        let outside_port = port(payload, 2)?;
+
+        let timeout = self.timeout;
+        let found = self.flows.iter_mut().flatten().find(|flow| {
+            flow.protocol == protocol
+                && flow.outside_port == outside_port
+                && !flow.is_expired(now, timeout)
+        });
+        let Some(flow) = found else {
+            return Ok(false);
+        };
+        flow.last_seen = now;
+
+        let (inside_addr, inside_port) = (flow.inside_addr, flow.inside_port);
+        translate_dst(header, payload, inside_addr, Some(inside_port))?;
+        Ok(true)
+    }
+
+    // Creates a flow in a free or expired slot, returning its newly allocated outside port.
+    fn insert(
+        &mut self,
+        protocol: IpProtocol,
+        inside_addr: Ipv4Addr,
+        inside_port: u16,
+        now: Instant,
+    ) -> Option<u16> {
+        self.expire(now);
+        let slot = self.flows.iter().position(Option::is_none)?;
+        let outside_port = self.allocate_port(protocol)?;
+        self.flows[slot] = Some(Flow {
+            protocol,
+            inside_addr,
+            inside_port,
+            outside_port,
+            last_seen: now,
+        });
+        Some(outside_port)
+    }
+
+    // Returns the next port of the range not used by a flow of `protocol`.
+    fn allocate_port(&mut self, protocol: IpProtocol) -> Option<u16> {
+        let range_len = u32::from(self.last_port - self.first_port) + 1;
+        for _ in 0..range_len {
+            let port = self.next_port;
+            self.next_port = if port == self.last_port {
+                self.first_port
+            } else {
+                port + 1
+            };
+
+            let in_use = self
+                .flows
+                .iter()
+                .flatten()
+                .any(|flow| flow.protocol == protocol && flow.outside_port == port);
+            if !in_use {
+                return Some(port);
+            }
+        }
+        None
+    }
+}
+
+fn is_tracked(protocol: IpProtocol) -> bool {
+    protocol == IpProtocol::UDP || protocol == IpProtocol::TCP
+}
+
+// Reads the port at `offset` of a UDP or TCP header.
+fn port(payload: &[u8], offset: usize) -> Result<u16, HeaderTruncated> {
+    match payload.get(offset..offset + 2) {
+        Some(bytes) => Ok(u16::from_be_bytes([bytes[0], bytes[1]])),
+        None => Err(HeaderTruncated),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::header::checksum::{compute_checksum, compute_checksum_chained, verify_checksum};
+    use crate::header::internet::Ipv4;
+    use crate::header::transport::Udp;
+
+    // IPv4/UDP 192.168.0.10:5000 → 8.8.8.8:53 with valid checksums
+    fn udp_packet() -> [u8; 32] {
+        let mut bytes = [
+            0x45, 0x00, 0x00, 0x20, 0x00, 0x00, 0x40, 0x00, 0x40, 0x11, 0x00, 0x00, 0xc0, 0xa8,
+            0x00, 0x0a, 0x08, 0x08, 0x08, 0x08, 0x13, 0x88, 0x00, 0x35, 0x00, 0x0c, 0x00, 0x00,
+            0xDE, 0xAD, 0xBE, 0xEF,
+        ];
+        let cks = !compute_checksum(&bytes[..20]);
+        bytes[10..12].copy_from_slice(&cks.to_ne_bytes());
+        let cks = !udp_checksum(&bytes);
+        bytes[26..28].copy_from_slice(&cks.to_ne_bytes());
+        bytes
+    }
+
+    fn udp_checksum(bytes: &[u8]) -> u16 {
+        let pseudo = [0x00, 0x11, bytes[24], bytes[25]];
+        compute_checksum_chained(&[&bytes[12..20], &pseudo, &bytes[20..]])
+    }
+
+    #[test]
+    fn translate_udp() {
+        let mut bytes = udp_packet();
+        assert_eq!(verify_checksum(&bytes[..20]), Ok(()));
+        assert_eq!(udp_checksum(&bytes), !0);
+
+        let public = Ipv4Addr::new([203, 0, 113, 7]);
+        let (mut header, payload) = Ipv4::from_bytes_mut(&mut bytes).unwrap();
+        translate_src(&mut header, payload, public, Some(61000)).unwrap();
+
+        assert_eq!(verify_checksum(&bytes[..20]), Ok(()));
+        assert_eq!(udp_checksum(&bytes), !0);
+        let (header, payload) = Ipv4::from_bytes(&bytes).unwrap();
+        assert_eq!(header.src(), public);
+        let (udp, _) = Udp::from_bytes(payload).unwrap();
+        assert_eq!(udp.source_port(), 61000);
+        assert_eq!(udp.destination_port(), 53);
+
+        // reply comes back to the public address and is translated to the inside host
+        let inside = Ipv4Addr::new([192, 168, 0, 10]);
+        let (mut header, payload) = Ipv4::from_bytes_mut(&mut bytes).unwrap();
+        translate_dst(&mut header, payload, inside, Some(5000)).unwrap();
+
+        assert_eq!(verify_checksum(&bytes[..20]), Ok(()));
+        assert_eq!(udp_checksum(&bytes), !0);
+        let (header, payload) = Ipv4::from_bytes(&bytes).unwrap();
+        assert_eq!(header.dst(), inside);
+        let (udp, _) = Udp::from_bytes(payload).unwrap();
+        assert_eq!(udp.destination_port(), 5000);
+    }
+
+    #[test]
+    fn translate_fragment() {
+        // a later fragment at offset 8 carrying data only
+        let mut bytes = udp_packet();
+        bytes[6..8].copy_from_slice(&[0x00, 0x01]);
+        bytes[10..12].copy_from_slice(&[0, 0]);
+        let cks = !compute_checksum(&bytes[..20]);
+        bytes[10..12].copy_from_slice(&cks.to_ne_bytes());
+        let data = bytes[20..].to_vec();
+
+        let public = Ipv4Addr::new([203, 0, 113, 7]);
+        let (mut header, payload) = Ipv4::from_bytes_mut(&mut bytes).unwrap();
+        translate_src(&mut header, payload, public, Some(61000)).unwrap();
+
+        assert_eq!(verify_checksum(&bytes[..20]), Ok(()));
+        let (header, payload) = Ipv4::from_bytes(&bytes).unwrap();
+        assert_eq!(header.src(), public);
+        assert_eq!(payload, data);
+
+        // even if it is too short to hold a transport header
+        let (mut header, payload) = Ipv4::from_bytes_mut(&mut bytes).unwrap();
+        translate_dst(&mut header, &mut payload[..4], public, Some(61000)).unwrap();
+        assert_eq!(verify_checksum(&bytes[..20]), Ok(()));
+        assert_eq!(&bytes[20..], data);
+    }
+
+    #[test]
+    fn translate_udp_without_checksum() {
+        let mut bytes = udp_packet();
+        bytes[26..28].copy_from_slice(&[0, 0]);
+
+        let (mut header, payload) = Ipv4::from_bytes_mut(&mut bytes).unwrap();
+        translate_src(&mut header, payload, Ipv4Addr::new([203, 0, 113, 7]), None).unwrap();
+
+        assert_eq!(verify_checksum(&bytes[..20]), Ok(()));
+        assert_eq!(&bytes[26..28], &[0, 0]);
+    }
+
+    // IPv4/TCP SYN 192.168.0.10:5000 → 8.8.8.8:80 with `options` and a valid TCP checksum
+    fn tcp_syn(options: &[u8]) -> Vec<u8> {
+        let len = 40 + options.len();
+        let mut bytes = vec![
+            0x45, 0x00, 0x00, len as u8, 0x00, 0x00, 0x40, 0x00, 0x40, 0x06, 0x00, 0x00, 0xc0,
+            0xa8, 0x00, 0x0a, 0x08, 0x08, 0x08, 0x08, 0x13, 0x88, 0x00, 0x50, 0x00, 0x00, 0x00,
+            0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00,
+        ];
+        bytes[32] = (((20 + options.len()) / 4) << 4) as u8;
+        bytes.extend_from_slice(options);
+        let cks = !tcp_checksum(&bytes);
+        bytes[36..38].copy_from_slice(&cks.to_ne_bytes());
+        bytes
+    }
+
+    fn tcp_checksum(bytes: &[u8]) -> u16 {
+        let len = ((bytes.len() - 20) as u16).to_be_bytes();
+        let pseudo = [0x00, 0x06, len[0], len[1]];
+        compute_checksum_chained(&[&bytes[12..20], &pseudo, &bytes[20..]])
+    }
+
+    fn mss(bytes: &[u8], offset: usize) -> u16 {
+        u16::from_be_bytes([bytes[offset], bytes[offset + 1]])
+    }
+
+    #[test]
+    fn clamp_syn_mss() {
+        let mut bytes = tcp_syn(&[0x02, 0x04, 0x05, 0xb4]);
+        assert_eq!(tcp_checksum(&bytes), !0);
+
+        let (header, payload) = Ipv4::from_bytes_mut(&mut bytes).unwrap();
+        assert_eq!(clamp_mss(&header, payload, 1400), Ok(true));
+        assert_eq!(mss(&bytes, 42), 1360);
+        assert_eq!(tcp_checksum(&bytes), !0);
+
+        // already small enough
+        let (header, payload) = Ipv4::from_bytes_mut(&mut bytes).unwrap();
+        assert_eq!(clamp_mss(&header, payload, 1500), Ok(false));
+        assert_eq!(mss(&bytes, 42), 1360);
+    }
+
+    #[test]
+    fn clamp_unaligned_mss() {
+        // NOP, MSS 1460, NOP, NOP, NOP
+        let mut bytes = tcp_syn(&[0x01, 0x02, 0x04, 0x05, 0xb4, 0x01, 0x01, 0x01]);
+        let (header, payload) = Ipv4::from_bytes_mut(&mut bytes).unwrap();
+        assert_eq!(clamp_mss(&header, payload, 1280), Ok(true));
+        assert_eq!(mss(&bytes, 43), 1240);
+        assert_eq!(tcp_checksum(&bytes), !0);
+    }
+
+    #[test]
+    fn clamp_only_syn() {
+        let mut bytes = tcp_syn(&[0x02, 0x04, 0x05, 0xb4]);
+        bytes[33] = 0x10;
+        let (header, payload) = Ipv4::from_bytes_mut(&mut bytes).unwrap();
+        assert_eq!(clamp_mss(&header, payload, 1400), Ok(false));
+        assert_eq!(mss(&bytes, 42), 1460);
+
+        let mut bytes = udp_packet();
+        let (header, payload) = Ipv4::from_bytes_mut(&mut bytes).unwrap();
+        assert_eq!(clamp_mss(&header, payload, 1400), Ok(false));
+
+        let mut bytes = tcp_syn(&[]);
+        let (header, payload) = Ipv4::from_bytes_mut(&mut bytes).unwrap();
+        assert_eq!(
+            clamp_mss(&header, &mut payload[..19], 1400),
+            Err(HeaderTruncated)
+        );
+    }
+
+    #[test]
+    fn clamp_ignores_fragments() {
+        // a later fragment at offset 8 whose data resembles a SYN with MSS 1460
+        let mut bytes = tcp_syn(&[0x02, 0x04, 0x05, 0xb4]);
+        bytes[6..8].copy_from_slice(&[0x00, 0x01]);
+        let before = bytes.clone();
+        let (header, payload) = Ipv4::from_bytes_mut(&mut bytes).unwrap();
+        assert_eq!(clamp_mss(&header, payload, 1400), Ok(false));
+        assert_eq!(bytes, before);
+    }
+
+    #[test]
+    fn conntrack_udp_flow() {
+        let public = Ipv4Addr::new([203, 0, 113, 7]);
+        let mut table = ConntrackTable::<2>::new(public, 61000, 61001, Duration::from_secs(30));
+
+        let mut bytes = udp_packet();
+        let (mut header, payload) = Ipv4::from_bytes_mut(&mut bytes).unwrap();
+        assert_eq!(
+            table.egress(&mut header, payload, Instant::from_millis(1_000)),
+            Ok(true)
+        );
+        assert_eq!(table.len(), 1);
+        assert_eq!(verify_checksum(&bytes[..20]), Ok(()));
+        assert_eq!(udp_checksum(&bytes), !0);
+        let (header, payload) = Ipv4::from_bytes(&bytes).unwrap();
+        assert_eq!(header.src(), public);
+        let (udp, _) = Udp::from_bytes(payload).unwrap();
+        assert_eq!(udp.source_port(), 61000);
+
+        // the reply swaps addresses and ports
+        bytes.copy_within(12..16, 16);
+        bytes[12..16].copy_from_slice(&[8, 8, 8, 8]);
+        bytes[16..20].copy_from_slice(public.as_bytes());
+        bytes.copy_within(20..22, 22);
+        bytes[20..22].copy_from_slice(&53u16.to_be_bytes());
+        bytes[22..24].copy_from_slice(&61000u16.to_be_bytes());
+        bytes[10..12].copy_from_slice(&[0, 0]);
+        let cks = !compute_checksum(&bytes[..20]);
+        bytes[10..12].copy_from_slice(&cks.to_ne_bytes());
+        bytes[26..28].copy_from_slice(&[0, 0]);
+        let cks = !udp_checksum(&bytes);
+        bytes[26..28].copy_from_slice(&cks.to_ne_bytes());
+
+        let (mut header, payload) = Ipv4::from_bytes_mut(&mut bytes).unwrap();
+        assert_eq!(
+            table.ingress(&mut header, payload, Instant::from_millis(2_000)),
+            Ok(true)
+        );
+        assert_eq!(verify_checksum(&bytes[..20]), Ok(()));
+        assert_eq!(udp_checksum(&bytes), !0);
+        let (header, payload) = Ipv4::from_bytes(&bytes).unwrap();
+        assert_eq!(header.src(), Ipv4Addr::new([8, 8, 8, 8]));
+        assert_eq!(header.dst(), Ipv4Addr::new([192, 168, 0, 10]));
+        let (udp, _) = Udp::from_bytes(payload).unwrap();
+        assert_eq!(udp.source_port(), 53);
+        assert_eq!(udp.destination_port(), 5000);
+
+        // once idle past the timeout, replies are no longer translated
+        let mut reply = bytes;
+        reply[16..20].copy_from_slice(public.as_bytes());
+        reply[22..24].copy_from_slice(&61000u16.to_be_bytes());
+        let (mut header, payload) = Ipv4::from_bytes_mut(&mut reply).unwrap();
+        assert_eq!(
+            table.ingress(&mut header, payload, Instant::from_millis(40_000)),
+            Ok(false)
+        );
+        table.expire(Instant::from_millis(40_000));
+        assert!(table.is_empty());
+    }
+
+    #[test]
+    fn conntrack_port_exhaustion() {
+        let public = Ipv4Addr::new([203, 0, 113, 7]);
+        let mut table = ConntrackTable::<4>::new(public, 61000, 61000, Duration::from_secs(30));
+
+        let mut bytes = udp_packet();
+        let (mut header, payload) = Ipv4::from_bytes_mut(&mut bytes).unwrap();
+        assert_eq!(table.egress(&mut header, payload, Instant::EPOCH), Ok(true));
+
+        // a second inside port cannot be given the only outside port
+        let mut bytes = udp_packet();
+        bytes[20..22].copy_from_slice(&5001u16.to_be_bytes());
+        let (mut header, payload) = Ipv4::from_bytes_mut(&mut bytes).unwrap();
+        assert_eq!(
+            table.egress(&mut header, payload, Instant::EPOCH),
+            Ok(false)
+        );
+        assert_eq!(table.len(), 1);
+
+        // but can once the first flow expires
+        let (mut header, payload) = Ipv4::from_bytes_mut(&mut bytes).unwrap();
+        assert_eq!(
+            table.egress(&mut header, payload, Instant::from_millis(31_000)),
+            Ok(true)
+        );
+        assert_eq!(table.len(), 1);
+    }
+
+    #[test]
+    fn conntrack_fragments() {
+        let public = Ipv4Addr::new([203, 0, 113, 7]);
+        let mut table = ConntrackTable::<4>::new(public, 61000, 61001, Duration::from_secs(30));
+
+        // a later fragment at offset 8 whose data would read as port 0xDEAD
+        let mut bytes = udp_packet();
+        bytes[6..8].copy_from_slice(&[0x00, 0x01]);
+        bytes[20..24].copy_from_slice(&[0xDE, 0xAD, 0xBE, 0xEF]);
+        bytes[10..12].copy_from_slice(&[0, 0]);
+        let cks = !compute_checksum(&bytes[..20]);
+        bytes[10..12].copy_from_slice(&cks.to_ne_bytes());
+        let data = bytes[20..].to_vec();
+
+        let (mut header, payload) = Ipv4::from_bytes_mut(&mut bytes).unwrap();
+        assert_eq!(table.egress(&mut header, payload, Instant::EPOCH), Ok(true));
+        assert!(table.is_empty());
+        assert_eq!(verify_checksum(&bytes[..20]), Ok(()));
+        let (header, payload) = Ipv4::from_bytes(&bytes).unwrap();
+        assert_eq!(header.src(), public);
+        assert_eq!(payload, data);
+
+        // track a flow with outside port 61000, then send a fragment to a port its data resembles
+        let mut first = udp_packet();
+        let (mut header, payload) = Ipv4::from_bytes_mut(&mut first).unwrap();
+        assert_eq!(table.egress(&mut header, payload, Instant::EPOCH), Ok(true));
+        bytes[12..16].copy_from_slice(&[8, 8, 8, 8]);
+        bytes[16..20].copy_from_slice(public.as_bytes());
+        bytes[20..24].copy_from_slice(&[0x00, 0x35, 0xEE, 0x48]);
+        let before = bytes;
+        let (mut header, payload) = Ipv4::from_bytes_mut(&mut bytes).unwrap();
+        assert_eq!(
+            table.ingress(&mut header, payload, Instant::EPOCH),
+            Ok(false)
+        );
+        assert_eq!(bytes, before);
+    }
+
+    #[test]
+    fn short_transport_header() {
+        let mut bytes = udp_packet();
+        let (mut header, payload) = Ipv4::from_bytes_mut(&mut bytes).unwrap();
+        assert_eq!(
+            translate_src(&mut header, &mut payload[..4], Ipv4Addr::LOCALHOST, None),
+            Err(HeaderTruncated)
+        );
+    }
+}
@@ -0,0 +1,244 @@
+//! Keep It Simple, Stupid (KISS) framing. [Read more][KISS]
+//!
+//! KISS frames packets between a host and an amateur radio Terminal Node Controller (TNC). Framing
+//! and escaping are the same as [SLIP][super::slip], but each frame begins with a type byte naming
+//! a TNC port in the high nibble and a command in the low nibble. Only data frames carry packets,
+//! typically AX.25 or IP, so a KISS link is [`HardwareType::Opaque`][super::HardwareType::Opaque].
+//!
+//! [KISS]: http://www.ax25.net/kiss.aspx
+
+use super::slip::{SlipDecoder, SlipWriter, SLIP_END, SLIP_ESC, SLIP_ESC_END, SLIP_ESC_ESC};
+
+/// Frame End. Delimits the end (and start) of a frame.
+pub const KISS_FEND: u8 = SLIP_END;
+/// Frame Escape. Escapes an FEND or FESC byte in the frame.
+pub const KISS_FESC: u8 = SLIP_ESC;
+/// Transposed Frame End. Follows an FESC in place of an FEND byte.
+pub const KISS_TFEND: u8 = SLIP_ESC_END;
+/// Transposed Frame Escape. Follows an FESC in place of an FESC byte.
+pub const KISS_TFESC: u8 = SLIP_ESC_ESC;
+
+/// The command of a frame carrying a packet.
+const DATA_FRAME: u8 = 0x00;
+
+/// Encodes `packet` as a KISS data frame for TNC `port` into `buf`, returning the length of the
+/// frame or [`None`] if `buf` is too small or `port` is greater than 15.
+///
+/// At most `2 * packet.len() + 4` bytes are written.
+pub fn kiss_encode(port: u8, packet: &[u8], buf: &mut [u8]) -> Option<usize> {
+    if port > 0x0F {
+        return None;
+    }
+
+    let mut writer = SlipWriter::new(buf);
+    writer.put(KISS_FEND)?;
+    writer.put_escaped(&[port << 4 | DATA_FRAME])?;
+    writer.put_escaped(packet)?;
+    writer.put(KISS_FEND)?;
+    Some(writer.len)
+}
+
+/// A KISS decoder fed one byte at a time from a byte stream.
+///
+/// Frames with a command other than data are skipped, along with any frame dropped by the
+/// underlying [`SlipDecoder`].
+#[derive(Debug, Default, Clone)]
+pub struct KissDecoder(SlipDecoder);
+
+impl KissDecoder {
+    /// Constructs a decoder expecting the start of a frame.
+    pub const fn new() -> Self {
+        KissDecoder(SlipDecoder::new())
+    }
+
+    /// Decodes `byte` into `buf`. Returns the TNC port and the length of the packet at the start
+    /// of `buf` once an FEND byte completes a data frame. `buf` must have room for the type byte
+    /// in addition to the packet.
+    ///
+    /// The same `buf` must be passed until a packet is returned.
+    pub fn push(&mut self, byte: u8, buf: &mut [u8]) -> Option<(u8, usize)> {
+        let len = self.0.push(byte, buf)?;
+        let ty = buf[0];
+        if ty & 0x0F != DATA_FRAME || len == 1 {
+            return None;
+        }
+
+        buf.copy_within(1..len, 0);
+        Some((ty >> 4, len - 1))
+    }
+}
+
+#[cfg(all(feature = "netdev", unix))]
+pub use kiss_netdev::KissNetDev;
+
+#[cfg(all(feature = "netdev", unix))]
+mod kiss_netdev {
+    use std::cell::RefCell;
+    use std::io::{self, Read, Write};
+    use std::os::fd::AsFd;
+    use std::time::Duration;
+
+    use super::{kiss_encode, KissDecoder};
+    use crate::netdev::stream::FrameReader;
+    use crate::netdev::{sys, Event, HardwareType, NetDev};
+
+    /// A [`NetDev`] sending and receiving KISS data frames on one TNC port over a byte stream, such
+    /// as a serial port. See [`kiss`][super].
+    #[derive(Debug)]
+    pub struct KissNetDev<S> {
+        stream: RefCell<S>,
+        rx: RefCell<(KissDecoder, FrameReader)>,
+        port: u8,
+        mtu: usize,
+    }
+
+    impl<S: Read + Write + AsFd> KissNetDev<S> {
+        /// Creates a device sending and receiving packets of up to `mtu` bytes on TNC `port` of
+        /// `stream`. Data frames for other ports are discarded.
+        ///
+        /// # Panics
+        ///
+        /// Panics if `port` is greater than 15.
+        pub fn new(stream: S, port: u8, mtu: usize) -> Self {
+            assert!(port <= 0x0F, "KISS port must fit in 4 bits");
+            KissNetDev {
+                stream: RefCell::new(stream),
+                rx: RefCell::new((KissDecoder::new(), FrameReader::new(mtu + 1))),
+                port,
+                mtu,
+            }
+        }
+
+        /// Consumes the device, returning the underlying stream.
+        pub fn into_inner(self) -> S {
+            self.stream.into_inner()
+        }
+    }
+
+    impl<S: Read + Write + AsFd> NetDev for KissNetDev<S> {
+        type Error = io::Error;
+
+        fn send(&self, buf: &[u8]) -> io::Result<usize> {
+            if buf.len() > self.mtu {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "packet larger than mtu",
+                ));
+            }
+
+            let mut frame = vec![0; 2 * buf.len() + 4];
+            let len = kiss_encode(self.port, buf, &mut frame).expect("frame sized for worst case");
+            let mut stream = self.stream.borrow_mut();
+            stream.write_all(&frame[..len])?;
+            stream.flush()?;
+
+            Ok(buf.len())
+        }
+
+        fn recv(&self, buf: &mut [u8]) -> io::Result<usize> {
+            let (decoder, reader) = &mut *self.rx.borrow_mut();
+            reader.read(
+                &mut *self.stream.borrow_mut(),
+                buf,
+                |byte, frame| match decoder.push(byte, frame) {
+                    Some((port, len)) if port == self.port => Some(len),
+                    _ => None,
+                },
+            )
+        }
+
+        fn poll(&self, interest: Event, timeout: Option<Duration>) -> io::Result<Event> {
+            if interest.is_readable() && self.rx.borrow().1.is_buffered() {
+                return Ok(Event::READABLE);
+            }
+            sys::poll(self.stream.borrow().as_fd(), interest, timeout)
+        }
+
+        #[inline]
+        fn mtu(&self) -> usize {
+            self.mtu
+        }
+
+        #[inline]
+        fn hw_type(&self) -> HardwareType {
+            HardwareType::Opaque
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode() {
+        let packet = [0x45, KISS_FEND, KISS_FESC];
+        let mut buf = [0; 10];
+        let len = kiss_encode(12, &packet, &mut buf).unwrap();
+        assert_eq!(
+            &buf[..len],
+            &[
+                KISS_FEND, KISS_FESC, KISS_TFEND, 0x45, KISS_FESC, KISS_TFEND, KISS_FESC,
+                KISS_TFESC, KISS_FEND
+            ]
+        );
+
+        assert_eq!(kiss_encode(16, &packet, &mut buf), None);
+        assert_eq!(kiss_encode(0, &packet, &mut buf[..6]), None);
+    }
+
+    #[test]
+    fn round_trip() {
+        let packet = [0x45, KISS_FEND, 0x01, KISS_FESC];
+        let mut frame = [0; 12];
+        let len = kiss_encode(1, &packet, &mut frame).unwrap();
+
+        let mut decoder = KissDecoder::new();
+        let mut buf = [0; 5];
+        let mut decoded = frame[..len]
+            .iter()
+            .filter_map(|&byte| decoder.push(byte, &mut buf));
+        assert_eq!(decoded.next(), Some((1, 4)));
+        assert_eq!(decoded.next(), None);
+        assert_eq!(&buf[..4], &packet);
+    }
+
+    #[test]
+    fn skip_commands() {
+        let mut decoder = KissDecoder::new();
+        let mut buf = [0; 4];
+        let txdelay = [KISS_FEND, 0x01, 0x32, KISS_FEND];
+        let empty = [0x00, KISS_FEND];
+        let data = [0x20, 0xAA, 0xBB, KISS_FEND];
+        let stream = [&txdelay[..], &empty[..], &data[..]].concat();
+        let decoded: Vec<_> = stream
+            .iter()
+            .filter_map(|&byte| decoder.push(byte, &mut buf))
+            .collect();
+        assert_eq!(decoded, [(2, 2)]);
+        assert_eq!(&buf[..2], &[0xAA, 0xBB]);
+    }
+
+    #[cfg(all(feature = "netdev", unix))]
+    #[test]
+    fn netdev_round_trip() {
+        use crate::netdev::{HardwareType, NetDev};
+        use std::io::Write;
+        use std::os::unix::net::UnixStream;
+
+        let (a, mut b) = UnixStream::pair().unwrap();
+        let a = KissNetDev::new(a, 0, 256);
+
+        // a frame for another port is discarded
+        let mut frame = [0; 16];
+        let len = kiss_encode(3, &[0x01], &mut frame).unwrap();
+        b.write_all(&frame[..len]).unwrap();
+        let len = kiss_encode(0, &[KISS_FEND, KISS_FESC], &mut frame).unwrap();
+        b.write_all(&frame[..len]).unwrap();
+
+        let mut buf = [0; 256];
+        assert_eq!(a.recv(&mut buf).unwrap(), 2);
+        assert_eq!(&buf[..2], &[KISS_FEND, KISS_FESC]);
+        assert_eq!(a.hw_type(), HardwareType::Opaque);
+    }
+}
@@ -12,11 +12,15 @@
 //!
 //! - [`TunTapInterface`] - A [`NetDev`] for the [TUN/TAP][tuntap] device driver.
 //! - [`PacketSocket`] - A [`NetDev`] for the [packet] socket family.
+//! - [`SlipNetDev`] - A [`NetDev`] for IP packets [SLIP][slip] framed on a byte stream.
+//! - [`KissNetDev`] - A [`NetDev`] for packets [KISS][kiss] framed to an amateur radio TNC.
 //!
 //! Feel free to use these [`NetDev`]s as references for your own implementations.
 //!
 //! [tuntap]: https://www.kernel.org/doc/html/latest/networking/tuntap.html
 //! [packet]: https://man7.org/linux/man-pages/man7/packet.7.html
+//! [slip]: https://tools.ietf.org/html/rfc1055
+//! [kiss]: http://www.ax25.net/kiss.aspx
 
 #[cfg(all(feature = "netdev", unix))]
 mod packet_socket;
@@ -28,12 +32,24 @@ pub use packet_socket::PacketSocket;
 #[cfg(all(feature = "netdev", any(target_os = "linux", target_os = "android")))]
 pub use tuntap_interface::TunTapInterface;
 #[cfg(all(feature = "netdev", unix))]
+mod stream;
+#[cfg(all(feature = "netdev", unix))]
 mod sys;
 
+pub mod kiss;
+pub mod slip;
+
+#[cfg(all(feature = "netdev", unix))]
+pub use kiss::KissNetDev;
+#[cfg(all(feature = "netdev", unix))]
+pub use slip::SlipNetDev;
+
 use core::fmt;
 use core::ops;
 use core::time::Duration;
 
+use crate::header::link::EtherAddr;
+
 /// Interface for network hardware capable of sending and receiving data on a given [`HardwareType`].
 pub trait NetDev {
     type Error;
@@ -63,11 +79,38 @@ pub trait NetDev {
     fn mtu(&self) -> usize;
     /// Returns [`HardwareType`] device operates on.
     fn hw_type(&self) -> HardwareType;
+    /// Returns the name of the interface the device is attached to, such as `"tap0"`. Defaults to
+    /// an empty name.
+    #[inline]
+    fn name(&self) -> &str {
+        ""
+    }
+    /// Asks the device to receive frames sent to the multicast address `addr`, such as the address
+    /// derived from a joined IP multicast group. Returns `Ok(false)` if the device does not manage a
+    /// multicast filter, which is the default.
+    #[inline]
+    fn add_multicast(&self, addr: EtherAddr) -> Result<bool, Self::Error> {
+        let _ = addr;
+        Ok(false)
+    }
+    /// Undoes [`add_multicast`][NetDev::add_multicast]. Returns `Ok(false)` if the device does not
+    /// manage a multicast filter, which is the default.
+    #[inline]
+    fn remove_multicast(&self, addr: EtherAddr) -> Result<bool, Self::Error> {
+        let _ = addr;
+        Ok(false)
+    }
+    /// Returns the [`Capabilities`] of the device. Defaults to none.
+    #[inline]
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new()
+    }
 }
 
 /// The hardware that a [`NetDev`] operates on. Indicates which link layer header will be
 /// present in calls to [`send`][NetDev::send] and [`recv`][NetDev::recv], if any.
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
+#[non_exhaustive]
 pub enum HardwareType {
     /// Sends and receives packets without any link layer header.  
     Opaque,
@@ -75,6 +118,143 @@ pub enum HardwareType {
     EthernetII,
     /// Sends and receives [`Ieee802154`][crate::header::link::Ieee802154] frames.
     Ieee802154,
+    /// Loops sent packets back without any link layer header, like [`Opaque`][Self::Opaque].
+    /// Such a device should also report [`Capabilities::LOOPBACK`].
+    Loopback,
+}
+
+/// [`NetDev`] flags indicating work the device does on behalf of the stack.
+///
+/// A device reports no capabilities unless it opts in. Capabilities combine with `|`, such as
+/// `Capabilities::TSO | Capabilities::TX_PADDING`.
+#[derive(Copy, PartialEq, Eq, Clone, PartialOrd, Ord)]
+pub struct Capabilities(u8);
+
+// bits must be one-hot
+const RX_CHECKSUM_OFFLOAD: u8 = 1 << 0;
+const TSO: u8 = 1 << 1;
+const GSO: u8 = 1 << 2;
+const MULTICAST_FILTER: u8 = 1 << 3;
+const TX_PADDING: u8 = 1 << 4;
+const LOOPBACK: u8 = 1 << 5;
+const TX_CHECKSUM_OFFLOAD: u8 = 1 << 6;
+
+impl Capabilities {
+    /// Received packets have had their checksums verified by the device, so
+    /// [`Ipv4::verify`][crate::header::internet::Ipv4::verify] and the other `verify` methods skip
+    /// verifying them again.
+    pub const RX_CHECKSUM_OFFLOAD: Capabilities = Capabilities(RX_CHECKSUM_OFFLOAD);
+    /// The device supports TCP segmentation offload, splitting a TCP segment larger than the MTU
+    /// into segments that fit.
+    pub const TSO: Capabilities = Capabilities(TSO);
+    /// The device supports generic segmentation offload, splitting any oversized transport
+    /// payload, such as a UDP datagram, into packets that fit the MTU.
+    pub const GSO: Capabilities = Capabilities(GSO);
+    /// The device supports filtering received multicast frames by the addresses added with
+    /// [`add_multicast`][NetDev::add_multicast].
+    pub const MULTICAST_FILTER: Capabilities = Capabilities(MULTICAST_FILTER);
+    /// The device supports padding frames shorter than the minimum frame size of the link, such
+    /// as 60 bytes for Ethernet.
+    pub const TX_PADDING: Capabilities = Capabilities(TX_PADDING);
+    /// The device loops sent packets back to the stack. Checksums computed on transmit may be left
+    /// as zero, so [`Ipv4::verify`][crate::header::internet::Ipv4::verify] and the other `verify`
+    /// methods skip verifying received packets.
+    pub const LOOPBACK: Capabilities = Capabilities(LOOPBACK);
+    /// The device computes IPv4, UDP and TCP checksums of sent packets. A
+    /// [`PacketTemplate`][crate::header::PacketTemplate] given these capabilities leaves the header
+    /// checksum zero and seeds the UDP checksum with the pseudo-header sum only.
+    pub const TX_CHECKSUM_OFFLOAD: Capabilities = Capabilities(TX_CHECKSUM_OFFLOAD);
+
+    /// Constructs [`Capabilities`] without any capability.
+    #[inline]
+    pub const fn new() -> Self {
+        Self(0)
+    }
+
+    /// Returns `true` if the device verifies checksums of received packets.
+    #[inline]
+    pub const fn rx_checksum_offload(&self) -> bool {
+        (self.0 & RX_CHECKSUM_OFFLOAD) != 0
+    }
+
+    /// Returns `true` if the device segments oversized TCP segments.
+    #[inline]
+    pub const fn tso(&self) -> bool {
+        (self.0 & TSO) != 0
+    }
+
+    /// Returns `true` if the device segments any oversized transport payload.
+    #[inline]
+    pub const fn gso(&self) -> bool {
+        (self.0 & GSO) != 0
+    }
+
+    /// Returns `true` if the device filters received multicast frames.
+    #[inline]
+    pub const fn multicast_filter(&self) -> bool {
+        (self.0 & MULTICAST_FILTER) != 0
+    }
+
+    /// Returns `true` if the device pads short frames.
+    #[inline]
+    pub const fn tx_padding(&self) -> bool {
+        (self.0 & TX_PADDING) != 0
+    }
+
+    /// Returns `true` if the device is a loopback.
+    #[inline]
+    pub const fn loopback(&self) -> bool {
+        (self.0 & LOOPBACK) != 0
+    }
+
+    // Returns `true` if checksums of received packets need not be verified.
+    #[inline]
+    pub(crate) const fn skips_rx_checksum(&self) -> bool {
+        (self.0 & (RX_CHECKSUM_OFFLOAD | LOOPBACK)) != 0
+    }
+
+    /// Returns `true` if the device computes checksums of sent packets.
+    #[inline]
+    pub const fn tx_checksum_offload(&self) -> bool {
+        (self.0 & TX_CHECKSUM_OFFLOAD) != 0
+    }
+}
+
+impl Default for Capabilities {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ops::BitOr for Capabilities {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+impl ops::BitOrAssign for Capabilities {
+    #[inline]
+    fn bitor_assign(&mut self, other: Self) {
+        self.0 = (*self | other).0;
+    }
+}
+
+impl fmt::Debug for Capabilities {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Capabilities")
+            .field("rx_checksum_offload", &self.rx_checksum_offload())
+            .field("tso", &self.tso())
+            .field("gso", &self.gso())
+            .field("multicast_filter", &self.multicast_filter())
+            .field("tx_padding", &self.tx_padding())
+            .field("loopback", &self.loopback())
+            .field("tx_checksum_offload", &self.tx_checksum_offload())
+            .finish()
+    }
 }
 
 /// A [`NetDev`] flag indicating readiness to perform I/O.
@@ -109,6 +289,13 @@ impl Event {
     }
 }
 
+impl Default for Event {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ops::BitOr for Event {
     type Output = Self;
 
@@ -133,3 +320,70 @@ impl fmt::Debug for Event {
             .finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Conservative;
+
+    impl NetDev for Conservative {
+        type Error = ();
+
+        fn send(&self, _buf: &[u8]) -> Result<usize, ()> {
+            Err(())
+        }
+
+        fn recv(&self, _buf: &mut [u8]) -> Result<usize, ()> {
+            Err(())
+        }
+
+        fn poll(&self, _interest: Event, _timeout: Option<Duration>) -> Result<Event, ()> {
+            Ok(Event::new())
+        }
+
+        fn mtu(&self) -> usize {
+            1500
+        }
+
+        fn hw_type(&self) -> HardwareType {
+            HardwareType::EthernetII
+        }
+    }
+
+    #[test]
+    fn default_capabilities() {
+        let caps = Conservative.capabilities();
+        assert_eq!(caps, Capabilities::default());
+        assert!(!caps.rx_checksum_offload());
+        assert!(!caps.tso());
+        assert!(!caps.gso());
+        assert!(!caps.multicast_filter());
+        assert!(!caps.tx_padding());
+        assert!(!caps.loopback());
+        assert!(!caps.tx_checksum_offload());
+    }
+
+    #[test]
+    fn default_multicast() {
+        let addr = EtherAddr::new([0x01, 0x00, 0x5e, 0x00, 0x00, 0x01]);
+        assert_eq!(Conservative.add_multicast(addr), Ok(false));
+        assert_eq!(Conservative.remove_multicast(addr), Ok(false));
+    }
+
+    #[test]
+    fn default_name() {
+        assert_eq!(Conservative.name(), "");
+    }
+
+    #[test]
+    fn combine_capabilities() {
+        let mut caps = Capabilities::TSO | Capabilities::GSO;
+        caps |= Capabilities::TX_PADDING;
+        assert!(caps.tso() && caps.gso() && caps.tx_padding());
+        assert!(!caps.rx_checksum_offload() && !caps.multicast_filter() && !caps.loopback());
+
+        let caps = Capabilities::LOOPBACK;
+        assert!(caps.loopback() && !caps.rx_checksum_offload());
+    }
+}
@@ -8,8 +8,9 @@ use rustix::net::{
     eth, recv, send, socket_with, AddressFamily, RecvFlags, SendFlags, SocketFlags, SocketType,
 };
 
-use super::{sys, Event};
+use super::{sys, Capabilities, Event};
 use super::{HardwareType, NetDev};
+use crate::header::link::EtherAddr;
 
 /// A socket of the AF_PACKET family. [Read more][packet]
 ///
@@ -17,6 +18,8 @@ use super::{HardwareType, NetDev};
 #[derive(Debug)]
 pub struct PacketSocket {
     fd: OwnedFd,
+    name: String,
+    ifindex: i32,
     mtu: usize,
     hw_type: HardwareType,
 }
@@ -27,7 +30,7 @@ impl PacketSocket {
     /// Requires superuser privileges or the `CAP_NET_RAW` capability.
     pub fn bind(name: &str, hw_type: HardwareType) -> io::Result<Self> {
         let (type_, protocol) = match hw_type {
-            HardwareType::Opaque => (SocketType::DGRAM, eth::ALL),
+            HardwareType::Opaque | HardwareType::Loopback => (SocketType::DGRAM, eth::ALL),
             HardwareType::EthernetII => (SocketType::RAW, eth::ALL),
             HardwareType::Ieee802154 => (SocketType::RAW, eth::IEEE802154),
         };
@@ -41,9 +44,16 @@ impl PacketSocket {
         let ifreq_name = sys::ifreq_name(name);
         sys::bind_interface(&fd, protocol, ifreq_name)?;
 
+        let ifindex = sys::ioctl_siocgifindex(&fd, ifreq_name)?;
         let mtu = sys::ioctl_siocgifmtu(&fd, ifreq_name)?;
 
-        Ok(PacketSocket { fd, mtu, hw_type })
+        Ok(PacketSocket {
+            fd,
+            name: name.into(),
+            ifindex,
+            mtu,
+            hw_type,
+        })
     }
 }
 
@@ -64,6 +74,24 @@ impl NetDev for PacketSocket {
         sys::poll(&self.fd, interest, timeout)
     }
 
+    fn add_multicast(&self, addr: EtherAddr) -> io::Result<bool> {
+        sys::packet_membership(&self.fd, self.ifindex, addr.into(), true)?;
+        Ok(true)
+    }
+
+    fn remove_multicast(&self, addr: EtherAddr) -> io::Result<bool> {
+        sys::packet_membership(&self.fd, self.ifindex, addr.into(), false)?;
+        Ok(true)
+    }
+
+    #[inline]
+    fn capabilities(&self) -> Capabilities {
+        match self.hw_type {
+            HardwareType::Loopback => Capabilities::MULTICAST_FILTER | Capabilities::LOOPBACK,
+            _ => Capabilities::MULTICAST_FILTER,
+        }
+    }
+
     #[inline]
     fn mtu(&self) -> usize {
         self.mtu
@@ -73,4 +101,41 @@ impl NetDev for PacketSocket {
     fn hw_type(&self) -> HardwareType {
         self.hw_type
     }
+
+    #[inline]
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[ignore = "requires CAP_NET_RAW"]
+    fn add_multicast() {
+        let socket = PacketSocket::bind("lo", HardwareType::EthernetII).unwrap();
+        let addr = EtherAddr::new([0x01, 0x00, 0x5e, 0x00, 0x00, 0xfb]);
+        assert!(socket.add_multicast(addr).unwrap());
+        assert!(socket.remove_multicast(addr).unwrap());
+    }
+
+    #[test]
+    #[ignore = "requires CAP_NET_RAW"]
+    fn loopback_capabilities() {
+        let socket = PacketSocket::bind("lo", HardwareType::Loopback).unwrap();
+        assert_eq!(socket.hw_type(), HardwareType::Loopback);
+        assert!(socket.capabilities().loopback());
+
+        let socket = PacketSocket::bind("lo", HardwareType::EthernetII).unwrap();
+        assert!(!socket.capabilities().loopback());
+    }
+
+    #[test]
+    #[ignore = "requires CAP_NET_RAW"]
+    fn name() {
+        let socket = PacketSocket::bind("lo", HardwareType::EthernetII).unwrap();
+        assert_eq!(socket.name(), "lo");
+    }
 }
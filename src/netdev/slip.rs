@@ -0,0 +1,311 @@
+//! Serial Line IP (SLIP) framing. [Read more][RFC 1055]
+//!
+//! SLIP delimits IP packets on a byte stream, such as a serial line, with an END byte. END and ESC
+//! bytes inside a packet are escaped. There is no addressing, error detection, or link layer
+//! header, so a SLIP link is [`HardwareType::Opaque`][super::HardwareType::Opaque].
+//!
+//! [RFC 1055]: https://tools.ietf.org/html/rfc1055
+
+/// Delimits the end (and start) of a frame.
+pub const SLIP_END: u8 = 0xC0;
+/// Escapes an END or ESC byte in the packet.
+pub const SLIP_ESC: u8 = 0xDB;
+/// Follows an ESC in place of an END byte.
+pub const SLIP_ESC_END: u8 = 0xDC;
+/// Follows an ESC in place of an ESC byte.
+pub const SLIP_ESC_ESC: u8 = 0xDD;
+
+/// Encodes `packet` as a SLIP frame into `buf`, returning the length of the frame or [`None`] if
+/// `buf` is too small.
+///
+/// The frame both begins and ends with an END byte, flushing any line noise received by the peer
+/// before the frame. At most `2 * packet.len() + 2` bytes are written.
+pub fn slip_encode(packet: &[u8], buf: &mut [u8]) -> Option<usize> {
+    let mut writer = SlipWriter::new(buf);
+    writer.put(SLIP_END)?;
+    writer.put_escaped(packet)?;
+    writer.put(SLIP_END)?;
+    Some(writer.len)
+}
+
+/// Writes bytes into a buffer, escaping those with special meaning to SLIP.
+pub(super) struct SlipWriter<'a> {
+    buf: &'a mut [u8],
+    pub(super) len: usize,
+}
+
+impl<'a> SlipWriter<'a> {
+    pub(super) fn new(buf: &'a mut [u8]) -> Self {
+        SlipWriter { buf, len: 0 }
+    }
+
+    pub(super) fn put(&mut self, byte: u8) -> Option<()> {
+        *self.buf.get_mut(self.len)? = byte;
+        self.len += 1;
+        Some(())
+    }
+
+    pub(super) fn put_escaped(&mut self, bytes: &[u8]) -> Option<()> {
+        for &byte in bytes {
+            match byte {
+                SLIP_END => {
+                    self.put(SLIP_ESC)?;
+                    self.put(SLIP_ESC_END)?;
+                }
+                SLIP_ESC => {
+                    self.put(SLIP_ESC)?;
+                    self.put(SLIP_ESC_ESC)?;
+                }
+                byte => self.put(byte)?,
+            }
+        }
+        Some(())
+    }
+}
+
+/// A SLIP decoder fed one byte at a time from a byte stream.
+///
+/// Empty frames between back to back END bytes are skipped. A frame that does not fit in the
+/// provided buffer is dropped, as is an ESC followed by anything but ESC_END or ESC_ESC.
+#[derive(Debug, Default, Clone)]
+pub struct SlipDecoder {
+    len: usize,
+    escaped: bool,
+    dropped: bool,
+}
+
+impl SlipDecoder {
+    /// Constructs a decoder expecting the start of a frame.
+    pub const fn new() -> Self {
+        SlipDecoder {
+            len: 0,
+            escaped: false,
+            dropped: false,
+        }
+    }
+
+    /// Decodes `byte` into `buf`. Returns the length of the packet at the start of `buf` once an
+    /// END byte completes a frame.
+    ///
+    /// The same `buf` must be passed until a packet is returned.
+    pub fn push(&mut self, byte: u8, buf: &mut [u8]) -> Option<usize> {
+        let byte = match (self.escaped, byte) {
+            (false, SLIP_END) => return self.finish(),
+            (false, SLIP_ESC) => {
+                self.escaped = true;
+                return None;
+            }
+            (false, byte) => byte,
+            (true, SLIP_ESC_END) => SLIP_END,
+            (true, SLIP_ESC_ESC) => SLIP_ESC,
+            (true, SLIP_END) => {
+                // protocol violation, but END still terminates the frame
+                self.dropped = true;
+                return self.finish();
+            }
+            (true, _) => {
+                self.escaped = false;
+                self.dropped = true;
+                return None;
+            }
+        };
+        self.escaped = false;
+
+        match buf.get_mut(self.len) {
+            Some(slot) => {
+                *slot = byte;
+                self.len += 1;
+            }
+            None => self.dropped = true,
+        }
+        None
+    }
+
+    fn finish(&mut self) -> Option<usize> {
+        let len = self.len;
+        let dropped = self.dropped;
+        *self = SlipDecoder::new();
+        (len != 0 && !dropped).then_some(len)
+    }
+}
+
+#[cfg(all(feature = "netdev", unix))]
+pub use slip_netdev::SlipNetDev;
+
+#[cfg(all(feature = "netdev", unix))]
+mod slip_netdev {
+    use std::cell::RefCell;
+    use std::io::{self, Read, Write};
+    use std::os::fd::AsFd;
+    use std::time::Duration;
+
+    use super::{slip_encode, SlipDecoder};
+    use crate::netdev::stream::FrameReader;
+    use crate::netdev::{sys, Event, HardwareType, NetDev};
+
+    /// A [`NetDev`] sending and receiving SLIP framed IP packets over a byte stream, such as a
+    /// serial port. See [`slip`][super].
+    #[derive(Debug)]
+    pub struct SlipNetDev<S> {
+        stream: RefCell<S>,
+        rx: RefCell<(SlipDecoder, FrameReader)>,
+        mtu: usize,
+    }
+
+    impl<S: Read + Write + AsFd> SlipNetDev<S> {
+        /// Creates a device sending and receiving IP packets of up to `mtu` bytes on `stream`.
+        /// The traditional SLIP MTU is 1006 bytes.
+        pub fn new(stream: S, mtu: usize) -> Self {
+            SlipNetDev {
+                stream: RefCell::new(stream),
+                rx: RefCell::new((SlipDecoder::new(), FrameReader::new(mtu))),
+                mtu,
+            }
+        }
+
+        /// Consumes the device, returning the underlying stream.
+        pub fn into_inner(self) -> S {
+            self.stream.into_inner()
+        }
+    }
+
+    impl<S: Read + Write + AsFd> NetDev for SlipNetDev<S> {
+        type Error = io::Error;
+
+        fn send(&self, buf: &[u8]) -> io::Result<usize> {
+            if buf.len() > self.mtu {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "packet larger than mtu",
+                ));
+            }
+
+            let mut frame = vec![0; 2 * buf.len() + 2];
+            let len = slip_encode(buf, &mut frame).expect("frame sized for worst case");
+            let mut stream = self.stream.borrow_mut();
+            stream.write_all(&frame[..len])?;
+            stream.flush()?;
+
+            Ok(buf.len())
+        }
+
+        fn recv(&self, buf: &mut [u8]) -> io::Result<usize> {
+            let (decoder, reader) = &mut *self.rx.borrow_mut();
+            reader.read(&mut *self.stream.borrow_mut(), buf, |byte, frame| {
+                decoder.push(byte, frame)
+            })
+        }
+
+        fn poll(&self, interest: Event, timeout: Option<Duration>) -> io::Result<Event> {
+            if interest.is_readable() && self.rx.borrow().1.is_buffered() {
+                return Ok(Event::READABLE);
+            }
+            sys::poll(self.stream.borrow().as_fd(), interest, timeout)
+        }
+
+        #[inline]
+        fn mtu(&self) -> usize {
+            self.mtu
+        }
+
+        #[inline]
+        fn hw_type(&self) -> HardwareType {
+            HardwareType::Opaque
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode() {
+        let packet = [0x45, SLIP_END, 0x01, SLIP_ESC, 0x02];
+        let mut buf = [0; 12];
+        let len = slip_encode(&packet, &mut buf).unwrap();
+        assert_eq!(
+            &buf[..len],
+            &[
+                SLIP_END,
+                0x45,
+                SLIP_ESC,
+                SLIP_ESC_END,
+                0x01,
+                SLIP_ESC,
+                SLIP_ESC_ESC,
+                0x02,
+                SLIP_END
+            ]
+        );
+
+        assert_eq!(slip_encode(&packet, &mut buf[..8]), None);
+    }
+
+    #[test]
+    fn decode() {
+        let packet = [0x45, SLIP_END, 0x01, SLIP_ESC, 0x02];
+        let mut frame = [0; 12];
+        let len = slip_encode(&packet, &mut frame).unwrap();
+
+        let mut decoder = SlipDecoder::new();
+        let mut buf = [0; 5];
+        let mut decoded = frame[..len]
+            .iter()
+            .filter_map(|&byte| decoder.push(byte, &mut buf));
+        assert_eq!(decoded.next(), Some(5));
+        assert_eq!(decoded.next(), None);
+        assert_eq!(buf, packet);
+    }
+
+    #[test]
+    fn decode_drops_bad_frames() {
+        let mut decoder = SlipDecoder::new();
+        let mut buf = [0; 2];
+        let stream = [
+            // too large
+            SLIP_END,
+            0x01,
+            0x02,
+            0x03,
+            SLIP_END,
+            // bad escape
+            0x01,
+            SLIP_ESC,
+            0x01,
+            SLIP_END,
+            // ok
+            0x04,
+            SLIP_ESC,
+            SLIP_ESC_ESC,
+            SLIP_END,
+        ];
+        let decoded: Vec<_> = stream
+            .iter()
+            .filter_map(|&byte| decoder.push(byte, &mut buf))
+            .collect();
+        assert_eq!(decoded, [2]);
+        assert_eq!(buf, [0x04, SLIP_ESC]);
+    }
+
+    #[cfg(all(feature = "netdev", unix))]
+    #[test]
+    fn netdev_round_trip() {
+        use crate::netdev::{Event, HardwareType, NetDev};
+        use std::os::unix::net::UnixStream;
+
+        let (a, b) = UnixStream::pair().unwrap();
+        let (a, b) = (SlipNetDev::new(a, 1006), SlipNetDev::new(b, 1006));
+
+        let packet = [0x45, SLIP_END, SLIP_ESC, 0x00];
+        assert_eq!(a.send(&packet).unwrap(), 4);
+        assert_eq!(a.send(&packet[..1]).unwrap(), 1);
+
+        let mut buf = [0; 1006];
+        assert_eq!(b.recv(&mut buf).unwrap(), 4);
+        assert_eq!(&buf[..4], &packet);
+        assert!(b.poll(Event::READABLE, None).unwrap().is_readable());
+        assert_eq!(b.recv(&mut buf).unwrap(), 1);
+        assert_eq!(b.hw_type(), HardwareType::Opaque);
+    }
+}
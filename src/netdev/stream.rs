@@ -0,0 +1,58 @@
+use std::io::{self, Read};
+
+/// Reads frames from a byte stream, buffering bytes read past the end of a frame for the next.
+#[derive(Debug)]
+pub struct FrameReader {
+    frame: Vec<u8>,
+    chunk: [u8; 256],
+    start: usize,
+    end: usize,
+}
+
+impl FrameReader {
+    /// Creates a reader of frames decoding to at most `frame_len` bytes.
+    pub fn new(frame_len: usize) -> Self {
+        FrameReader {
+            frame: vec![0; frame_len],
+            chunk: [0; 256],
+            start: 0,
+            end: 0,
+        }
+    }
+
+    /// Returns `true` if bytes have been read from the stream, but not yet decoded.
+    pub fn is_buffered(&self) -> bool {
+        self.start < self.end
+    }
+
+    /// Feeds bytes from `stream` to `decode` until it completes a frame, copying the frame into
+    /// `buf`.
+    pub fn read<S: Read>(
+        &mut self,
+        stream: &mut S,
+        buf: &mut [u8],
+        mut decode: impl FnMut(u8, &mut [u8]) -> Option<usize>,
+    ) -> io::Result<usize> {
+        loop {
+            if !self.is_buffered() {
+                self.end = stream.read(&mut self.chunk)?;
+                self.start = 0;
+                if self.end == 0 {
+                    return Err(io::ErrorKind::UnexpectedEof.into());
+                }
+            }
+
+            while self.is_buffered() {
+                let byte = self.chunk[self.start];
+                self.start += 1;
+                if let Some(len) = decode(byte, &mut self.frame) {
+                    let packet = buf.get_mut(..len).ok_or_else(|| {
+                        io::Error::new(io::ErrorKind::InvalidInput, "buffer too small")
+                    })?;
+                    packet.copy_from_slice(&self.frame[..len]);
+                    return Ok(len);
+                }
+            }
+        }
+    }
+}
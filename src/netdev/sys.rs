@@ -6,8 +6,8 @@ use std::os::raw::{c_char, c_int, c_short, c_ushort};
 use std::time::Duration;
 
 use libc::{
-    __c_anonymous_ifr_ifru, bind, ifreq, sockaddr, sockaddr_ll, socklen_t, IFF_NO_PI, IFF_TAP,
-    IFF_TUN, IF_NAMESIZE,
+    __c_anonymous_ifr_ifru, bind, ifreq, setsockopt, sockaddr, sockaddr_ll, socklen_t, AF_UNSPEC,
+    ARPHRD_ETHER, IFF_NO_PI, IFF_TAP, IFF_TUN, IF_NAMESIZE, SOL_PACKET,
 };
 use rustix::{
     event::{PollFd, PollFlags},
@@ -25,6 +25,29 @@ type TUNSETIFF = WriteOpcode<b'T', 202, c_int>;
 type SIOCGIFMTU = BadOpcode<0x8921>;
 // https://github.com/torvalds/linux/blob/4fbbed7872677b0a28ba8237169968171a61efbd/include/uapi/linux/sockios.h#L85
 type SIOCGIFINDEX = BadOpcode<0x8933>;
+// https://github.com/torvalds/linux/blob/4fbbed7872677b0a28ba8237169968171a61efbd/include/uapi/linux/sockios.h#L76
+type SIOCSIFHWADDR = BadOpcode<0x8924>;
+// https://github.com/torvalds/linux/blob/4fbbed7872677b0a28ba8237169968171a61efbd/include/uapi/linux/sockios.h#L81
+type SIOCGIFHWADDR = BadOpcode<0x8927>;
+// https://github.com/torvalds/linux/blob/4fbbed7872677b0a28ba8237169968171a61efbd/include/uapi/linux/sockios.h#L83
+type SIOCADDMULTI = BadOpcode<0x8931>;
+// https://github.com/torvalds/linux/blob/4fbbed7872677b0a28ba8237169968171a61efbd/include/uapi/linux/sockios.h#L84
+type SIOCDELMULTI = BadOpcode<0x8932>;
+
+// https://github.com/torvalds/linux/blob/4fbbed7872677b0a28ba8237169968171a61efbd/include/uapi/linux/if_packet.h#L48
+const PACKET_ADD_MEMBERSHIP: c_int = 1;
+const PACKET_DROP_MEMBERSHIP: c_int = 2;
+// https://github.com/torvalds/linux/blob/4fbbed7872677b0a28ba8237169968171a61efbd/include/uapi/linux/if_packet.h#L296
+const PACKET_MR_MULTICAST: c_ushort = 0;
+
+// https://github.com/torvalds/linux/blob/4fbbed7872677b0a28ba8237169968171a61efbd/include/uapi/linux/if_packet.h#L289
+#[repr(C)]
+struct packet_mreq {
+    mr_ifindex: c_int,
+    mr_type: c_ushort,
+    mr_alen: c_ushort,
+    mr_address: [u8; 8],
+}
 
 pub fn ifreq_name(name: &str) -> [c_char; IF_NAMESIZE] {
     let mut ifreq_name = [b'\0' as c_char; IF_NAMESIZE];
@@ -51,6 +74,7 @@ pub fn ioctl_tunsetiff<Fd: AsFd>(
         HardwareType::Ieee802154 => {
             return Err(io::Error::other("TUN/TAP does not support IEEE 802.15.4"))
         }
+        HardwareType::Loopback => return Err(io::Error::other("TUN/TAP is not a loopback")),
     } | IFF_NO_PI as c_short;
 
     let ifreq = ifreq {
@@ -87,6 +111,82 @@ pub fn ioctl_siocgifindex<Fd: AsFd>(fd: Fd, ifreq_name: [c_char; IF_NAMESIZE]) -
     }
 }
 
+pub fn ioctl_siocsifhwaddr<Fd: AsFd>(
+    fd: Fd,
+    ifreq_name: [c_char; IF_NAMESIZE],
+    addr: [u8; 6],
+) -> io::Result<()> {
+    let mut sa_data = [0; 14];
+    for (data, byte) in sa_data.iter_mut().zip(addr) {
+        *data = byte as c_char;
+    }
+    let ifreq = ifreq {
+        ifr_name: ifreq_name,
+        ifr_ifru: __c_anonymous_ifr_ifru {
+            ifru_hwaddr: sockaddr {
+                sa_family: ARPHRD_ETHER,
+                sa_data,
+            },
+        },
+    };
+
+    unsafe { ioctl(fd, Setter::<SIOCSIFHWADDR, ifreq>::new(ifreq))? };
+
+    Ok(())
+}
+
+pub fn ioctl_siocgifhwaddr<Fd: AsFd>(
+    fd: Fd,
+    ifreq_name: [c_char; IF_NAMESIZE],
+) -> io::Result<[u8; 6]> {
+    let mut ifreq = ifreq {
+        ifr_name: ifreq_name,
+        ifr_ifru: __c_anonymous_ifr_ifru {
+            ifru_hwaddr: sockaddr {
+                sa_family: 0,
+                sa_data: [0; 14],
+            },
+        },
+    };
+
+    unsafe {
+        ioctl(fd, Updater::<SIOCGIFHWADDR, ifreq>::new(&mut ifreq))?;
+        let sa_data = ifreq.ifr_ifru.ifru_hwaddr.sa_data;
+        Ok(core::array::from_fn(|i| sa_data[i] as u8))
+    }
+}
+
+pub fn ioctl_siocmulti<Fd: AsFd>(
+    fd: Fd,
+    ifreq_name: [c_char; IF_NAMESIZE],
+    addr: [u8; 6],
+    add: bool,
+) -> io::Result<()> {
+    let mut sa_data = [0; 14];
+    for (data, byte) in sa_data.iter_mut().zip(addr) {
+        *data = byte as c_char;
+    }
+    let ifreq = ifreq {
+        ifr_name: ifreq_name,
+        ifr_ifru: __c_anonymous_ifr_ifru {
+            ifru_hwaddr: sockaddr {
+                sa_family: AF_UNSPEC as c_ushort,
+                sa_data,
+            },
+        },
+    };
+
+    unsafe {
+        if add {
+            ioctl(fd, Setter::<SIOCADDMULTI, ifreq>::new(ifreq))?
+        } else {
+            ioctl(fd, Setter::<SIOCDELMULTI, ifreq>::new(ifreq))?
+        }
+    };
+
+    Ok(())
+}
+
 pub fn bind_interface<Fd: AsFd>(
     fd: Fd,
     protocol: Protocol,
@@ -117,6 +217,42 @@ pub fn bind_interface<Fd: AsFd>(
     Ok(())
 }
 
+pub fn packet_membership<Fd: AsFd>(
+    fd: Fd,
+    ifindex: c_int,
+    addr: [u8; 6],
+    add: bool,
+) -> io::Result<()> {
+    let mut mr_address = [0; 8];
+    mr_address[..6].copy_from_slice(&addr);
+    let mreq = packet_mreq {
+        mr_ifindex: ifindex,
+        mr_type: PACKET_MR_MULTICAST,
+        mr_alen: 6,
+        mr_address,
+    };
+
+    let result = unsafe {
+        setsockopt(
+            fd.as_fd().as_raw_fd(),
+            SOL_PACKET,
+            if add {
+                PACKET_ADD_MEMBERSHIP
+            } else {
+                PACKET_DROP_MEMBERSHIP
+            },
+            &mreq as *const packet_mreq as *const _,
+            mem::size_of::<packet_mreq>() as socklen_t,
+        )
+    };
+
+    if result == -1 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(())
+}
+
 pub fn poll<Fd: AsFd>(fd: Fd, interest: Event, timeout: Option<Duration>) -> io::Result<Event> {
     let mut flags = PollFlags::empty();
     if interest.is_readable() {
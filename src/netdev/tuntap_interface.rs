@@ -1,11 +1,14 @@
 #![allow(unsafe_code)]
 
-use std::fs::OpenOptions;
-use std::io;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read};
+use std::os::raw::c_char;
 use std::time::Duration;
 
 use super::{sys, Event};
-use super::{HardwareType, NetDev};
+use super::{Capabilities, HardwareType, NetDev};
+use crate::header::link::EtherAddr;
+use libc::IF_NAMESIZE;
 use rustix::fd::OwnedFd;
 use rustix::fs::{fcntl_setfl, OFlags};
 use rustix::io::{read, write};
@@ -17,6 +20,8 @@ use rustix::net::{socket, AddressFamily, SocketType};
 #[derive(Debug)]
 pub struct TunTapInterface {
     fd: OwnedFd,
+    name: String,
+    ifreq_name: [c_char; IF_NAMESIZE],
     mtu: usize,
     hw_type: HardwareType,
 }
@@ -41,7 +46,52 @@ impl TunTapInterface {
         let socket = socket(AddressFamily::INET, SocketType::DGRAM, None)?;
         let mtu = sys::ioctl_siocgifmtu(&socket, ifreq_name)?;
 
-        Ok(TunTapInterface { fd, mtu, hw_type })
+        Ok(TunTapInterface {
+            fd,
+            name: name.into(),
+            ifreq_name,
+            mtu,
+            hw_type,
+        })
+    }
+
+    /// Returns the hardware address of a TAP interface.
+    pub fn hw_addr(&self) -> io::Result<EtherAddr> {
+        self.check_tap()?;
+        let socket = socket(AddressFamily::INET, SocketType::DGRAM, None)?;
+        sys::ioctl_siocgifhwaddr(&socket, self.ifreq_name).map(EtherAddr::new)
+    }
+
+    /// Sets the hardware address of a TAP interface to `addr`, replacing the random address
+    /// assigned by the kernel.
+    ///
+    /// Requires superuser privileges or `CAP_NET_ADMIN` capabilities.
+    pub fn set_hw_addr(&self, addr: EtherAddr) -> io::Result<()> {
+        self.check_tap()?;
+        let socket = socket(AddressFamily::INET, SocketType::DGRAM, None)?;
+        sys::ioctl_siocsifhwaddr(&socket, self.ifreq_name, addr.into())
+    }
+
+    /// Sets the hardware address of a TAP interface to a new random, locally administered
+    /// address, which is returned. See [`EtherAddr::from_random`].
+    ///
+    /// Requires superuser privileges or `CAP_NET_ADMIN` capabilities.
+    pub fn set_random_hw_addr(&self) -> io::Result<EtherAddr> {
+        let mut bytes = [0; 6];
+        File::open("/dev/urandom")?.read_exact(&mut bytes)?;
+        let addr = EtherAddr::from_random(bytes);
+        self.set_hw_addr(addr)?;
+        Ok(addr)
+    }
+
+    fn check_tap(&self) -> io::Result<()> {
+        if self.hw_type != HardwareType::EthernetII {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "TUN interface has no hardware address",
+            ));
+        }
+        Ok(())
     }
 }
 
@@ -58,6 +108,40 @@ impl NetDev for TunTapInterface {
         read(&self.fd, buf).map_err(io::Error::from)
     }
 
+    /// Adds `addr` to the multicast filter of a TAP interface. A TUN interface has no link layer
+    /// to filter, so `Ok(false)` is returned.
+    ///
+    /// Requires superuser privileges or `CAP_NET_ADMIN` capabilities.
+    fn add_multicast(&self, addr: EtherAddr) -> io::Result<bool> {
+        if self.check_tap().is_err() {
+            return Ok(false);
+        }
+        let socket = socket(AddressFamily::INET, SocketType::DGRAM, None)?;
+        sys::ioctl_siocmulti(&socket, self.ifreq_name, addr.into(), true)?;
+        Ok(true)
+    }
+
+    /// Removes `addr` from the multicast filter of a TAP interface. A TUN interface has no link
+    /// layer to filter, so `Ok(false)` is returned.
+    ///
+    /// Requires superuser privileges or `CAP_NET_ADMIN` capabilities.
+    fn remove_multicast(&self, addr: EtherAddr) -> io::Result<bool> {
+        if self.check_tap().is_err() {
+            return Ok(false);
+        }
+        let socket = socket(AddressFamily::INET, SocketType::DGRAM, None)?;
+        sys::ioctl_siocmulti(&socket, self.ifreq_name, addr.into(), false)?;
+        Ok(true)
+    }
+
+    #[inline]
+    fn capabilities(&self) -> Capabilities {
+        match self.hw_type {
+            HardwareType::EthernetII => Capabilities::MULTICAST_FILTER,
+            _ => Capabilities::new(),
+        }
+    }
+
     #[inline]
     fn mtu(&self) -> usize {
         self.mtu
@@ -72,4 +156,54 @@ impl NetDev for TunTapInterface {
     fn hw_type(&self) -> HardwareType {
         self.hw_type
     }
+
+    #[inline]
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[ignore = "requires CAP_NET_ADMIN"]
+    fn set_hw_addr() {
+        let tap = TunTapInterface::bind("tygress-tap0", HardwareType::EthernetII).unwrap();
+        let addr = EtherAddr::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x01]);
+        tap.set_hw_addr(addr).unwrap();
+        assert_eq!(tap.hw_addr().unwrap(), addr);
+
+        let addr = tap.set_random_hw_addr().unwrap();
+        assert!(addr.is_local() && addr.is_unicast());
+        assert_eq!(tap.hw_addr().unwrap(), addr);
+
+        let tun = TunTapInterface::bind("tygress-tun0", HardwareType::Opaque).unwrap();
+        assert_eq!(
+            tun.hw_addr().unwrap_err().kind(),
+            io::ErrorKind::Unsupported
+        );
+    }
+
+    #[test]
+    #[ignore = "requires CAP_NET_ADMIN"]
+    fn add_multicast() {
+        let tap = TunTapInterface::bind("tygress-tap1", HardwareType::EthernetII).unwrap();
+        assert!(tap.capabilities().multicast_filter());
+        let addr = EtherAddr::new([0x01, 0x00, 0x5e, 0x00, 0x00, 0xfb]);
+        assert!(tap.add_multicast(addr).unwrap());
+        assert!(tap.remove_multicast(addr).unwrap());
+
+        let tun = TunTapInterface::bind("tygress-tun2", HardwareType::Opaque).unwrap();
+        assert!(!tun.capabilities().multicast_filter());
+        assert!(!tun.add_multicast(addr).unwrap());
+    }
+
+    #[test]
+    #[ignore = "requires CAP_NET_ADMIN"]
+    fn name() {
+        let tun = TunTapInterface::bind("tygress-tun1", HardwareType::Opaque).unwrap();
+        assert_eq!(tun.name(), "tygress-tun1");
+    }
 }
@@ -0,0 +1,244 @@
+//! [`PmtuCache`] for Path MTU Discovery
+//!
+//! [`PmtuCache`] remembering the reduced MTU of paths to IPv4 destinations, learned from ICMPv4
+//! "Fragmentation Needed" errors. See [RFC 1191].
+//!
+//! [RFC 1191]: https://tools.ietf.org/html/rfc1191
+use core::time::Duration;
+
+use crate::header::internet::{Icmpv4, Icmpv4Error, Ipv4Addr};
+use crate::time::Instant;
+
+/// A fixed-capacity cache of path MTUs by destination. [Read more][RFC 1191]
+///
+/// Packets sent with the Don't Fragment flag that are too large for a link along the path are
+/// dropped by the router in front of that link, which reports the MTU of the next hop back in an
+/// ICMPv4 error. The cache lowers the path MTU of the destination accordingly. Callers sizing
+/// future packets, such as TCP segments with [`clamp_mss`][crate::nat::clamp_mss] or UDP payloads,
+/// look up [`path_mtu`][PmtuCache::path_mtu] so that they fit the path. Entries age out after a timeout, since the path may change and allow a larger
+/// MTU again.
+///
+/// When the cache is full, the entry updated least recently is replaced.
+///
+/// [RFC 1191]: https://tools.ietf.org/html/rfc1191#section-6.3
+#[derive(Debug, Clone)]
+pub struct PmtuCache<const N: usize> {
+    entries: [Option<PmtuEntry>; N],
+    timeout: Duration,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct PmtuEntry {
+    dst: Ipv4Addr,
+    mtu: u16,
+    updated: Instant,
+}
+
+impl PmtuEntry {
+    fn is_expired(&self, now: Instant, timeout: Duration) -> bool {
+        now - self.updated > timeout
+    }
+}
+
+impl<const N: usize> PmtuCache<N> {
+    /// The smallest MTU every IPv4 link must support. Reported MTUs are never lowered below it.
+    pub const MIN_MTU: u16 = 68;
+    /// The timeout recommended by RFC 1191, after which a larger path MTU may be tried again.
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+    /// Constructs an empty cache expiring entries not lowered for longer than `timeout`.
+    #[inline]
+    pub const fn new(timeout: Duration) -> Self {
+        PmtuCache {
+            entries: [None; N],
+            timeout,
+        }
+    }
+
+    /// Returns the number of cached paths, including expired paths not yet forgotten by
+    /// [`expire`][PmtuCache::expire].
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.iter().flatten().count()
+    }
+
+    /// Returns `true` if no paths are cached.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.iter().all(Option::is_none)
+    }
+
+    /// Forgets paths not lowered for longer than the timeout at time `now`.
+    pub fn expire(&mut self, now: Instant) {
+        for slot in &mut self.entries {
+            if slot.is_some_and(|entry| entry.is_expired(now, self.timeout)) {
+                *slot = None;
+            }
+        }
+    }
+
+    /// Returns the cached MTU of the path to `dst` at time `now`, or [`None`] if it is unknown or
+    /// expired.
+    pub fn get(&self, dst: Ipv4Addr, now: Instant) -> Option<u16> {
+        self.entries
+            .iter()
+            .flatten()
+            .find(|entry| entry.dst == dst && !entry.is_expired(now, self.timeout))
+            .map(|entry| entry.mtu)
+    }
+
+    /// Returns the MTU to use for packets to `dst` at time `now`, the smaller of the cached path
+    /// MTU and `link_mtu`.
+    #[inline]
+    pub fn path_mtu(&self, dst: Ipv4Addr, link_mtu: usize, now: Instant) -> usize {
+        match self.get(dst, now) {
+            Some(mtu) => link_mtu.min(mtu as usize),
+            None => link_mtu,
+        }
+    }
+
+    /// Lowers the path MTU of `dst` to `mtu` at time `now`. Returns `true` if the cached MTU was
+    /// lowered, or `false` if `mtu` is not smaller than what is already cached or the cache has no
+    /// room for entries at all.
+    ///
+    /// `mtu` is raised to [`MIN_MTU`][PmtuCache::MIN_MTU] if smaller.
+    pub fn lower(&mut self, dst: Ipv4Addr, mtu: u16, now: Instant) -> bool {
+        let mtu = mtu.max(Self::MIN_MTU);
+        if let Some(entry) = self
+            .entries
+            .iter_mut()
+            .flatten()
+            .find(|entry| entry.dst == dst)
+        {
+            if !entry.is_expired(now, self.timeout) && entry.mtu <= mtu {
+                return false;
+            }
+            *entry = PmtuEntry {
+                dst,
+                mtu,
+                updated: now,
+            };
+            return true;
+        }
+
+        let entry = Some(PmtuEntry {
+            dst,
+            mtu,
+            updated: now,
+        });
+        // empty slots sort before any entry, so they are filled before the oldest is replaced
+        let slot = self
+            .entries
+            .iter_mut()
+            .min_by_key(|slot| slot.map(|entry| entry.updated));
+        match slot {
+            Some(slot) => {
+                *slot = entry;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Lowers the path MTU of the destination of the original datagram carried by an ICMPv4
+    /// "Fragmentation Needed" error `icmp` at time `now`. See [`lower`][PmtuCache::lower].
+    ///
+    /// Returns `false` for any other message, or if the router did not report the MTU of the next
+    /// hop.
+    pub fn update(&mut self, icmp: &Icmpv4, error: &Icmpv4Error<'_>, now: Instant) -> bool {
+        match icmp.next_hop_mtu() {
+            Some(mtu) if mtu != 0 => self.lower(error.header().dst(), mtu, now),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DST: Ipv4Addr = Ipv4Addr::new([8, 8, 8, 8]);
+
+    // Destination Unreachable, Fragmentation Needed with a next-hop MTU of 1400, carrying an IPv4
+    // packet 192.168.0.10 → 8.8.8.8
+    const FRAG_NEEDED: [u8; 36] = [
+        0x03, 0x04, 0x00, 0x00, 0x00, 0x00, 0x05, 0x78, 0x45, 0x00, 0x05, 0xdc, 0x00, 0x00, 0x40,
+        0x00, 0x40, 0x11, 0x00, 0x00, 0xc0, 0xa8, 0x00, 0x0a, 0x08, 0x08, 0x08, 0x08, 0x13, 0x88,
+        0x00, 0x35, 0x05, 0xc8, 0x00, 0x00,
+    ];
+
+    #[test]
+    fn frag_needed_lowers_pmtu() {
+        let mut cache = PmtuCache::<4>::new(PmtuCache::<4>::DEFAULT_TIMEOUT);
+        let now = Instant::from_millis(1_000);
+        assert_eq!(cache.path_mtu(DST, 1500, now), 1500);
+
+        let (icmp, payload) = Icmpv4::from_bytes(&FRAG_NEEDED).unwrap();
+        let (error, _) = Icmpv4Error::from_bytes(payload).unwrap();
+        assert!(cache.update(icmp, &error, now));
+        assert_eq!(cache.get(DST, now), Some(1400));
+        assert_eq!(cache.path_mtu(DST, 1500, now), 1400);
+        assert_eq!(cache.path_mtu(DST, 1280, now), 1280);
+
+        // a larger MTU does not raise the cached one
+        assert!(!cache.lower(DST, 1450, now));
+        assert!(cache.lower(DST, 1300, now));
+        assert_eq!(cache.get(DST, now), Some(1300));
+        assert!(cache.lower(DST, 0, now));
+        assert_eq!(cache.get(DST, now), Some(68));
+    }
+
+    #[test]
+    fn pmtu_ages_out() {
+        let mut cache = PmtuCache::<4>::new(Duration::from_secs(600));
+        let now = Instant::from_millis(0);
+        cache.lower(DST, 1400, now);
+
+        let later = now + Duration::from_secs(601);
+        assert_eq!(cache.get(DST, later), None);
+        assert_eq!(cache.len(), 1);
+        cache.expire(later);
+        assert!(cache.is_empty());
+
+        // an expired entry is replaced even by a larger MTU
+        cache.lower(DST, 1200, now);
+        assert!(cache.lower(DST, 1400, later));
+        assert_eq!(cache.get(DST, later), Some(1400));
+    }
+
+    #[test]
+    fn full_cache_replaces_oldest() {
+        let mut cache = PmtuCache::<2>::new(PmtuCache::<2>::DEFAULT_TIMEOUT);
+        let a = Ipv4Addr::new([10, 0, 0, 1]);
+        let b = Ipv4Addr::new([10, 0, 0, 2]);
+        cache.lower(a, 1400, Instant::from_millis(0));
+        cache.lower(b, 1400, Instant::from_millis(1));
+        cache.lower(DST, 1400, Instant::from_millis(2));
+
+        let now = Instant::from_millis(3);
+        assert_eq!(cache.get(a, now), None);
+        assert_eq!(cache.get(b, now), Some(1400));
+        assert_eq!(cache.get(DST, now), Some(1400));
+    }
+
+    #[test]
+    fn zero_capacity() {
+        let mut cache = PmtuCache::<0>::new(PmtuCache::<0>::DEFAULT_TIMEOUT);
+        let now = Instant::from_millis(0);
+        assert!(!cache.lower(DST, 1400, now));
+        assert_eq!(cache.get(DST, now), None);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn other_errors_ignored() {
+        let mut cache = PmtuCache::<4>::new(PmtuCache::<4>::DEFAULT_TIMEOUT);
+        let mut bytes = FRAG_NEEDED;
+        // port unreachable
+        bytes[1] = 0x03;
+        let (icmp, payload) = Icmpv4::from_bytes(&bytes).unwrap();
+        let (error, _) = Icmpv4Error::from_bytes(payload).unwrap();
+        assert!(!cache.update(icmp, &error, Instant::EPOCH));
+        assert!(cache.is_empty());
+    }
+}
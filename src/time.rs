@@ -0,0 +1,219 @@
+//! Monotonic time without `std`.
+//!
+//! Timers, such as retransmission and cache expiry, measure time with an [`Instant`] read from a
+//! [`Clock`]. An [`Instant`] is only meaningful relative to other instants of the same clock, so any
+//! fixed point in time, such as boot, can serve as the epoch. If the `std` feature is enabled,
+//! [`StdClock`] reads [`std::time::Instant`].
+
+use core::fmt;
+use core::ops::{Add, AddAssign, Sub, SubAssign};
+use core::time::Duration;
+
+/// A measurement of a monotonically nondecreasing clock, as a [`Duration`] since the epoch of the
+/// clock.
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Default)]
+pub struct Instant(Duration);
+
+impl Instant {
+    /// The epoch of the clock.
+    pub const EPOCH: Instant = Instant(Duration::ZERO);
+
+    /// Constructs an instant `since_epoch` after the epoch of the clock.
+    #[inline]
+    pub const fn from_duration(since_epoch: Duration) -> Self {
+        Instant(since_epoch)
+    }
+
+    /// Constructs an instant `millis` milliseconds after the epoch of the clock.
+    #[inline]
+    pub const fn from_millis(millis: u64) -> Self {
+        Instant(Duration::from_millis(millis))
+    }
+
+    /// Returns the time elapsed from the epoch of the clock to this instant.
+    #[inline]
+    pub const fn duration_since_epoch(&self) -> Duration {
+        self.0
+    }
+
+    /// Returns the time elapsed from `earlier` to this instant or [`None`] if `earlier` is later.
+    #[inline]
+    pub const fn checked_duration_since(&self, earlier: Instant) -> Option<Duration> {
+        self.0.checked_sub(earlier.0)
+    }
+
+    /// Returns the time elapsed from `earlier` to this instant or zero if `earlier` is later.
+    #[inline]
+    pub const fn saturating_duration_since(&self, earlier: Instant) -> Duration {
+        self.0.saturating_sub(earlier.0)
+    }
+
+    /// Returns the instant `duration` later or [`None`] on overflow.
+    #[inline]
+    pub const fn checked_add(&self, duration: Duration) -> Option<Instant> {
+        match self.0.checked_add(duration) {
+            Some(since_epoch) => Some(Instant(since_epoch)),
+            None => None,
+        }
+    }
+
+    /// Returns the instant `duration` earlier or [`None`] if it would precede the epoch.
+    #[inline]
+    pub const fn checked_sub(&self, duration: Duration) -> Option<Instant> {
+        match self.0.checked_sub(duration) {
+            Some(since_epoch) => Some(Instant(since_epoch)),
+            None => None,
+        }
+    }
+}
+
+impl Add<Duration> for Instant {
+    type Output = Instant;
+
+    /// # Panics
+    ///
+    /// Panics on overflow. See [`Instant::checked_add`].
+    #[inline]
+    fn add(self, rhs: Duration) -> Instant {
+        self.checked_add(rhs)
+            .expect("overflow when adding duration to instant")
+    }
+}
+
+impl AddAssign<Duration> for Instant {
+    #[inline]
+    fn add_assign(&mut self, rhs: Duration) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub<Duration> for Instant {
+    type Output = Instant;
+
+    /// # Panics
+    ///
+    /// Panics if the result would precede the epoch. See [`Instant::checked_sub`].
+    #[inline]
+    fn sub(self, rhs: Duration) -> Instant {
+        self.checked_sub(rhs)
+            .expect("overflow when subtracting duration from instant")
+    }
+}
+
+impl SubAssign<Duration> for Instant {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Duration) {
+        *self = *self - rhs;
+    }
+}
+
+impl Sub<Instant> for Instant {
+    type Output = Duration;
+
+    /// Returns the time elapsed from `rhs` to this instant, saturating to zero like
+    /// [`std::time::Instant`].
+    #[inline]
+    fn sub(self, rhs: Instant) -> Duration {
+        self.saturating_duration_since(rhs)
+    }
+}
+
+impl fmt::Display for Instant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{:03}s", self.0.as_secs(), self.0.subsec_millis())
+    }
+}
+
+/// A source of [`Instant`]s.
+///
+/// Successive calls to [`now`][Clock::now] must never go backwards.
+pub trait Clock {
+    /// Returns the current time.
+    fn now(&self) -> Instant;
+}
+
+#[cfg(feature = "std")]
+pub use std_clock::StdClock;
+
+#[cfg(feature = "std")]
+mod std_clock {
+    use super::{Clock, Instant};
+
+    /// A [`Clock`] reading [`std::time::Instant`], with its epoch at the creation of the clock.
+    #[derive(Debug, Clone, Copy)]
+    pub struct StdClock {
+        epoch: std::time::Instant,
+    }
+
+    impl StdClock {
+        /// Creates a clock with its epoch at the current time.
+        #[inline]
+        pub fn new() -> Self {
+            StdClock {
+                epoch: std::time::Instant::now(),
+            }
+        }
+    }
+
+    impl Default for StdClock {
+        #[inline]
+        fn default() -> Self {
+            StdClock::new()
+        }
+    }
+
+    impl Clock for StdClock {
+        #[inline]
+        fn now(&self) -> Instant {
+            Instant::from_duration(self.epoch.elapsed())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let start = Instant::from_millis(1_500);
+        let later = start + Duration::from_millis(250);
+        assert_eq!(later, Instant::from_millis(1_750));
+        assert_eq!(later - start, Duration::from_millis(250));
+        assert_eq!(later - Duration::from_millis(1_750), Instant::EPOCH);
+
+        // elapsed time saturates like std
+        assert_eq!(start - later, Duration::ZERO);
+        assert_eq!(start.checked_duration_since(later), None);
+
+        let mut instant = start;
+        instant += Duration::from_secs(1);
+        instant -= Duration::from_millis(500);
+        assert_eq!(instant, Instant::from_millis(2_000));
+
+        assert_eq!(Instant::EPOCH.checked_sub(Duration::from_nanos(1)), None);
+        assert_eq!(
+            Instant::from_duration(Duration::MAX).checked_add(Duration::from_nanos(1)),
+            None
+        );
+        assert_eq!(start.to_string(), "1.500s");
+    }
+
+    #[test]
+    fn ordering() {
+        let a = Instant::from_millis(10);
+        let b = Instant::from_millis(20);
+        assert!(Instant::EPOCH < a && a < b);
+        assert_eq!(a.max(b), b);
+        assert_eq!(Instant::default(), Instant::EPOCH);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn std_clock() {
+        let clock = StdClock::new();
+        let a = clock.now();
+        let b = clock.now();
+        assert!(a <= b);
+    }
+}